@@ -0,0 +1,56 @@
+use analysis::conversion_type::ConversionType;
+use analysis::imports::Imports;
+use analysis::namespaces;
+use analysis::rust_type::used_rust_type;
+use config::gobjects::GObject;
+use env::Env;
+use library::{Alias, Type};
+
+/// The generated aliases with their configs.
+pub fn generated(env: &Env) -> Vec<(&Alias, &GObject)> {
+    env.config
+        .objects
+        .values()
+        .filter(|c| {
+            c.status.need_generate() && c.type_id.map_or(false, |tid| tid.ns_id == namespaces::MAIN)
+        })
+        .filter_map(|c| match *env.library.type_(c.type_id.unwrap()) {
+            Type::Alias(ref alias) => Some((alias, c)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Only aliases of numbers passed as they are can be wrapped.
+pub fn can_be_newtype(env: &Env, alias: &Alias) -> bool {
+    match *env.library.type_(alias.typ) {
+        Type::Fundamental(_) => ConversionType::of(env, alias.typ) == ConversionType::Direct,
+        _ => false,
+    }
+}
+
+/// Imports of the module holding all aliases.
+pub fn analyze_imports(env: &Env) -> Imports {
+    let aliases = generated(env);
+    let mut imports = Imports::new();
+
+    for &(alias, config) in &aliases {
+        if let Ok(s) = used_rust_type(env, alias.typ) {
+            imports.add_used_type(&s, config.version.or(alias.version));
+        }
+    }
+    if aliases
+        .iter()
+        .any(|&(alias, config)| config.newtype && can_be_newtype(env, alias))
+    {
+        imports.add("ffi", None);
+        imports.add("glib::translate::*", None);
+    }
+
+    // Aliases of aliases are defined in the same module
+    for &(alias, _) in &aliases {
+        imports.remove(&alias.name);
+    }
+    imports.clean_glib(env);
+    imports
+}
@@ -0,0 +1,96 @@
+use analysis::functions::Info as FuncInfo;
+use analysis::imports::Imports;
+use config::gobjects::GObject;
+use env::Env;
+use library::{self, Fundamental, Transfer, Type};
+use version::Version;
+
+/// Accessor used to implement `AsRef<str>` for a string wrapper type.
+#[derive(Debug)]
+pub struct Info {
+    pub glib_name: String,
+    pub version: Option<Version>,
+}
+
+pub fn analyze(
+    env: &Env,
+    obj: &GObject,
+    functions: &[FuncInfo],
+    imports: &mut Imports,
+) -> Option<Info> {
+    let accessor = match obj.as_ref_str {
+        Some(ref accessor) => accessor,
+        None => return None,
+    };
+
+    find_accessor(env, obj, "AsRef<str>", accessor, functions, false, imports).map(|func| Info {
+        glib_name: func.glib_name.clone(),
+        version: func.version,
+    })
+}
+
+/// Finds the method `accessor` returning a borrowed string, used to implement
+/// `trait_name`. The string is read with `CStr`.
+pub fn find_accessor<'a>(
+    env: &Env,
+    obj: &GObject,
+    trait_name: &str,
+    accessor: &str,
+    functions: &'a [FuncInfo],
+    allow_nullable: bool,
+    imports: &mut Imports,
+) -> Option<&'a FuncInfo> {
+    let func = match functions.iter().find(|f| f.name == accessor) {
+        Some(func) => func,
+        None => {
+            error!(
+                "Accessor `{}` for {} of {} not found",
+                accessor,
+                trait_name,
+                obj.name
+            );
+            return None;
+        }
+    };
+
+    if let Err(msg) = check_accessor(env, func, allow_nullable) {
+        error!(
+            "Can't implement {} for {} with `{}`: {}",
+            trait_name,
+            obj.name,
+            accessor,
+            msg
+        );
+        return None;
+    }
+
+    imports.add("std::ffi::CStr", func.version);
+    Some(func)
+}
+
+fn check_accessor(
+    env: &Env,
+    func: &FuncInfo,
+    allow_nullable: bool,
+) -> Result<(), &'static str> {
+    if func.kind != library::FunctionKind::Method || func.parameters.c_parameters.len() != 1
+        || !func.outs.is_empty()
+    {
+        return Err("accessor must be a method without parameters");
+    }
+    let ret = match func.ret.parameter {
+        Some(ref ret) => ret,
+        None => return Err("accessor returns nothing"),
+    };
+    match *env.type_(ret.typ) {
+        Type::Fundamental(Fundamental::Utf8) => (),
+        _ => return Err("accessor doesn't return a string"),
+    }
+    if *ret.nullable && !allow_nullable {
+        return Err("accessor is nullable");
+    }
+    if ret.transfer != Transfer::None {
+        return Err("accessor doesn't return a borrowed string");
+    }
+    Ok(())
+}
@@ -5,9 +5,10 @@ use std::vec::Vec;
 use consts::TYPE_PARAMETERS_START;
 use env::Env;
 use analysis::imports::Imports;
-use analysis::function_parameters::CParameter;
+use analysis::function_parameters::{is_bytes, is_cancellable, CParameter};
 use analysis::rust_type::bounds_rust_type;
-use library::{Function, Fundamental, Nullable, ParameterDirection, Type, TypeId};
+use config::gobjects::GObject;
+use library::{Class, Function, Fundamental, Nullable, ParameterDirection, Type, TypeId};
 use traits::IntoString;
 
 #[derive(Clone, Eq, Debug, PartialEq)]
@@ -101,7 +102,10 @@ impl Bounds {
         par: &CParameter,
     ) -> Option<String> {
         let mut ret = None;
-        if !par.instance_parameter && par.direction != ParameterDirection::Out {
+        if !par.instance_parameter && par.direction != ParameterDirection::Out
+            && !(par.direction == ParameterDirection::In && is_bytes(env, par.typ))
+            && par.date_time.is_none()
+        {
             if let Some(bound_type) = Bounds::type_for(env, par.typ, par.nullable) {
                 ret = Some(Bounds::get_to_glib_extra(&bound_type));
                 let type_name = bounds_rust_type(env, par.typ);
@@ -117,18 +121,26 @@ impl Bounds {
     }
 
     pub fn type_for(env: &Env, type_id: TypeId, nullable: Nullable) -> Option<BoundType> {
+        let bound_type = Bounds::type_for_inner(env, type_id, nullable);
+        if env.config.into_option_params {
+            bound_type
+        } else {
+            without_into(bound_type)
+        }
+    }
+    fn type_for_inner(env: &Env, type_id: TypeId, nullable: Nullable) -> Option<BoundType> {
         use self::BoundType::*;
         match *env.library.type_(type_id) {
             Type::Fundamental(Fundamental::Filename) => Some(AsRef(None)),
             Type::Fundamental(Fundamental::Utf8) if *nullable => Some(Into(Some('_'), None)),
             Type::Class(..) if !*nullable => {
-                if env.class_hierarchy.subtypes(type_id).next().is_some() {
+                if Bounds::need_is_a(env, type_id) {
                     Some(IsA(None))
                 } else {
                     None
                 }
             }
-            Type::Class(..) => if env.class_hierarchy.subtypes(type_id).next().is_some() {
+            Type::Class(..) => if Bounds::need_is_a(env, type_id) {
                 Some(Into(Some('_'), Some(Box::new(IsA(None)))))
             } else {
                 Some(Into(Some('_'), None))
@@ -141,6 +153,19 @@ impl Bounds {
             _ => Some(Into(Some('_'), None)),
         }
     }
+    fn need_is_a(env: &Env, type_id: TypeId) -> bool {
+        if env.config.non_final_is_a {
+            return match *env.library.type_(type_id) {
+                Type::Class(ref klass) => {
+                    let obj = env.config.objects.get(&type_id.full_name(&env.library));
+                    !is_final_class(klass, obj)
+                }
+                _ => true,
+            };
+        }
+        // Cancellables have no subtypes in the GIR, but are subclassed by users
+        env.class_hierarchy.subtypes(type_id).next().is_some() || is_cancellable(env, type_id)
+    }
     fn get_to_glib_extra(bound_type: &BoundType) -> String {
         use self::BoundType::*;
         match *bound_type {
@@ -233,6 +258,21 @@ impl Bounds {
     }
 }
 
+/// Subclasses of non-final classes are accepted through `IsA` bounds without upcasting.
+/// The `final` object option overrides the GIR, which rarely marks classes final.
+fn is_final_class(klass: &Class, obj: Option<&GObject>) -> bool {
+    obj.and_then(|obj| obj.final_type).unwrap_or(klass.final_type)
+}
+
+/// Without `into_option_params` nullable parameters are taken as plain `Option`s,
+/// still generic over the wrapped bound if any.
+fn without_into(bound_type: Option<BoundType>) -> Option<BoundType> {
+    match bound_type {
+        Some(BoundType::Into(_, inner)) => inner.map(|inner| *inner),
+        bound_type => bound_type,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -271,4 +311,35 @@ mod tests {
         assert_eq!(bounds.get_parameter_alias_info("b"), Some(('Q', typ)));
         assert_eq!(bounds.get_parameter_alias_info("c"), None);
     }
+
+    #[test]
+    fn final_class_from_gir_or_config() {
+        let mut klass = Class::default();
+        let mut obj = GObject::default();
+        assert!(!is_final_class(&klass, None));
+        assert!(!is_final_class(&klass, Some(&obj)));
+        klass.final_type = true;
+        assert!(is_final_class(&klass, None));
+        assert!(is_final_class(&klass, Some(&obj)));
+        obj.final_type = Some(false);
+        assert!(!is_final_class(&klass, Some(&obj)));
+        klass.final_type = false;
+        obj.final_type = Some(true);
+        assert!(is_final_class(&klass, Some(&obj)));
+    }
+
+    #[test]
+    fn into_bounds_can_be_switched_off() {
+        let into = BoundType::Into(Some('_'), Some(Box::new(BoundType::IsA(None))));
+        assert_eq!(without_into(Some(into)), Some(BoundType::IsA(None)));
+        assert_eq!(without_into(Some(BoundType::Into(None, None))), None);
+        assert_eq!(
+            without_into(Some(BoundType::IsA(None))),
+            Some(BoundType::IsA(None))
+        );
+        assert_eq!(
+            without_into(Some(BoundType::AsRef(None))),
+            Some(BoundType::AsRef(None))
+        );
+    }
 }
@@ -116,7 +116,15 @@ fn analyze_property(
         let mut is_into = false;
         let mut bounds_str = String::new();
         let dir = library::ParameterDirection::In;
-        let set_params = if let Some(bound) = Bounds::type_for(env, typ, nullable) {
+        // Nullable values are only generic through `Into`
+        let bound = Bounds::type_for(env, typ, nullable).and_then(|bound| {
+            if *nullable && !bound.is_into() {
+                None
+            } else {
+                Some(bound)
+            }
+        });
+        let set_params = if let Some(bound) = bound {
             is_into = bound.is_into();
             let r_type = bounds_rust_type(env, typ).into_string();
             let mut bounds = Bounds::default();
@@ -75,4 +75,72 @@ impl Info {
             None => &[],
         }
     }
+
+    /// Whether `tid` descends from `GObject.InitiallyUnowned`, i.e. its
+    /// constructors return floating references that must be sunk.
+    pub fn is_initially_unowned(&self, library: &Library, tid: TypeId) -> bool {
+        match library.find_type(0, "GObject.InitiallyUnowned") {
+            Some(unowned_tid) => self.supertypes(tid).contains(&unowned_tid),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_library() -> (Library, TypeId, TypeId, TypeId) {
+        let mut library = Library::new("Gtk");
+        let gobject_ns = library.add_namespace("GObject");
+        let object = library.add_type(
+            gobject_ns,
+            "Object",
+            Type::Class(Class {
+                name: "Object".into(),
+                c_type: "GObject".into(),
+                ..Default::default()
+            }),
+        );
+        let unowned = library.add_type(
+            gobject_ns,
+            "InitiallyUnowned",
+            Type::Class(Class {
+                name: "InitiallyUnowned".into(),
+                c_type: "GInitiallyUnowned".into(),
+                parent: Some(object),
+                ..Default::default()
+            }),
+        );
+        let widget = library.add_type(
+            MAIN_NAMESPACE,
+            "Widget",
+            Type::Class(Class {
+                name: "Widget".into(),
+                c_type: "GtkWidget".into(),
+                parent: Some(unowned),
+                ..Default::default()
+            }),
+        );
+        let window_group = library.add_type(
+            MAIN_NAMESPACE,
+            "WindowGroup",
+            Type::Class(Class {
+                name: "WindowGroup".into(),
+                c_type: "GtkWindowGroup".into(),
+                parent: Some(object),
+                ..Default::default()
+            }),
+        );
+        (library, unowned, widget, window_group)
+    }
+
+    #[test]
+    fn initially_unowned_detected_through_parents() {
+        let (library, unowned, widget, window_group) = make_library();
+        let info = run(&library);
+        assert!(info.is_initially_unowned(&library, widget));
+        assert!(!info.is_initially_unowned(&library, window_group));
+        assert!(!info.is_initially_unowned(&library, unowned));
+    }
 }
@@ -65,7 +65,16 @@ impl ConversionType {
                 UIntPtr => ConversionType::Direct,
                 Unsupported => ConversionType::Unknown,
             },
-            Alias(ref alias) => ConversionType::of(env, alias.typ),
+            Alias(ref alias) => {
+                let inner = ConversionType::of(env, alias.typ);
+                match env.config.objects.get(&type_id.full_name(library)) {
+                    // Newtypes are converted with `ToGlib`/`FromGlib`
+                    Some(&GObject { newtype: true, .. }) if inner == ConversionType::Direct => {
+                        ConversionType::Scalar
+                    }
+                    _ => inner,
+                }
+            }
             Bitfield(_) => ConversionType::Scalar,
             Record(_) => ConversionType::Pointer,
             Union(_) => ConversionType::Pointer,
@@ -73,6 +82,11 @@ impl ConversionType {
             Interface(_) => ConversionType::Pointer,
             Class(_) => ConversionType::Pointer,
             CArray(_) => ConversionType::Pointer,
+            FixedArray(inner_tid, _)
+                if ConversionType::of(env, inner_tid) == ConversionType::Direct =>
+            {
+                ConversionType::Direct
+            }
             List(_) => ConversionType::Pointer,
             SList(_) => ConversionType::Pointer,
             _ => ConversionType::Unknown,
@@ -0,0 +1,32 @@
+use analysis::as_ref_str::find_accessor;
+use analysis::functions::Info as FuncInfo;
+use analysis::imports::Imports;
+use config::gobjects::GObject;
+use env::Env;
+use version::Version;
+
+/// Accessor used to implement `PartialEq<str>` for a string wrapper type.
+#[derive(Debug)]
+pub struct Info {
+    pub glib_name: String,
+    pub nullable: bool,
+    pub version: Option<Version>,
+}
+
+pub fn analyze(
+    env: &Env,
+    obj: &GObject,
+    functions: &[FuncInfo],
+    imports: &mut Imports,
+) -> Option<Info> {
+    let accessor = match obj.eq_str {
+        Some(ref accessor) => accessor,
+        None => return None,
+    };
+
+    find_accessor(env, obj, "PartialEq<str>", accessor, functions, true, imports).map(|func| Info {
+        glib_name: func.glib_name.clone(),
+        nullable: func.ret.parameter.as_ref().map_or(false, |ret| *ret.nullable),
+        version: func.version,
+    })
+}
@@ -0,0 +1,116 @@
+use analysis::functions::Info as FuncInfo;
+use analysis::imports::Imports;
+use analysis::out_parameters::Mode;
+use config::gobjects::GObject;
+use env::Env;
+use library::{self, Fundamental, Type};
+use version::Version;
+
+/// Constructor used to implement `FromStr`.
+#[derive(Debug)]
+pub struct Info {
+    pub func_name: String,
+    /// The parser reports failures as `glib::Error`, otherwise it returns `None`
+    /// and a unit error type is generated
+    pub throws: bool,
+    pub version: Option<Version>,
+}
+
+pub fn analyze(
+    env: &Env,
+    obj: &GObject,
+    functions: &[FuncInfo],
+    imports: &mut Imports,
+) -> Option<Info> {
+    let parser = match obj.from_str {
+        Some(ref parser) => parser,
+        None => return None,
+    };
+
+    let func = match functions.iter().find(|f| f.name == *parser) {
+        Some(func) => func,
+        None => {
+            error!("Parser `{}` for FromStr of {} not found", parser, obj.name);
+            return None;
+        }
+    };
+
+    let throws = match check_parser(env, func) {
+        Ok(throws) => throws,
+        Err(msg) => {
+            error!(
+                "Can't implement FromStr for {} with `{}`: {}",
+                obj.name,
+                parser,
+                msg
+            );
+            return None;
+        }
+    };
+
+    imports.add("std::str::FromStr", func.version);
+    if !throws {
+        imports.add("std::fmt", func.version);
+        imports.add("std::error", func.version);
+    }
+
+    Some(Info {
+        func_name: func.name.clone(),
+        throws: throws,
+        version: func.version,
+    })
+}
+
+/// Returns whether the parser throws.
+fn check_parser(env: &Env, func: &FuncInfo) -> Result<bool, &'static str> {
+    if func.kind == library::FunctionKind::Method {
+        return Err("parser must not be a method");
+    }
+    let params = &func.parameters.rust_parameters;
+    if params.len() != 1 || params[0].allow_none {
+        return Err("parser must take exactly one string");
+    }
+    match *env.type_(params[0].typ) {
+        Type::Fundamental(Fundamental::Utf8) => (),
+        _ => return Err("parser must take exactly one string"),
+    }
+    let nullable = match func.outs.mode {
+        Mode::Throws(true) => {
+            if func.outs.iter().skip(1).any(|p| !p.is_error) {
+                return Err("parser must not have out parameters");
+            }
+            *func.outs.params[0].nullable
+        }
+        Mode::None => match func.ret.parameter {
+            Some(ref ret) => *ret.nullable,
+            None => return Err("parser returns nothing"),
+        },
+        Mode::Throws(false) => return Err("parser returns nothing"),
+        _ => return Err("parser must not have out parameters"),
+    };
+    error_channel(func.outs.mode, nullable)
+}
+
+fn error_channel(mode: Mode, nullable: bool) -> Result<bool, &'static str> {
+    match (mode, nullable) {
+        (Mode::Throws(true), false) => Ok(true),
+        (Mode::Throws(_), _) => Err("parser returns an optional value or none with an error"),
+        (Mode::None, true) => Ok(false),
+        (Mode::None, false) => Err("parser can't fail, implement `From<&str>` instead"),
+        _ => Err("parser must not have out parameters"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser_error_channel() {
+        assert_eq!(error_channel(Mode::Throws(true), false), Ok(true));
+        assert_eq!(error_channel(Mode::None, true), Ok(false));
+        assert!(error_channel(Mode::Throws(true), true).is_err());
+        assert!(error_channel(Mode::None, false).is_err());
+        assert!(error_channel(Mode::Combined, true).is_err());
+    }
+}
@@ -1,14 +1,16 @@
 use std::collections::HashMap;
 
 use config;
+use config::TimeIntegration;
 use config::parameter_matchable::ParameterMatchable;
 use env::Env;
 use library;
 use nameutil;
 use super::conversion_type::ConversionType;
-use super::rust_type::rust_type;
+use super::rust_type::{is_narrowing, rust_type, size_cast};
 use super::ref_mode::RefMode;
 use super::out_parameters::can_as_return;
+use super::return_value::{is_date_time, is_time_t};
 use traits::IntoString;
 
 //TODO: remove unused fields
@@ -31,6 +33,11 @@ pub struct CParameter {
     pub transfer: library::Transfer,
     pub caller_allocates: bool,
     pub is_error: bool,
+    pub duration_us: bool,
+    /// `GLib.DateTime` input taken as a Rust time type
+    pub date_time: Option<TimeIntegration>,
+    /// `time_t` input taken as a Rust time type
+    pub time_t: Option<TimeIntegration>,
 
     //analysis fields
     pub ref_mode: RefMode,
@@ -39,6 +46,30 @@ pub struct CParameter {
 #[derive(Clone, Debug)]
 pub enum TransformationType {
     ToGlibDirect { name: String },
+    ToGlibCast {
+        name: String,
+        type_: String,
+        checked: bool,
+    },
+    ToGlibFixedArray { name: String },
+    ToGlibDuration { name: String },
+    ToGlibDateTime {
+        name: String,
+        time: TimeIntegration,
+        date_time_type: String,
+        nullable: library::Nullable,
+        transfer: library::Transfer,
+    },
+    ToGlibTimeT {
+        name: String,
+        time: TimeIntegration,
+    },
+    ToGlibBytes {
+        name: String,
+        bytes_type: String,
+        nullable: library::Nullable,
+        transfer: library::Transfer,
+    },
     ToGlibScalar {
         name: String,
         nullable: library::Nullable,
@@ -53,12 +84,14 @@ pub enum TransformationType {
     },
     ToGlibStash { name: String },
     ToGlibBorrow,
+    ToGlibNull,
     ToGlibUnknown { name: String },
     Into { name: String, with_stash: bool },
     Length {
         array_name: String,
         array_length_name: String,
         array_length_type: String,
+        checked: bool,
     },
 }
 
@@ -67,15 +100,30 @@ impl TransformationType {
         use self::TransformationType::*;
         match *self {
             ToGlibDirect { .. } |
+            ToGlibCast { .. } |
+            ToGlibFixedArray { .. } |
+            ToGlibDuration { .. } |
+            ToGlibDateTime { .. } |
+            ToGlibTimeT { .. } |
+            ToGlibBytes { .. } |
             ToGlibScalar { .. } |
             ToGlibPointer { .. } |
             ToGlibStash { .. } |
             ToGlibBorrow |
+            ToGlibNull |
             ToGlibUnknown { .. } => true,
             _ => false,
         }
     }
 
+    pub fn is_checked_cast(&self) -> bool {
+        use self::TransformationType::*;
+        match *self {
+            ToGlibCast { checked, .. } | Length { checked, .. } => checked,
+            _ => false,
+        }
+    }
+
     pub fn set_to_glib_extra(&mut self, to_glib_extra_: &str) {
         if let TransformationType::ToGlibPointer {
             ref mut to_glib_extra,
@@ -132,6 +180,34 @@ impl Parameters {
         };
         self.transformations.push(transformation);
     }
+
+    /// Removes the `Gio.Cancellable` parameter from the Rust side,
+    /// `NULL` is passed to the C function instead.
+    pub fn skip_cancellable(&mut self, env: &Env) -> bool {
+        let ind_c = match self.c_parameters
+            .iter()
+            .position(|p| !p.instance_parameter && is_cancellable(env, p.typ))
+        {
+            Some(ind_c) => ind_c,
+            None => return false,
+        };
+
+        self.rust_parameters.retain(|p| p.ind_c != ind_c);
+
+        let transformations = self.transformations.drain(..).collect::<Vec<_>>();
+        for mut transformation in transformations {
+            if transformation.ind_c == ind_c {
+                if !transformation.transformation_type.is_to_glib() {
+                    continue;
+                }
+                transformation.ind_rust = None;
+                transformation.transformation_type = TransformationType::ToGlibNull;
+            }
+            self.transformations.push(transformation);
+        }
+
+        true
+    }
 }
 
 pub fn analyze(
@@ -196,18 +272,49 @@ pub fn analyze(
             transfer = library::Transfer::None;
         }
 
-        let immutable = configured_functions
-            .matched_parameters(&name)
-            .iter()
-            .any(|p| p.constant);
-        let ref_mode = RefMode::without_unneeded_mut(env, par, immutable);
-
         let nullable_override = configured_functions
             .matched_parameters(&name)
             .iter()
             .filter_map(|p| p.nullable)
             .next();
-        let nullable = nullable_override.unwrap_or(par.nullable);
+        let nullable = if par.instance_parameter {
+            // `&self` is never NULL, even if the instance parameter is nullable
+            library::Nullable(false)
+        } else if is_optional_by_default(&par.typ.full_name(&env.library)) {
+            nullable_override.unwrap_or(library::Nullable(true))
+        } else {
+            nullable_override.unwrap_or(par.nullable)
+        };
+
+        let duration_us = configured_functions
+            .matched_parameters(&name)
+            .iter()
+            .any(|p| p.duration_us);
+        let duration_us = duration_us && check_duration_us(env, par);
+
+        let time = env.config.time_integration;
+        let is_time_in = time.rust_type().is_some() && !par.instance_parameter
+            && par.direction == library::ParameterDirection::In;
+        let date_time = if is_time_in && is_date_time(env, par.typ) {
+            Some(time)
+        } else {
+            None
+        };
+        let time_t = if is_time_in && is_time_t(&par.c_type) {
+            Some(time)
+        } else {
+            None
+        };
+
+        let immutable = configured_functions
+            .matched_parameters(&name)
+            .iter()
+            .any(|p| p.constant);
+        let ref_mode = if is_consumed(env, par, transfer, nullable) {
+            RefMode::None
+        } else {
+            RefMode::without_unneeded_mut(env, par, immutable)
+        };
 
         let c_par = CParameter {
             name: name.clone(),
@@ -220,6 +327,9 @@ pub fn analyze(
             nullable: nullable,
             ref_mode: ref_mode,
             is_error: par.is_error,
+            duration_us: duration_us,
+            date_time: date_time,
+            time_t: time_t,
         };
         parameters.c_parameters.push(c_par);
 
@@ -232,7 +342,9 @@ pub fn analyze(
             };
             parameters.rust_parameters.push(rust_par);
 
-            if *nullable && is_into(env, par) {
+            if *nullable && env.config.into_option_params && date_time.is_none()
+                && is_into(env, par)
+            {
                 let with_stash = ref_mode == RefMode::ByRef;
                 let transformation = Transformation {
                     ind_c: ind_c,
@@ -259,7 +371,39 @@ pub fn analyze(
         }
 
         let transformation_type = match ConversionType::of(env, par.typ) {
-            ConversionType::Direct => TransformationType::ToGlibDirect { name: name },
+            ConversionType::Pointer if is_bytes_in(env, par) => TransformationType::ToGlibBytes {
+                name: name,
+                bytes_type: rust_type(env, par.typ).into_string(),
+                nullable: nullable,
+                transfer: transfer,
+            },
+            ConversionType::Direct if duration_us => {
+                TransformationType::ToGlibDuration { name: name }
+            }
+            ConversionType::Direct if time_t.is_some() => TransformationType::ToGlibTimeT {
+                name: name,
+                time: time,
+            },
+            ConversionType::Pointer if date_time.is_some() => {
+                TransformationType::ToGlibDateTime {
+                    name: name,
+                    time: time,
+                    date_time_type: rust_type(env, par.typ).into_string(),
+                    nullable: nullable,
+                    transfer: transfer,
+                }
+            }
+            ConversionType::Direct if is_fixed_array(env, par.typ) => {
+                TransformationType::ToGlibFixedArray { name: name }
+            }
+            ConversionType::Direct => match size_cast(env, par.typ) {
+                Some(type_) => TransformationType::ToGlibCast {
+                    name: name,
+                    type_: type_.into(),
+                    checked: is_checked_cast(env, &rust_type(env, par.typ).into_string(), type_),
+                },
+                None => TransformationType::ToGlibDirect { name: name },
+            },
             ConversionType::Scalar => TransformationType::ToGlibScalar {
                 name: name,
                 nullable: nullable,
@@ -286,6 +430,71 @@ pub fn analyze(
     parameters
 }
 
+pub fn is_fixed_array(env: &Env, typ: library::TypeId) -> bool {
+    match *env.library.type_(typ) {
+        library::Type::FixedArray(..) => true,
+        library::Type::Alias(ref alias) => is_fixed_array(env, alias.typ),
+        _ => false,
+    }
+}
+
+/// Only `gint64` input parameters can be passed as a `Duration` in microseconds.
+fn check_duration_us(env: &Env, par: &library::Parameter) -> bool {
+    let is_int64 = match *env.library.type_(par.typ) {
+        library::Type::Fundamental(library::Fundamental::Int64) => true,
+        _ => false,
+    };
+    if !is_int64 || par.direction != library::ParameterDirection::In {
+        error!(
+            "Parameter `{}` configured as `duration_us` isn't a gint64 input",
+            par.name
+        );
+        return false;
+    }
+    true
+}
+
+pub fn is_cancellable(env: &Env, typ: library::TypeId) -> bool {
+    typ.full_name(&env.library) == "Gio.Cancellable"
+}
+
+/// A cancellable is always optional, `NULL` for a main context means
+/// the thread-default one, even if the GIR misses the annotation.
+fn is_optional_by_default(full_name: &str) -> bool {
+    full_name == "Gio.Cancellable" || full_name == "GLib.MainContext"
+}
+
+/// With `consume_transfer_full` objects and boxed types passed with `transfer full`
+/// are moved into the function, the ownership is handed to C with `to_glib_full()`.
+fn is_consumed(
+    env: &Env,
+    par: &library::Parameter,
+    transfer: library::Transfer,
+    nullable: library::Nullable,
+) -> bool {
+    if !env.config.consume_transfer_full || par.direction != library::ParameterDirection::In || transfer != library::Transfer::Full
+        || *nullable
+    {
+        return false;
+    }
+    match *env.library.type_(par.typ) {
+        library::Type::Class(..) | library::Type::Interface(..) | library::Type::Record(..) => {
+            true
+        }
+        _ => false,
+    }
+}
+
+pub fn is_bytes(env: &Env, typ: library::TypeId) -> bool {
+    typ.full_name(&env.library) == "GLib.Bytes"
+}
+
+/// `GBytes` input parameters are accepted as `&[u8]`.
+fn is_bytes_in(env: &Env, par: &library::Parameter) -> bool {
+    par.direction == library::ParameterDirection::In && !par.instance_parameter
+        && is_bytes(env, par.typ)
+}
+
 fn is_into(env: &Env, par: &library::Parameter) -> bool {
     fn is_into_inner(env: &Env, par: &library::Type) -> bool {
         match *par {
@@ -298,7 +507,8 @@ fn is_into(env: &Env, par: &library::Parameter) -> bool {
             _ => true,
         }
     }
-    !par.instance_parameter && is_into_inner(env, env.library.type_(par.typ))
+    !par.instance_parameter && !is_bytes_in(env, par)
+        && is_into_inner(env, env.library.type_(par.typ))
 }
 
 fn get_length_type(
@@ -307,14 +517,23 @@ fn get_length_type(
     length_name: &str,
     length_typ: library::TypeId,
 ) -> TransformationType {
-    let array_length_type = rust_type(env, length_typ).into_string();
+    // The length is passed to C, so in its native width
+    let array_length_type = match size_cast(env, length_typ) {
+        Some(type_) => type_.into(),
+        None => rust_type(env, length_typ).into_string(),
+    };
     TransformationType::Length {
         array_name: array_name.to_string(),
         array_length_name: length_name.to_string(),
+        checked: is_checked_cast(env, "usize", &array_length_type),
         array_length_type: array_length_type,
     }
 }
 
+fn is_checked_cast(env: &Env, from: &str, to: &str) -> bool {
+    env.config.checked_casts && is_narrowing(from, to)
+}
+
 fn detect_length<'a>(
     env: &Env,
     pos: usize,
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::vec::Vec;
 
 use analysis::bounds::Bounds;
@@ -46,9 +46,19 @@ pub struct Info {
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
     pub not_version: Option<Version>,
+    pub replaced_in: Option<Version>,
+    /// Named by a `rename` in the config
+    pub renamed: bool,
+    pub removed_version: Option<Version>,
     pub cfg_condition: Option<String>,
     pub assertion: SafetyAssertionMode,
     pub doc_hidden: bool,
+    pub body_override: Option<String>,
+    pub doc_ignore_parameters: Vec<String>,
+    /// Error domain enum a thrown `glib::Error` is converted into
+    pub error_type: Option<String>,
+    /// Returns its receiver for chaining instead of nothing
+    pub chain: bool,
 }
 
 pub fn analyze<F: Borrow<library::Function>>(
@@ -61,6 +71,15 @@ pub fn analyze<F: Borrow<library::Function>>(
     deps: Option<&[library::TypeId]>,
 ) -> Vec<Info> {
     let mut funcs = Vec::new();
+    // Ignored functions are still taken into account as they're usually implemented manually
+    let mut names: HashSet<&str> = functions.iter().map(|f| &f.borrow().name[..]).collect();
+    for func in functions {
+        for configured in obj.functions.matched(&func.borrow().name) {
+            if let Some(ref rename) = configured.rename {
+                names.insert(rename);
+            }
+        }
+    }
 
     for func in functions {
         let func = func.borrow();
@@ -71,7 +90,24 @@ pub fn analyze<F: Borrow<library::Function>>(
         if env.is_totally_deprecated(func.deprecated_version) {
             continue;
         }
-        let name = nameutil::mangle_keywords(&*func.name).into_owned();
+        let version = configured_functions
+            .iter()
+            .filter_map(|f| f.version)
+            .min()
+            .or(func.version);
+        if env.is_too_new(&func.name, version) {
+            continue;
+        }
+        let name = match configured_functions.iter().filter_map(|f| f.rename.as_ref()).next() {
+            Some(rename) => rename.clone(),
+            None if env.config.short_constructor_names
+                && func.kind == library::FunctionKind::Constructor =>
+            {
+                let name = short_constructor_name(&func.name, &names);
+                nameutil::mangle_keywords(name).into_owned()
+            }
+            None => nameutil::mangle_keywords(&*func.name).into_owned(),
+        };
         let signature_params = Signature::new(func);
         let mut not_version = None;
         if func.kind == library::FunctionKind::Method {
@@ -89,11 +125,38 @@ pub fn analyze<F: Borrow<library::Function>>(
             signatures.insert(name.clone(), signature_params);
         }
 
-        let mut info = analyze_function(env, name, func, type_tid, &configured_functions, imports);
+        let mut info = analyze_function(
+            env,
+            name.clone(),
+            func,
+            type_tid,
+            &configured_functions,
+            false,
+            imports,
+        );
         info.not_version = not_version;
         funcs.push(info);
+
+        let has_cancellable = func.parameters
+            .iter()
+            .any(|p| !p.instance_parameter && function_parameters::is_cancellable(env, p.typ));
+        if env.config.generate_without_cancellable && has_cancellable {
+            let mut info = analyze_function(
+                env,
+                format!("{}_without_cancellable", name),
+                func,
+                type_tid,
+                &configured_functions,
+                true,
+                imports,
+            );
+            info.not_version = not_version;
+            funcs.push(info);
+        }
     }
 
+    gate_versioned_signatures(&mut funcs, env.config.min_cfg_version);
+
     funcs
 }
 
@@ -103,6 +166,7 @@ fn analyze_function(
     func: &library::Function,
     type_tid: library::TypeId,
     configured_functions: &[&config::functions::Function],
+    without_cancellable: bool,
     imports: &mut Imports,
 ) -> Info {
     let mut commented = false;
@@ -117,14 +181,85 @@ fn analyze_function(
         .or(func.version);
     let version = env.config.filter_version(version);
     let deprecated_version = func.deprecated_version;
+    let removed_version = configured_functions
+        .iter()
+        .filter_map(|f| f.removed_version)
+        .min();
+    let removed_version = match removed_version {
+        Some(removed)
+            if removed <= env.config.min_cfg_version || version.map_or(false, |v| removed <= v) =>
+        {
+            error!(
+                "removed_version of {} must be newer than its version and min_cfg_version",
+                func.c_identifier.as_ref().unwrap_or(&func.name)
+            );
+            None
+        }
+        removed_version => removed_version,
+    };
     let cfg_condition = configured_functions
         .iter()
         .filter_map(|f| f.cfg_condition.clone())
         .next();
+    let cfg_condition = env.config
+        .platform_cfg
+        .cfg_condition(cfg_condition, func.c_identifier.as_ref().unwrap_or(&func.name));
+    let cfg_condition = stability_cfg_condition(
+        cfg_condition,
+        func.stability,
+        env.config.unstable_feature.as_ref().map(|s| &s[..]),
+    );
+    let cfg_condition = feature_cfg_condition(
+        cfg_condition,
+        func.c_identifier
+            .as_ref()
+            .and_then(|name| env.config.function_features.get(name))
+            .map(|s| &s[..]),
+    );
     let doc_hidden = configured_functions.iter().any(|f| f.doc_hidden);
+    let body_override = configured_functions
+        .iter()
+        .filter_map(|f| f.body_override.clone())
+        .next();
     let disable_length_detect = configured_functions.iter().any(|f| f.disable_length_detect);
+    let error_type = configured_functions
+        .iter()
+        .filter_map(|f| f.error_type.clone())
+        .next();
+    let error_type = match error_type {
+        Some(_) if !func.throws => {
+            error!(
+                "error_type given for {}, which doesn't throw",
+                func.c_identifier.as_ref().unwrap_or(&func.name)
+            );
+            None
+        }
+        Some(ref error_type) if !has_failed_member(env, error_type) => {
+            error!(
+                "error_type {} given for {} has no `failed` member for errors of other domains",
+                error_type,
+                func.c_identifier.as_ref().unwrap_or(&func.name)
+            );
+            None
+        }
+        error_type => error_type,
+    };
+    let chain = configured_functions.iter().any(|f| f.chain);
+    let chain = if chain && !can_chain(func) {
+        error!(
+            "chain given for {}, which isn't a method returning nothing",
+            func.c_identifier.as_ref().unwrap_or(&func.name)
+        );
+        false
+    } else {
+        chain
+    };
+    let configured_doc_ignore: Vec<&str> = configured_functions
+        .iter()
+        .flat_map(|f| f.doc_ignore_parameters.iter().map(|s| &s[..]))
+        .collect();
 
-    let ret = return_value::analyze(
+    let mut ret = return_value::analyze(
         env,
         func,
         type_tid,
@@ -141,6 +276,7 @@ fn analyze_function(
         disable_length_detect,
     );
     parameters.analyze_return(env, &ret.parameter);
+    let skip_cancellable = without_cancellable && parameters.skip_cancellable(env);
 
     for (pos, par) in parameters.c_parameters.iter().enumerate() {
         assert!(
@@ -148,6 +284,11 @@ fn analyze_function(
             "Wrong instance parameter in {}",
             func.c_identifier.as_ref().unwrap()
         );
+        if skip_cancellable && !par.instance_parameter
+            && function_parameters::is_cancellable(env, par.typ)
+        {
+            continue;
+        }
         if let Ok(s) = used_rust_type(env, par.typ) {
             used_types.push(s);
         }
@@ -183,6 +324,14 @@ fn analyze_function(
     }
 
     let (outs, unsupported_outs) = out_parameters::analyze(env, func, configured_functions);
+    if !outs.is_empty() {
+        // The return is a part of a tuple typed by the outs
+        ret.date_time = None;
+        ret.time_t = None;
+        ret.bytes_to_vec = false;
+        ret.invalid_char = None;
+        ret.string_type = None;
+    }
     if unsupported_outs {
         warn!(
             "Function {} has unsupported outs",
@@ -202,10 +351,31 @@ fn analyze_function(
             }
         }
 
-        imports.add_used_types(&used_types, version);
+        imports.add_used_types_with_constraint(
+            &used_types,
+            version,
+            cfg_condition.as_ref().map(|s| &s[..]),
+        );
+        if skip_cancellable {
+            imports.add("std::ptr", version);
+        }
+        if parameters
+            .transformations
+            .iter()
+            .any(|t| t.transformation_type.is_checked_cast())
+        {
+            imports.add_with_constraint(
+                "std::convert::TryFrom",
+                version,
+                cfg_condition.as_ref().map(|s| &s[..]),
+            );
+        }
         if ret.base_tid.is_some() {
             imports.add("glib::object::Downcast", None);
         }
+        if let Some(ref error_type) = error_type {
+            imports.add(error_type, version);
+        }
         bounds.update_imports(imports);
     }
 
@@ -215,7 +385,13 @@ fn analyze_function(
         Visibility::Public
     };
     let is_method = func.kind == library::FunctionKind::Method;
-    let assertion = SafetyAssertionMode::of(env, is_method, &parameters);
+    let main_thread_only = configured_functions
+        .iter()
+        .filter_map(|f| f.main_thread_only)
+        .next();
+    let assertion = SafetyAssertionMode::of(env, is_method, &parameters)
+        .with_main_thread_only(main_thread_only);
+    let doc_ignore_parameters = doc_ignore_parameters(&configured_doc_ignore, &parameters);
 
     Info {
         name: name,
@@ -230,10 +406,120 @@ fn analyze_function(
         version: version,
         deprecated_version: deprecated_version,
         not_version: None,
+        replaced_in: None,
+        renamed: configured_functions.iter().any(|f| f.rename.is_some()),
+        removed_version: removed_version,
         cfg_condition: cfg_condition,
         assertion: assertion,
         doc_hidden: doc_hidden,
+        body_override: body_override,
+        doc_ignore_parameters: doc_ignore_parameters,
+        error_type: error_type,
+        chain: chain,
+    }
+}
+
+/// A `new` function without parameters, used to implement `Default`.
+pub fn default_from_new(functions: &[Info]) -> Option<&Info> {
+    functions.iter().find(|f| {
+        !f.visibility.hidden() && f.name == "new" && f.parameters.rust_parameters.is_empty()
+    })
+}
+
+/// Only a method without any result can return its receiver instead.
+fn can_chain(func: &library::Function) -> bool {
+    func.kind == library::FunctionKind::Method && func.ret.typ == library::TypeId::tid_none()
+        && !func.throws
+        && func.parameters
+            .iter()
+            .all(|p| p.direction != library::ParameterDirection::Out)
+}
+
+/// Errors of other domains than the one of an `error_type` are returned as its
+/// `Failed` member, like error codes it doesn't know.
+fn has_failed_member(env: &Env, error_type: &str) -> bool {
+    match env.library
+        .find_type(library::MAIN_NAMESPACE, error_type)
+        .map(|tid| env.type_(tid))
+    {
+        Some(&Type::Enumeration(ref enum_)) => enum_.members.iter().any(|m| m.name == "failed"),
+        _ => false,
+    }
+}
+
+/// Parameters left out of the generated docs: the configured ones and the ones
+/// removed from the Rust signature. Out parameters are kept, they're returned.
+fn doc_ignore_parameters(configured: &[&str], parameters: &Parameters) -> Vec<String> {
+    let mut ignored: Vec<String> = configured.iter().map(|s| (*s).to_owned()).collect();
+    for (ind_c, par) in parameters.c_parameters.iter().enumerate() {
+        if par.instance_parameter || par.direction == library::ParameterDirection::Out {
+            continue;
+        }
+        let in_signature = parameters.rust_parameters.iter().any(|p| p.ind_c == ind_c);
+        if !in_signature && !ignored.contains(&par.name) {
+            ignored.push(par.name.clone());
+        }
+    }
+    ignored
+}
+
+/// Functions renamed to the same name are signatures of one method in different
+/// versions, each one is only generated until the next one is available.
+/// Names shared without a `rename` are collisions, reported by `identifiers::check`.
+fn gate_versioned_signatures(funcs: &mut [Info], min_cfg_version: Version) {
+    let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+    for (pos, func) in funcs.iter().enumerate() {
+        by_name
+            .entry(func.name.clone())
+            .or_insert_with(Vec::new)
+            .push(pos);
+    }
+
+    for (name, mut positions) in by_name {
+        if positions.len() < 2 {
+            continue;
+        }
+        let not_renamed = positions.iter().filter(|&&pos| !funcs[pos].renamed).count();
+        if not_renamed > 1 {
+            continue;
+        }
+        positions.sort_by_key(|&pos| funcs[pos].version);
+        let versions: Vec<Option<Version>> =
+            positions.iter().map(|&pos| funcs[pos].version).collect();
+        match replaced_in(&versions, min_cfg_version) {
+            Some(replaced) => for (&pos, replaced_in) in positions.iter().zip(replaced) {
+                funcs[pos].replaced_in = replaced_in;
+            },
+            None => {
+                error!(
+                    "Signatures of `{}` need distinct versions newer than the minimal one",
+                    name
+                );
+                for &pos in &positions[1..] {
+                    funcs[pos].visibility = Visibility::Comment;
+                }
+            }
+        }
+    }
+}
+
+/// For the versions of the signatures of one method, oldest first, the version
+/// each one is replaced in. `None` if the versions don't tell them apart.
+fn replaced_in(
+    versions: &[Option<Version>],
+    min_cfg_version: Version,
+) -> Option<Vec<Option<Version>>> {
+    let mut replaced = Vec::with_capacity(versions.len());
+    for pair in versions.windows(2) {
+        match pair[1] {
+            Some(newer) if newer > min_cfg_version && pair[0].map_or(true, |v| v < newer) => {
+                replaced.push(Some(newer))
+            }
+            _ => return None,
+        }
     }
+    replaced.push(None);
+    Some(replaced)
 }
 
 pub fn is_carray_with_direct_elements(env: &Env, typ: library::TypeId) -> bool {
@@ -252,3 +538,255 @@ pub fn is_carray_with_direct_elements(env: &Env, typ: library::TypeId) -> bool {
         _ => false,
     }
 }
+
+/// Strips `new_` from constructor names, so `new_with_label` becomes `with_label`
+/// and `new_from_file` becomes `from_file`, unless that's the name of another function.
+fn short_constructor_name<'a>(name: &'a str, names: &HashSet<&str>) -> &'a str {
+    if name.starts_with("new_") {
+        let short = &name[4..];
+        if !names.contains(short) {
+            return short;
+        }
+    }
+    name
+}
+
+/// Gates unstable API behind the configured `unstable_feature`.
+pub fn stability_cfg_condition(
+    cfg_condition: Option<String>,
+    stability: library::Stability,
+    unstable_feature: Option<&str>,
+) -> Option<String> {
+    match stability {
+        library::Stability::Unstable => feature_cfg_condition(cfg_condition, unstable_feature),
+        _ => cfg_condition,
+    }
+}
+
+/// Gates a function behind a Cargo feature in addition to its other conditions.
+fn feature_cfg_condition(cfg_condition: Option<String>, feature: Option<&str>) -> Option<String> {
+    match feature {
+        Some(feature) => config::combine_cfg_conditions(
+            cfg_condition,
+            Some(&format!("feature = \"{}\"", feature)),
+        ),
+        None => cfg_condition,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use analysis::function_parameters::{CParameter, RustParameter};
+    use analysis::imports::Imports;
+    use library::Stability;
+
+    #[test]
+    fn constructors_get_short_names() {
+        let names: HashSet<&str> = [
+            "new",
+            "new_with_label",
+            "new_from_file",
+            "new_for_path",
+            "for_path",
+        ].iter()
+            .cloned()
+            .collect();
+        assert_eq!(short_constructor_name("new", &names), "new");
+        assert_eq!(short_constructor_name("new_with_label", &names), "with_label");
+        assert_eq!(short_constructor_name("new_from_file", &names), "from_file");
+        // Collides with another function
+        assert_eq!(short_constructor_name("new_for_path", &names), "new_for_path");
+    }
+
+    #[test]
+    fn unstable_function_is_feature_gated() {
+        let cfg = stability_cfg_condition(None, Stability::Unstable, Some("unstable"));
+        assert_eq!(cfg, Some("feature = \"unstable\"".to_owned()));
+
+        let cfg = stability_cfg_condition(
+            Some("unix".to_owned()),
+            Stability::Unstable,
+            Some("unstable"),
+        );
+        assert_eq!(cfg, Some("all(unix, feature = \"unstable\")".to_owned()));
+
+        let mut imports = Imports::new();
+        imports.add_with_constraint("std::ptr", None, cfg.as_ref().map(|s| &s[..]));
+        let (name, conditions) = imports.iter().next().unwrap();
+        assert_eq!(name, "std::ptr");
+        assert_eq!(
+            conditions.constraint,
+            Some("all(unix, feature = \"unstable\")".to_owned())
+        );
+    }
+
+    #[test]
+    fn mapped_function_is_feature_gated() {
+        let cfg = feature_cfg_condition(None, Some("experimental"));
+        assert_eq!(cfg, Some("feature = \"experimental\"".to_owned()));
+        assert_eq!(feature_cfg_condition(None, None), None);
+
+        let cfg = feature_cfg_condition(Some("unix".to_owned()), Some("experimental"));
+        assert_eq!(cfg, Some("all(unix, feature = \"experimental\")".to_owned()));
+
+        let mut imports = Imports::new();
+        imports.add_with_constraint("std::ptr", None, cfg.as_ref().map(|s| &s[..]));
+        let (_, conditions) = imports.iter().next().unwrap();
+        assert_eq!(conditions.constraint, cfg);
+    }
+
+    #[test]
+    fn stable_function_is_not_gated() {
+        assert_eq!(
+            stability_cfg_condition(None, Stability::Stable, Some("unstable")),
+            None
+        );
+        assert_eq!(
+            stability_cfg_condition(None, Stability::Unstable, None),
+            None
+        );
+    }
+
+    fn c_parameter(name: &str, direction: library::ParameterDirection) -> CParameter {
+        CParameter {
+            name: name.into(),
+            typ: library::TypeId::tid_none(),
+            c_type: "gpointer".into(),
+            instance_parameter: false,
+            direction: direction,
+            nullable: Nullable(false),
+            transfer: library::Transfer::None,
+            caller_allocates: false,
+            is_error: false,
+            duration_us: false,
+            date_time: None,
+            time_t: None,
+            ref_mode: RefMode::None,
+        }
+    }
+
+    #[test]
+    fn removed_and_configured_parameters_are_not_documented() {
+        use library::ParameterDirection::*;
+
+        let mut parameters = Parameters {
+            rust_parameters: Vec::new(),
+            c_parameters: vec![
+                c_parameter("data", In),
+                c_parameter("len", In),
+                c_parameter("flags", In),
+                c_parameter("result", Out),
+            ],
+            transformations: Vec::new(),
+        };
+        for &ind_c in &[0, 2] {
+            parameters.rust_parameters.push(RustParameter {
+                ind_c: ind_c,
+                name: parameters.c_parameters[ind_c].name.clone(),
+                typ: library::TypeId::tid_none(),
+                allow_none: false,
+            });
+        }
+
+        assert_eq!(doc_ignore_parameters(&[], &parameters), vec!["len"]);
+        assert_eq!(
+            doc_ignore_parameters(&["flags"], &parameters),
+            vec!["flags", "len"]
+        );
+    }
+
+    #[test]
+    fn versioned_signatures_are_replaced_by_newer() {
+        let min = Version::Full(3, 10, 0);
+        let v3_20 = Version::Full(3, 20, 0);
+        let v3_22 = Version::Full(3, 22, 0);
+        assert_eq!(
+            replaced_in(&[None, Some(v3_20), Some(v3_22)], min),
+            Some(vec![Some(v3_20), Some(v3_22), None])
+        );
+        // Same version
+        assert_eq!(replaced_in(&[Some(v3_20), Some(v3_20)], min), None);
+        // Always available
+        assert_eq!(replaced_in(&[None, Some(min)], min), None);
+    }
+
+    const WINDOW: &'static str = r#"
+    <class name="Window" c:type="TestWindow" parent="GObject.Object"
+           glib:type-name="TestWindow" glib:get-type="test_window_get_type">
+      <method name="set_icon" c:identifier="test_window_set_icon">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="window" transfer-ownership="none">
+            <type name="Window" c:type="TestWindow*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+      <method name="set_icon_full" c:identifier="test_window_set_icon_full" version="1.2">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="window" transfer-ownership="none">
+            <type name="Window" c:type="TestWindow*"/>
+          </instance-parameter>
+          <parameter name="size" transfer-ownership="none">
+            <type name="gint" c:type="gint"/>
+          </parameter>
+        </parameters>
+      </method>
+    </class>"#;
+
+    const DEFAULT_ICON: &'static str = r#"
+      <function name="set_icon" c:identifier="test_window_set_default_icon" version="1.4">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+      </function>
+    </class>"#;
+
+    fn window_functions(gir: &str, object: &str) -> Vec<(String, Option<Version>)> {
+        use test_env;
+
+        let objects = format!(
+            r#"
+[[object]]
+name = "Test.Window"
+status = "generate"
+{}
+"#,
+            object
+        );
+        let env = test_env::env(gir, "", &objects);
+        env.analysis.objects["Test.Window"]
+            .functions
+            .iter()
+            .map(|f| (f.glib_name.clone(), f.replaced_in))
+            .collect()
+    }
+
+    #[test]
+    fn same_names_are_not_versioned_signatures_without_rename() {
+        let gir = WINDOW.replace("\n    </class>", DEFAULT_ICON);
+        let functions = window_functions(&gir, "");
+        assert_eq!(functions.len(), 3);
+        assert!(functions.iter().all(|&(_, replaced_in)| replaced_in.is_none()));
+    }
+
+    #[test]
+    fn renamed_signatures_are_replaced_by_newer() {
+        let object = r#"
+    [[object.function]]
+    name = "set_icon_full"
+    rename = "set_icon""#;
+        assert_eq!(
+            window_functions(WINDOW, object),
+            vec![
+                ("test_window_set_icon".to_owned(), Some(Version::Full(1, 2, 0))),
+                ("test_window_set_icon_full".to_owned(), None),
+            ]
+        );
+    }
+}
@@ -6,3 +6,12 @@ pub struct StatusedTypeId {
     pub name: String,
     pub status: GStatus,
 }
+
+/// Returns the `use` paths of derives given by path.
+pub fn derive_uses(extra_derives: &[String]) -> Vec<&str> {
+    extra_derives
+        .iter()
+        .filter(|derive| derive.contains("::"))
+        .map(|derive| &derive[..])
+        .collect()
+}
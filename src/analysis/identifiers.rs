@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use env::Env;
+use super::functions::Info as FuncInfo;
+
+/// A generated Rust identifier and the C symbol or GIR name it's derived from.
+#[derive(Debug)]
+struct Identifier<'a> {
+    name: &'a str,
+    cfg_condition: Option<&'a str>,
+    c_name: &'a str,
+}
+
+/// Reports generated identifiers colliding after all renaming and prefix stripping,
+/// which otherwise only show up as confusing errors of the compiled bindings.
+pub fn check(env: &Env) {
+    for info in env.analysis.objects.values() {
+        check_functions(&info.full_name, &info.functions);
+    }
+    for info in env.analysis.records.values() {
+        check_functions(&info.full_name, &info.functions);
+    }
+    if let Some(ref info) = env.analysis.global_functions {
+        check_functions(&info.full_name, &info.functions);
+    }
+
+    let types: Vec<Identifier> = env.analysis
+        .objects
+        .values()
+        .map(|info| Identifier {
+            name: &info.name,
+            cfg_condition: info.cfg_condition.as_ref().map(|s| &s[..]),
+            c_name: &info.full_name,
+        })
+        .chain(env.analysis.records.values().map(|info| {
+            Identifier {
+                name: &info.name,
+                cfg_condition: info.cfg_condition.as_ref().map(|s| &s[..]),
+                c_name: &info.full_name,
+            }
+        }))
+        .collect();
+    for (name, first, second) in duplicates(&types) {
+        error!(
+            "Type `{}` is generated for both `{}` and `{}`, ignore one of them",
+            name,
+            first,
+            second
+        );
+    }
+}
+
+fn check_functions(owner: &str, functions: &[FuncInfo]) {
+    let identifiers: Vec<Identifier> = functions
+        .iter()
+        // Replaced signatures are never generated together with the newer one
+        .filter(|f| !f.visibility.hidden() && f.replaced_in.is_none())
+        .map(|f| Identifier {
+            name: &f.name,
+            cfg_condition: f.cfg_condition.as_ref().map(|s| &s[..]),
+            c_name: &f.glib_name,
+        })
+        .collect();
+    for (name, first, second) in duplicates(&identifiers) {
+        error!(
+            "Function `{}` of {} is generated for both `{}` and `{}`, \
+             use `rename` in the config of one of them",
+            name,
+            owner,
+            first,
+            second
+        );
+    }
+}
+
+/// Returns the Rust name, the first and the colliding C symbol of every collision.
+/// Identifiers under different cfg conditions don't collide.
+fn duplicates<'a>(identifiers: &[Identifier<'a>]) -> Vec<(&'a str, &'a str, &'a str)> {
+    let mut seen: HashMap<(&str, Option<&str>), &str> = HashMap::new();
+    let mut duplicates = Vec::new();
+    for identifier in identifiers {
+        let key = (identifier.name, identifier.cfg_condition);
+        if let Some(first) = seen.get(&key) {
+            duplicates.push((identifier.name, *first, identifier.c_name));
+        }
+        seen.entry(key).or_insert(identifier.c_name);
+    }
+    duplicates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identifier<'a>(
+        name: &'a str,
+        cfg_condition: Option<&'a str>,
+        c_name: &'a str,
+    ) -> Identifier<'a> {
+        Identifier {
+            name: name,
+            cfg_condition: cfg_condition,
+            c_name: c_name,
+        }
+    }
+
+    #[test]
+    fn functions_deriving_same_name_collide() {
+        let identifiers = vec![
+            identifier("get_name", None, "gtk_widget_get_name"),
+            identifier("set_name", None, "gtk_widget_set_name"),
+            identifier("get_name", None, "gtk_widget_get_name_v2"),
+        ];
+        assert_eq!(
+            duplicates(&identifiers),
+            vec![("get_name", "gtk_widget_get_name", "gtk_widget_get_name_v2")]
+        );
+    }
+
+    #[test]
+    fn differently_conditioned_names_dont_collide() {
+        let identifiers = vec![
+            identifier("get_fd", Some("unix"), "g_socket_get_fd"),
+            identifier("get_fd", Some("windows"), "g_socket_get_handle"),
+        ];
+        assert!(duplicates(&identifiers).is_empty());
+    }
+}
@@ -4,9 +4,15 @@ use env::Env;
 use super::namespaces;
 use version::Version;
 
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportConditions {
+    pub version: Option<Version>,
+    pub constraint: Option<String>,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct Imports {
-    map: BTreeMap<String, Option<Version>>,
+    map: BTreeMap<String, ImportConditions>,
 }
 
 impl Imports {
@@ -17,27 +23,64 @@ impl Imports {
     }
 
     pub fn add(&mut self, name: &str, version: Option<Version>) {
-        let entry = self.map.entry(name.to_owned()).or_insert(version);
-        if version < *entry {
-            *entry = version;
+        self.add_with_constraint(name, version, None);
+    }
+
+    /// Adds an import that is only needed under the `constraint` cfg.
+    /// Used by differently constrained items, the import becomes unconditional.
+    pub fn add_with_constraint(
+        &mut self,
+        name: &str,
+        version: Option<Version>,
+        constraint: Option<&str>,
+    ) {
+        let entry = self.map
+            .entry(name.to_owned())
+            .or_insert_with(|| ImportConditions {
+                version: version,
+                constraint: constraint.map(|s| s.to_owned()),
+            });
+        if version < entry.version {
+            entry.version = version;
+        }
+        if entry.constraint.as_ref().map(|s| &s[..]) != constraint {
+            entry.constraint = None;
         }
     }
 
     pub fn add_used_type(&mut self, used_type: &str, version: Option<Version>) {
+        self.add_used_type_with_constraint(used_type, version, None);
+    }
+
+    pub fn add_used_type_with_constraint(
+        &mut self,
+        used_type: &str,
+        version: Option<Version>,
+        constraint: Option<&str>,
+    ) {
         if let Some(i) = used_type.find("::") {
             if i == 0 {
-                self.add(&used_type[2..], version);
+                self.add_with_constraint(&used_type[2..], version, constraint);
             } else {
-                self.add(&used_type[..i], version);
+                self.add_with_constraint(&used_type[..i], version, constraint);
             }
         } else {
-            self.add(used_type, version);
+            self.add_with_constraint(used_type, version, constraint);
         }
     }
 
     pub fn add_used_types(&mut self, used_types: &[String], version: Option<Version>) {
+        self.add_used_types_with_constraint(used_types, version, None);
+    }
+
+    pub fn add_used_types_with_constraint(
+        &mut self,
+        used_types: &[String],
+        version: Option<Version>,
+        constraint: Option<&str>,
+    ) {
         for s in used_types {
-            self.add_used_type(s, version);
+            self.add_used_type_with_constraint(s, version, constraint);
         }
     }
 
@@ -50,13 +93,13 @@ impl Imports {
             return;
         }
         self.remove("glib");
-        let glibs: Vec<(String, Option<Version>)> = self.map
+        let glibs: Vec<(String, ImportConditions)> = self.map
             .iter()
             .filter_map(|p| {
                 let glib_offset = p.0.find("glib::");
                 if let Some(glib_offset) = glib_offset {
                     if glib_offset == 0 {
-                        Some((p.0.clone(), *p.1))
+                        Some((p.0.clone(), p.1.clone()))
                     } else {
                         None
                     }
@@ -67,11 +110,109 @@ impl Imports {
             .collect();
         for p in glibs {
             self.remove(&p.0);
-            self.add(&p.0[6..], p.1);
+            self.add_with_constraint(
+                &p.0[6..],
+                p.1.version,
+                p.1.constraint.as_ref().map(|s| &s[..]),
+            );
+        }
+    }
+
+    /// Prefixes the crate-local imports for which `crate_of` returns another crate.
+    pub fn rebase<F>(&mut self, crate_of: F)
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let rebased: Vec<(String, String)> = self.map
+            .keys()
+            .filter(|name| !name.contains("::"))
+            .filter_map(|name| crate_of(name).map(|krate| (name.clone(), krate)))
+            .collect();
+        for (name, krate) in rebased {
+            if let Some(conditions) = self.map.remove(&name) {
+                self.map.insert(format!("{}::{}", krate, name), conditions);
+            }
         }
     }
 
-    pub fn iter(&self) -> Iter<String, Option<Version>> {
+    pub fn iter(&self) -> Iter<String, ImportConditions> {
         self.map.iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constrained_import() {
+        let mut imports = Imports::new();
+        imports.add_with_constraint("UnixMountEntry", None, Some("unix"));
+        imports.add_used_types_with_constraint(&["UnixFDList".to_owned()], None, Some("unix"));
+        imports.add_with_constraint("File", None, Some("unix"));
+        imports.add("File", None);
+        imports.add_with_constraint("Win32InputStream", None, Some("windows"));
+        imports.add_with_constraint("Win32InputStream", None, Some("unix"));
+
+        let constraints = imports
+            .iter()
+            .map(|(name, c)| (&name[..], c.constraint.as_ref().map(|s| &s[..])))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                ("File", None),
+                ("UnixFDList", Some("unix")),
+                ("UnixMountEntry", Some("unix")),
+                ("Win32InputStream", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn rebase_to_other_crate() {
+        let mut imports = Imports::new();
+        imports.add("ffi", None);
+        imports.add("glib::translate::*", None);
+        imports.add("Element", None);
+        imports.add("VideoInfo", None);
+        imports.rebase(|name| if name == "VideoInfo" {
+            Some("gst_video".to_owned())
+        } else {
+            None
+        });
+
+        let names = imports.iter().map(|(name, _)| &name[..]).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["Element", "ffi", "glib::translate::*", "gst_video::VideoInfo"]
+        );
+    }
+
+    #[test]
+    fn rebase_to_module_path() {
+        let mut imports = Imports::new();
+        imports.add("ffi", None);
+        imports.add("glib::translate::*", None);
+        imports.add("Button", None);
+        imports.add("ButtonExt", None);
+        imports.add("gio::File", None);
+        imports.rebase(|name| if name.starts_with("Button") {
+            Some("ffi::generated".to_owned())
+        } else {
+            None
+        });
+
+        let names = imports.iter().map(|(name, _)| &name[..]).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "ffi",
+                "ffi::generated::Button",
+                "ffi::generated::ButtonExt",
+                "gio::File",
+                "glib::translate::*",
+            ]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::cmp;
+
+use analysis::functions::Info as FuncInfo;
+use analysis::imports::Imports;
+use analysis::rust_type::used_rust_type;
+use config::gobjects::GObject;
+use env::Env;
+use library::{self, Transfer};
+use version::Version;
+
+/// The C functions used to generate a bounds-checked `get(index)` for a type.
+#[derive(Debug)]
+pub struct Info {
+    pub glib_name: String,
+    pub len_glib_name: Option<String>,
+    /// Whether the length is a signed integer, which may be negative
+    pub len_signed: bool,
+    pub index_type: &'static str,
+    pub item: String,
+    pub transfer: Transfer,
+    pub version: Option<Version>,
+}
+
+pub fn analyze(
+    env: &Env,
+    obj: &GObject,
+    functions: &[FuncInfo],
+    imports: &mut Imports,
+) -> Option<Info> {
+    let config = match obj.index {
+        Some(ref config) => config,
+        None => return None,
+    };
+
+    if functions.iter().any(|f| f.name == "get") {
+        error!("Can't generate `get` of {}, it already has such a function", obj.name);
+        return None;
+    }
+
+    let find = |name: &str| {
+        let func = functions.iter().find(|f| f.name == name);
+        if func.is_none() {
+            error!("Function `{}` for `get` of {} not found", name, obj.name);
+        }
+        func
+    };
+
+    let get = match find(&config.get) {
+        Some(func) => func,
+        None => return None,
+    };
+    let (index_type, item_tid, transfer) = match check_get(env, get) {
+        Ok(checked) => checked,
+        Err(msg) => {
+            error!("Can't generate `get` of {} with `{}`: {}", obj.name, config.get, msg);
+            return None;
+        }
+    };
+
+    let mut version = get.version;
+    let (len_glib_name, len_signed) = match config.len {
+        Some(ref name) => {
+            let len = match find(name) {
+                Some(func) => func,
+                None => return None,
+            };
+            let len_type = match check_len(env, len) {
+                Ok(len_type) => len_type,
+                Err(msg) => {
+                    error!("Can't generate `get` of {} with `{}`: {}", obj.name, name, msg);
+                    return None;
+                }
+            };
+            version = cmp::max(version, len.version);
+            (Some(len.glib_name.clone()), len_type == "i32")
+        }
+        None => (None, false),
+    };
+
+    if let Ok(s) = used_rust_type(env, item_tid) {
+        imports.add_used_type(&s, version);
+    }
+
+    Some(Info {
+        glib_name: get.glib_name.clone(),
+        len_glib_name: len_glib_name,
+        len_signed: len_signed,
+        index_type: index_type,
+        item: config.item.clone(),
+        transfer: transfer,
+        version: version,
+    })
+}
+
+/// Rust type of a `gint` or `guint` index or length.
+fn index_type(env: &Env, tid: library::TypeId) -> Option<&'static str> {
+    use library::Fundamental::*;
+    match *env.library.type_(tid) {
+        library::Type::Fundamental(Int) | library::Type::Fundamental(Int32) => Some("i32"),
+        library::Type::Fundamental(UInt) | library::Type::Fundamental(UInt32) => Some("u32"),
+        _ => None,
+    }
+}
+
+/// Returns the type of the index, and the type and the transfer of the items.
+fn check_get(
+    env: &Env,
+    func: &FuncInfo,
+) -> Result<(&'static str, library::TypeId, Transfer), &'static str> {
+    if func.kind != library::FunctionKind::Method {
+        return Err("getter must be a method");
+    }
+    if func.parameters.c_parameters.len() != 2 || !func.outs.is_empty() {
+        return Err("getter must only take an index");
+    }
+    let index_type = match index_type(env, func.parameters.c_parameters[1].typ) {
+        Some(index_type) => index_type,
+        None => return Err("index isn't an integer"),
+    };
+    match func.ret.parameter {
+        Some(ref ret) => Ok((index_type, ret.typ, ret.transfer)),
+        None => Err("getter returns nothing"),
+    }
+}
+
+/// Returns the type of the length.
+fn check_len(env: &Env, func: &FuncInfo) -> Result<&'static str, &'static str> {
+    if func.kind != library::FunctionKind::Method || func.parameters.c_parameters.len() != 1 {
+        return Err("length function must be a method without parameters");
+    }
+    match func.ret.parameter.as_ref().and_then(|ret| index_type(env, ret.typ)) {
+        Some(len_type) => Ok(len_type),
+        None => Err("length function doesn't return an integer"),
+    }
+}
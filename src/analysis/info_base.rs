@@ -19,6 +19,15 @@ pub struct InfoBase {
     pub deprecated_version: Option<Version>,
     pub cfg_condition: Option<String>,
     pub concurrency: library::Concurrency,
+    pub as_ref_str: Option<as_ref_str::Info>,
+    pub eq_str: Option<eq_str::Info>,
+    pub iterator_next: Option<iterator_next::Info>,
+    pub index_getter: Option<index_getter::Info>,
+    pub from_str: Option<from_str::Info>,
+    /// `Eq` is implemented in addition to the `PartialEq` from the `equal` function
+    pub generate_eq: bool,
+    /// Borrowed counterpart of this owned type, bridged with `Borrow` and `ToOwned`
+    pub borrowed_type: Option<String>,
 }
 
 impl InfoBase {
@@ -0,0 +1,86 @@
+use analysis::functions::Info as FuncInfo;
+use analysis::imports::Imports;
+use config::gobjects::GObject;
+use library;
+use version::Version;
+
+/// How the configured init function is surfaced at the crate root.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kind {
+    /// The generated function is re-exported as is.
+    Reexport,
+    /// A wrapper without parameters passes the process arguments as `argc`/`argv`.
+    Argv,
+}
+
+#[derive(Debug)]
+pub struct Info {
+    pub name: String,
+    pub glib_name: String,
+    pub kind: Kind,
+    pub version: Option<Version>,
+}
+
+pub fn analyze(
+    init: &str,
+    obj: &GObject,
+    library_functions: &[&library::Function],
+    functions: &[FuncInfo],
+    imports: &mut Imports,
+) -> Option<Info> {
+    if obj.functions.matched(init).iter().any(|f| f.ignore) {
+        error!("Init function `{}` is ignored", init);
+        return None;
+    }
+    if let Some(func) = library_functions.iter().find(|f| f.name == init) {
+        let c_types: Vec<&str> = func.parameters.iter().map(|p| &p.c_type[..]).collect();
+        if is_argv_signature(&c_types) {
+            imports.add("std::env", func.version);
+            imports.add("std::ffi::CString", func.version);
+            imports.add_with_constraint("std::os::unix::ffi::OsStrExt", func.version, Some("unix"));
+            imports.add("std::ptr", func.version);
+            return Some(Info {
+                name: func.name.clone(),
+                glib_name: func.c_identifier.as_ref().unwrap().clone(),
+                kind: Kind::Argv,
+                version: func.version,
+            });
+        }
+    }
+
+    match functions
+        .iter()
+        .find(|f| f.name == init && !f.visibility.hidden())
+    {
+        Some(func) => Some(Info {
+            name: func.name.clone(),
+            glib_name: func.glib_name.clone(),
+            kind: Kind::Reexport,
+            version: func.version,
+        }),
+        None => {
+            error!("Init function `{}` not found", init);
+            None
+        }
+    }
+}
+
+/// Checks for `(int *argc, char ***argv)` as taken by `gtk_init`.
+fn is_argv_signature(c_types: &[&str]) -> bool {
+    c_types.len() == 2 && c_types[0].replace(' ', "") == "int*"
+        && c_types[1].replace(' ', "") == "char***"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn argc_argv_parameters_are_detected() {
+        assert!(is_argv_signature(&["int*", "char***"]));
+        assert!(is_argv_signature(&["int *", "char ***"]));
+        assert!(!is_argv_signature(&[]));
+        assert!(!is_argv_signature(&["int*"]));
+        assert!(!is_argv_signature(&["int*", "char**"]));
+    }
+}
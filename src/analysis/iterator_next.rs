@@ -0,0 +1,123 @@
+use analysis::conversion_type::ConversionType;
+use analysis::functions::Info as FuncInfo;
+use analysis::imports::Imports;
+use analysis::ref_mode::RefMode;
+use analysis::rust_type::used_rust_type;
+use config::gobjects::{GObject, IteratorEnd};
+use env::Env;
+use library::{self, Transfer};
+use version::Version;
+
+/// The C function used to implement `Iterator` for a type.
+#[derive(Debug)]
+pub struct Info {
+    pub glib_name: String,
+    pub item: String,
+    pub end: IteratorEnd,
+    pub transfer: Transfer,
+    /// How the C item is converted, pointers are returned as `NULL` at the end
+    pub conversion_type: ConversionType,
+    pub mut_self: bool,
+    pub version: Option<Version>,
+}
+
+pub fn analyze(
+    env: &Env,
+    obj: &GObject,
+    functions: &[FuncInfo],
+    imports: &mut Imports,
+) -> Option<Info> {
+    let config = match obj.iterator_next {
+        Some(ref config) => config,
+        None => return None,
+    };
+
+    let func = match functions.iter().find(|f| f.name == config.func) {
+        Some(func) => func,
+        None => {
+            error!(
+                "Function `{}` for Iterator of {} not found",
+                config.func,
+                obj.name
+            );
+            return None;
+        }
+    };
+
+    let checked = check_next(func, config.end).and_then(|(item_tid, transfer)| {
+        let conversion_type = ConversionType::of(env, item_tid);
+        match conversion_type {
+            ConversionType::Pointer | ConversionType::Borrow => (),
+            ConversionType::Direct | ConversionType::Scalar if config.end == IteratorEnd::False => (),
+            _ if config.end == IteratorEnd::Null => return Err("items aren't pointers"),
+            _ => return Err("items can't be converted"),
+        }
+        Ok((item_tid, transfer, conversion_type))
+    });
+    let (item_tid, transfer, conversion_type) = match checked {
+        Ok(checked) => checked,
+        Err(msg) => {
+            error!(
+                "Can't implement Iterator for {} with `{}`: {}",
+                obj.name,
+                config.func,
+                msg
+            );
+            return None;
+        }
+    };
+
+    if config.end == IteratorEnd::False {
+        match conversion_type {
+            ConversionType::Pointer | ConversionType::Borrow => {
+                imports.add("std::ptr", func.version)
+            }
+            _ => imports.add("std::mem", func.version),
+        }
+    }
+    if let Ok(s) = used_rust_type(env, item_tid) {
+        imports.add_used_type(&s, func.version);
+    }
+
+    Some(Info {
+        glib_name: func.glib_name.clone(),
+        item: config.item.clone(),
+        end: config.end,
+        transfer: transfer,
+        conversion_type: conversion_type,
+        mut_self: func.parameters.c_parameters[0].ref_mode == RefMode::ByRefMut,
+        version: func.version,
+    })
+}
+
+/// Returns the type and the transfer of the items.
+fn check_next(
+    func: &FuncInfo,
+    end: IteratorEnd,
+) -> Result<(library::TypeId, Transfer), &'static str> {
+    if func.kind != library::FunctionKind::Method {
+        return Err("next function must be a method");
+    }
+    match end {
+        IteratorEnd::Null => {
+            if func.parameters.c_parameters.len() != 1 {
+                return Err("next function must not have parameters");
+            }
+            match func.ret.parameter {
+                Some(ref ret) => Ok((ret.typ, ret.transfer)),
+                None => Err("next function returns nothing"),
+            }
+        }
+        IteratorEnd::False => {
+            match func.ret.parameter {
+                Some(ref ret) if ret.typ == library::TypeId::tid_bool() => (),
+                _ => return Err("next function doesn't return a boolean"),
+            }
+            let outs: Vec<_> = func.outs.iter().filter(|par| !par.name.is_empty()).collect();
+            if func.parameters.c_parameters.len() != 2 || outs.len() != 1 {
+                return Err("next function must have exactly one out parameter");
+            }
+            Ok((outs[0].typ, outs[0].transfer))
+        }
+    }
+}
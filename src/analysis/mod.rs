@@ -1,20 +1,30 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
+use config::error::Result;
 use env::Env;
 use library;
 use library::{Type, TypeId};
+use nameutil::split_namespace_name;
 
+pub mod alias;
+pub mod as_ref_str;
 pub mod bounds;
 pub mod c_type;
 pub mod child_properties;
 pub mod class_hierarchy;
 pub mod conversion_type;
+pub mod eq_str;
 pub mod ffi_type;
+pub mod from_str;
 pub mod function_parameters;
 pub mod functions;
 pub mod general;
+pub mod identifiers;
 pub mod imports;
+pub mod index_getter;
 pub mod info_base;
+pub mod init_function;
+pub mod iterator_next;
 pub mod namespaces;
 pub mod object;
 pub mod out_parameters;
@@ -38,9 +48,11 @@ pub struct Analysis {
     pub objects: BTreeMap<String, object::Info>,
     pub records: BTreeMap<String, record::Info>,
     pub global_functions: Option<info_base::InfoBase>,
+    pub alias_imports: imports::Imports,
+    pub init_function: Option<init_function::Info>,
 }
 
-pub fn run(env: &mut Env) {
+pub fn run(env: &mut Env) -> Result<()> {
     let mut to_analyze: Vec<(TypeId, Vec<TypeId>)> = Vec::with_capacity(env.config.objects.len());
     for obj in env.config.objects.values() {
         if obj.status.ignored() {
@@ -74,11 +86,182 @@ pub fn run(env: &mut Env) {
             "Not analyzed {} objects due unfinished dependencies",
             to_analyze.len()
         );
-        return;
+        return Ok(());
     }
 
     // Analyze free functions as the last step once all types are analyzed
     analyze_global_functions(env);
+    env.analysis.alias_imports = alias::analyze_imports(env);
+
+    try!(rebase_partitioned_imports(env));
+    prefix_module_path_imports(env);
+
+    identifiers::check(env);
+
+    Ok(())
+}
+
+/// Makes types generated into another crate of the split namespace
+/// referenced as `other_crate::Type`. Fails if the crates would depend on each other,
+/// which Cargo doesn't allow.
+fn rebase_partitioned_imports(env: &mut Env) -> Result<()> {
+    if env.config.partitions.is_empty() {
+        return Ok(());
+    }
+    let main_crate = env.namespaces.main().crate_name.clone();
+    let crates = partitioned_crates(env, &main_crate);
+
+    let partitions = &env.config.partitions;
+    let own_crate = |full_name: &str| {
+        partitions
+            .crate_of(full_name)
+            .unwrap_or(&main_crate[..])
+            .to_owned()
+    };
+    let crate_of = |own_crate: &str, name: &str| match crates.get(name) {
+        Some(krate) if krate != own_crate => Some(krate.clone()),
+        _ => None,
+    };
+
+    let mut owners = imports_by_owner(&mut env.analysis);
+
+    let mut dependencies: BTreeSet<(String, String)> = BTreeSet::new();
+    for &(ref full_name, ref imports) in &owners {
+        let own_crate = own_crate(full_name);
+        for (name, _) in imports.iter() {
+            if let Some(krate) = crate_of(&own_crate, name) {
+                dependencies.insert((own_crate.clone(), krate));
+            }
+        }
+    }
+    if let Some((krate, dependency)) = cyclic_dependency(&dependencies) {
+        bail!(
+            "Crates `{}` and `{}` of the partitioned namespace would depend on each other, \
+             move the types referencing each other into the same crate",
+            krate,
+            dependency
+        );
+    }
+
+    for &mut (ref full_name, ref mut imports) in &mut owners {
+        let own_crate = own_crate(full_name);
+        imports.rebase(|name| crate_of(&own_crate, name));
+    }
+    Ok(())
+}
+
+/// Maps the names of the generated types and traits to the crates they're generated into.
+fn partitioned_crates(env: &Env, main_crate: &str) -> HashMap<String, String> {
+    let mut crates: HashMap<String, String> = HashMap::new();
+
+    // Enumerations, flags and aliases always stay in the main crate
+    let main_ns = &env.library.namespace(library::MAIN_NAMESPACE).name;
+    for obj in env.config.objects.values() {
+        if !obj.status.need_generate() {
+            continue;
+        }
+        let (ns, name) = split_namespace_name(&obj.name);
+        if ns != Some(&main_ns[..]) {
+            continue;
+        }
+        match env.library.find_type(0, &obj.name).map(|tid| env.type_(tid)) {
+            Some(&Type::Enumeration(_)) | Some(&Type::Bitfield(_)) | Some(&Type::Alias(_)) => {
+                crates.insert(name.to_owned(), main_crate.to_owned());
+            }
+            _ => {}
+        }
+    }
+
+    let partitions = &env.config.partitions;
+    for info in env.analysis.objects.values() {
+        let krate = partitions.crate_of(&info.full_name).unwrap_or(main_crate);
+        crates.insert(info.name.clone(), krate.to_owned());
+        crates.insert(info.trait_name.clone(), krate.to_owned());
+    }
+    for info in env.analysis.records.values() {
+        let krate = partitions.crate_of(&info.full_name).unwrap_or(main_crate);
+        crates.insert(info.name.clone(), krate.to_owned());
+    }
+    crates
+}
+
+fn imports_by_owner(analysis: &mut Analysis) -> Vec<(String, &mut imports::Imports)> {
+    let mut owners = Vec::new();
+    for info in analysis.objects.values_mut() {
+        owners.push((info.full_name.clone(), &mut info.base.imports));
+    }
+    for info in analysis.records.values_mut() {
+        owners.push((info.full_name.clone(), &mut info.base.imports));
+    }
+    if let Some(ref mut info) = analysis.global_functions {
+        owners.push((info.full_name.clone(), &mut info.imports));
+    }
+    // Aliases are always generated into the main crate
+    owners.push((String::new(), &mut analysis.alias_imports));
+    owners
+}
+
+/// Finds a dependency between crates which leads back to the depending crate.
+fn cyclic_dependency(dependencies: &BTreeSet<(String, String)>) -> Option<(String, String)> {
+    dependencies
+        .iter()
+        .find(|&&(ref krate, ref dependency)| depends_on(dependencies, dependency, krate))
+        .cloned()
+}
+
+fn depends_on(dependencies: &BTreeSet<(String, String)>, krate: &str, target: &str) -> bool {
+    let mut visited = HashSet::new();
+    let mut pending = vec![krate];
+    while let Some(krate) = pending.pop() {
+        if krate == target {
+            return true;
+        }
+        if !visited.insert(krate) {
+            continue;
+        }
+        pending.extend(
+            dependencies
+                .iter()
+                .filter(|&&(ref from, _)| from == krate)
+                .map(|&(_, ref to)| &to[..]),
+        );
+    }
+    false
+}
+
+/// Makes the generated types referenced as `module_path::Type`
+/// when the bindings aren't placed at the crate root.
+fn prefix_module_path_imports(env: &mut Env) {
+    let module_path = match env.config.module_path {
+        Some(ref module_path) => module_path.clone(),
+        None => return,
+    };
+    let main_ns = &env.library.namespace(library::MAIN_NAMESPACE).name;
+
+    let mut generated: HashSet<String> = env.config
+        .objects
+        .values()
+        .filter(|obj| obj.status.need_generate())
+        .filter(|obj| split_namespace_name(&obj.name).0 == Some(&main_ns[..]))
+        .map(|obj| split_namespace_name(&obj.name).1.to_owned())
+        .collect();
+    generated.extend(env.analysis.objects.values().map(|info| info.trait_name.clone()));
+
+    let module_path_of = |name: &str| if generated.contains(name) {
+        Some(module_path.clone())
+    } else {
+        None
+    };
+    for info in env.analysis.objects.values_mut() {
+        info.base.imports.rebase(&module_path_of);
+    }
+    for info in env.analysis.records.values_mut() {
+        info.base.imports.rebase(&module_path_of);
+    }
+    if let Some(ref mut info) = env.analysis.global_functions {
+        info.imports.rebase(&module_path_of);
+    }
+    env.analysis.alias_imports.rebase(&module_path_of);
 }
 
 fn analyze_global_functions(env: &mut Env) {
@@ -103,9 +286,10 @@ fn analyze_global_functions(env: &mut Env) {
     imports.add("glib::translate::*", None);
     imports.add("ffi", None);
 
+    let library_functions = functions;
     let functions = functions::analyze(
         env,
-        &functions,
+        &library_functions,
         TypeId::tid_none(),
         obj,
         &mut imports,
@@ -113,8 +297,16 @@ fn analyze_global_functions(env: &mut Env) {
         None,
     );
 
+    let init_function = match env.config.init_function {
+        Some(ref init) => {
+            init_function::analyze(init, obj, &library_functions, &functions, &mut imports)
+        }
+        None => None,
+    };
+
     imports.clean_glib(env);
 
+    env.analysis.init_function = init_function;
     env.analysis.global_functions = Some(info_base::InfoBase {
         full_name: full_name,
         type_id: TypeId::tid_none(),
@@ -1,4 +1,5 @@
 //use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::ops::Index;
 
 use library;
@@ -47,7 +48,7 @@ impl Index<NsId> for Info {
     }
 }
 
-pub fn run(gir: &library::Library) -> Info {
+pub fn run(gir: &library::Library, ffi_crate_names: &BTreeMap<String, String>) -> Info {
     let mut namespaces = Vec::with_capacity(gir.namespaces.len());
     //let mut name_index = HashMap::with_capacity(gir.namespaces.len());
     let mut glib_ns_id = None;
@@ -55,8 +56,11 @@ pub fn run(gir: &library::Library) -> Info {
     for (ns_id, ns) in gir.namespaces.iter().enumerate() {
         let ns_id = ns_id as NsId;
         let crate_name = nameutil::crate_name(&ns.name);
+        // The FFI crate of the main namespace is always imported as `ffi`
         let ffi_crate_name = if ns_id == MAIN {
             "ffi".to_owned()
+        } else if let Some(name) = ffi_crate_names.get(&ns.name) {
+            name.clone()
         } else {
             format!("{}_ffi", crate_name)
         };
@@ -85,3 +89,22 @@ pub fn run(gir: &library::Library) -> Info {
         glib_ns_id: glib_ns_id.expect("Missing `GLib` namespace!"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ffi_crate_name_override() {
+        let mut library = library::Library::new("Gtk");
+        let glib_ns_id = library.add_namespace("GLib");
+        let gdk_ns_id = library.add_namespace("Gdk");
+        let mut ffi_crate_names = BTreeMap::new();
+        ffi_crate_names.insert("Gdk".to_owned(), "gdk_sys".to_owned());
+
+        let namespaces = run(&library, &ffi_crate_names);
+        assert_eq!(namespaces[MAIN].ffi_crate_name, "ffi");
+        assert_eq!(namespaces[glib_ns_id].ffi_crate_name, "glib_ffi");
+        assert_eq!(namespaces[gdk_ns_id].ffi_crate_name, "gdk_sys");
+    }
+}
@@ -18,6 +18,7 @@ pub struct Info {
     pub c_class_type: Option<String>,
     pub get_type: String,
     pub supertypes: Vec<general::StatusedTypeId>,
+    pub ancestors: Vec<supertypes::Ancestor>,
     pub generate_trait: bool,
     pub trait_name: String,
     pub has_constructors: bool,
@@ -29,6 +30,14 @@ pub struct Info {
     pub properties: Vec<properties::Property>,
     pub child_properties: ChildProperties,
     pub signatures: Signatures,
+    pub fundamental: Option<Fundamental>,
+}
+
+/// Memory management of a non-GObject fundamental type
+#[derive(Debug)]
+pub struct Fundamental {
+    pub ref_fn: String,
+    pub unref_fn: String,
 }
 
 impl Info {
@@ -86,6 +95,25 @@ pub fn class(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<Info>
         None => return None,
     };
 
+    if env.is_too_new(&full_name, obj.version.or(klass.version)) {
+        return None;
+    }
+
+    let fundamental = if obj.fundamental.unwrap_or(klass.is_fundamental) {
+        match analyze_fundamental(klass) {
+            Some(fundamental) => Some(fundamental),
+            None => {
+                error!(
+                    "Missing ref or unref function for fundamental type {}",
+                    full_name
+                );
+                return None;
+            }
+        }
+    } else {
+        None
+    };
+
     let mut imports = Imports::new();
     imports.add("glib::translate::*", None);
     imports.add("ffi", None);
@@ -93,8 +121,20 @@ pub fn class(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<Info>
     imports.add("gobject_ffi", None);
     imports.add("std::mem", None);
     imports.add("std::ptr", None);
+    if env.config.generate_downgrade && fundamental.is_none() {
+        imports.add("glib::ObjectExt", None);
+        imports.add("glib::WeakRef", None);
+    }
 
     let supertypes = supertypes::analyze(env, class_tid, &mut imports);
+    let ancestors = if obj.as_ref_ancestors {
+        supertypes::ancestors(env, &supertypes)
+    } else {
+        Vec::new()
+    };
+    if !ancestors.is_empty() {
+        imports.add("glib::object::Cast", None);
+    }
 
     let mut generate_trait = obj.generate_trait;
     let trait_name = obj.trait_name
@@ -158,9 +198,28 @@ pub fn class(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<Info>
         klass.deprecated_version,
     );
 
+    let as_ref_str = as_ref_str::analyze(env, obj, &functions, &mut imports);
+    let eq_str = eq_str::analyze(env, obj, &functions, &mut imports);
+    let iterator_next = iterator_next::analyze(env, obj, &functions, &mut imports);
+    let index_getter = index_getter::analyze(env, obj, &functions, &mut imports);
+    let from_str = from_str::analyze(env, obj, &functions, &mut imports);
+    if let Some(ref borrowed_type) = obj.borrowed_type {
+        imports.add_used_type(borrowed_type, None);
+    }
+
     let child_properties =
         child_properties::analyze(env, obj.child_properties.as_ref(), class_tid, &mut imports);
 
+    if obj.identity_eq {
+        error!(
+            "identity_eq for {} is redundant, objects are compared and hashed by identity",
+            obj.name
+        );
+    }
+    if env.config.generate_value_from {
+        imports.add("glib::Value", None);
+    }
+
     let has_methods = functions
         .iter()
         .any(|f| f.kind == library::FunctionKind::Method);
@@ -194,12 +253,21 @@ pub fn class(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<Info>
         type_id: class_tid,
         name: name,
         functions: functions,
+        generate_eq: special_functions::check_generate_eq(&obj.name, obj.generate_eq, &specials),
         specials: specials,
         imports: imports,
         version: version,
         deprecated_version: deprecated_version,
-        cfg_condition: obj.cfg_condition.clone(),
+        cfg_condition: env.config
+            .platform_cfg
+            .cfg_condition(obj.cfg_condition.clone(), &klass.c_type),
         concurrency: obj.concurrency,
+        as_ref_str: as_ref_str,
+        eq_str: eq_str,
+        iterator_next: iterator_next,
+        index_getter: index_getter,
+        from_str: from_str,
+        borrowed_type: obj.borrowed_type.clone(),
     };
 
     // patch up trait methods in the symbol table
@@ -221,6 +289,7 @@ pub fn class(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<Info>
         c_class_type: klass.c_class_type.clone(),
         get_type: klass.glib_get_type.clone(),
         supertypes: supertypes,
+        ancestors: ancestors,
         generate_trait: generate_trait,
         trait_name: trait_name,
         has_constructors: has_constructors,
@@ -232,11 +301,35 @@ pub fn class(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<Info>
         properties: properties,
         child_properties: child_properties,
         signatures: signatures,
+        fundamental: fundamental,
     };
 
     Some(info)
 }
 
+fn analyze_fundamental(klass: &library::Class) -> Option<Fundamental> {
+    let find_method = |names: &[&str]| {
+        klass
+            .functions
+            .iter()
+            .filter(|f| f.kind == library::FunctionKind::Method)
+            .find(|f| names.contains(&&f.name[..]))
+            .and_then(|f| f.c_identifier.clone())
+    };
+    let ref_fn = klass
+        .ref_fn
+        .clone()
+        .or_else(|| find_method(&["ref_sink", "ref"]));
+    let unref_fn = klass.unref_fn.clone().or_else(|| find_method(&["unref"]));
+    match (ref_fn, unref_fn) {
+        (Some(ref_fn), Some(unref_fn)) => Some(Fundamental {
+            ref_fn: ref_fn,
+            unref_fn: unref_fn,
+        }),
+        _ => None,
+    }
+}
+
 pub fn interface(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<Info> {
     info!("Analyzing interface {}", obj.name);
     let full_name = obj.name.clone();
@@ -255,6 +348,10 @@ pub fn interface(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<I
         None => return None,
     };
 
+    if env.is_too_new(&full_name, obj.version.or(iface.version)) {
+        return None;
+    }
+
     let mut imports = Imports::new();
     imports.add("glib::translate::*", None);
     imports.add("ffi", None);
@@ -263,6 +360,10 @@ pub fn interface(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<I
     imports.add("gobject_ffi", None);
     imports.add("std::mem", None);
     imports.add("std::ptr", None);
+    if env.config.generate_downgrade {
+        imports.add("glib::ObjectExt", None);
+        imports.add("glib::WeakRef", None);
+    }
 
     let supertypes = supertypes::analyze(env, iface_tid, &mut imports);
 
@@ -313,9 +414,18 @@ pub fn interface(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<I
         iface.deprecated_version,
     );
 
+    let as_ref_str = as_ref_str::analyze(env, obj, &functions, &mut imports);
+    let eq_str = eq_str::analyze(env, obj, &functions, &mut imports);
+    let iterator_next = iterator_next::analyze(env, obj, &functions, &mut imports);
+    let index_getter = index_getter::analyze(env, obj, &functions, &mut imports);
+    let from_str = from_str::analyze(env, obj, &functions, &mut imports);
+
     if !properties.is_empty() {
         imports.add("glib", None);
     }
+    if env.config.generate_value_from {
+        imports.add("glib::Value", None);
+    }
 
     //don't `use` yourself
     imports.remove(&name);
@@ -331,8 +441,17 @@ pub fn interface(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<I
         imports: imports,
         version: version,
         deprecated_version: deprecated_version,
-        cfg_condition: obj.cfg_condition.clone(),
+        cfg_condition: env.config
+            .platform_cfg
+            .cfg_condition(obj.cfg_condition.clone(), &iface.c_type),
         concurrency: obj.concurrency,
+        as_ref_str: as_ref_str,
+        eq_str: eq_str,
+        iterator_next: iterator_next,
+        index_getter: index_getter,
+        from_str: from_str,
+        generate_eq: false,
+        borrowed_type: None,
     };
 
     let has_methods = !base.methods().is_empty();
@@ -84,22 +84,31 @@ pub fn analyze(
         info.mode = Mode::None;
     }
     if info.mode == Mode::Combined || info.mode == Mode::Throws(true) {
-        let mut ret = func.ret.clone();
         //TODO: switch to use analyzed returns (it add too many Return<Option<>>)
-        if let Some(val) = nullable_override {
-            ret.nullable = val;
-        }
-        info.params.insert(0, ret);
+        info.params.insert(0, function_return(&func.ret, nullable_override));
     }
 
     (info, unsupported_outs)
 }
 
+/// The function return as the first out. A nullable return of a throwing
+/// function is returned as `Result<Option<T>, Error>`, where a NULL without
+/// an error is `Ok(None)`.
+fn function_return(ret: &Parameter, nullable_override: Option<Nullable>) -> Parameter {
+    let mut ret = ret.clone();
+    if let Some(val) = nullable_override {
+        ret.nullable = val;
+    }
+    ret
+}
+
 pub fn analyze_imports(env: &Env, func: &Function, imports: &mut Imports) {
     for par in &func.parameters {
         if par.direction == ParameterDirection::Out {
             match *env.library.type_(par.typ) {
-                Type::Bitfield(..) | Type::Enumeration(..) => imports.add("std::mem", func.version),
+                Type::Bitfield(..) | Type::Enumeration(..) | Type::FixedArray(..) => {
+                    imports.add("std::mem", func.version)
+                }
                 Type::Fundamental(fund)
                     if fund != Fundamental::Utf8 && fund != Fundamental::Filename =>
                 {
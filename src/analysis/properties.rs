@@ -1,4 +1,6 @@
 use analysis::bounds::Bound;
+use analysis::ffi_type::{ffi_type, used_ffi_type};
+use analysis::functions::stability_cfg_condition;
 use analysis::imports::Imports;
 use analysis::ref_mode::RefMode;
 use analysis::rust_type::*;
@@ -27,7 +29,9 @@ pub struct Property {
     pub set_in_ref_mode: RefMode,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub cfg_condition: Option<String>,
     pub bound: Option<Bound>,
+    pub object_ffi_type: Option<String>, //for getter of objects
 }
 
 pub fn analyze(
@@ -53,6 +57,14 @@ pub fn analyze(
         if env.is_totally_deprecated(prop.deprecated_version) {
             continue;
         }
+        let version = configured_properties
+            .iter()
+            .filter_map(|f| f.version)
+            .min()
+            .or(prop.version);
+        if env.is_too_new(&prop.name, version) {
+            continue;
+        }
 
         let (getter, setter, notify_signal) = analyze_property(
             env,
@@ -78,29 +90,35 @@ pub fn analyze(
         let type_string = rust_type(env, prop.typ);
         let used_type_string = used_rust_type(env, prop.typ);
         if let Some(prop) = getter {
-            if let Ok(ref s) = used_type_string {
-                imports.add_used_type(s, prop.version);
-            }
-            if prop.conversion != PropertyConversion::Direct {
-                imports.add("std::mem::transmute", prop.version);
-            }
-            if type_string.is_ok() && prop.default_value.is_some() {
-                imports.add("glib::Value", prop.version);
+            {
+                let cfg = prop.cfg_condition.as_ref().map(|s| &s[..]);
+                if let Ok(ref s) = used_type_string {
+                    imports.add_used_type_with_constraint(s, prop.version, cfg);
+                }
+                if prop.conversion != PropertyConversion::Direct {
+                    imports.add_with_constraint("std::mem::transmute", prop.version, cfg);
+                }
+                if type_string.is_ok() && prop.default_value.is_some() {
+                    imports.add_with_constraint("glib::Value", prop.version, cfg);
+                }
             }
 
             properties.push(prop);
         }
         if let Some(prop) = setter {
-            if let Ok(ref s) = used_type_string {
-                imports.add_used_type(s, prop.version);
-            }
-            if type_string.is_ok() {
-                imports.add("glib::Value", prop.version);
-            }
+            {
+                let cfg = prop.cfg_condition.as_ref().map(|s| &s[..]);
+                if let Ok(ref s) = used_type_string {
+                    imports.add_used_type_with_constraint(s, prop.version, cfg);
+                }
+                if type_string.is_ok() {
+                    imports.add_with_constraint("glib::Value", prop.version, cfg);
+                }
 
-            if prop.bound.is_some() {
-                imports.add("glib", prop.version);
-                imports.add("glib::object::IsA", prop.version);
+                if prop.bound.is_some() {
+                    imports.add_with_constraint("glib", prop.version, cfg);
+                    imports.add_with_constraint("glib::object::IsA", prop.version, cfg);
+                }
             }
 
             properties.push(prop);
@@ -130,6 +148,11 @@ fn analyze_property(
         .filter_map(|f| f.version)
         .min()
         .or(prop.version);
+    let cfg_condition = stability_cfg_condition(
+        None,
+        prop.stability,
+        env.config.unstable_feature.as_ref().map(|s| &s[..]),
+    );
     let name_for_func = nameutil::signal_to_snake(&name);
     let var_name = nameutil::mangle_keywords(&*name_for_func).into_owned();
     let get_func_name = format!("get_property_{}", name_for_func);
@@ -176,6 +199,23 @@ fn analyze_property(
         set_in_ref_mode = RefMode::ByRef;
     }
     let nullable = library::Nullable(set_in_ref_mode.is_ref());
+    let object_ffi_type = match *type_ {
+        library::Type::Class(library::Class { ref c_type, .. }) |
+        library::Type::Interface(library::Interface { ref c_type, .. }) => {
+            let c_type = format!("{}*", c_type);
+            if readable {
+                if let Some(s) = used_ffi_type(env, prop.typ, &c_type) {
+                    imports.add_used_type_with_constraint(
+                        &s,
+                        prop_version,
+                        cfg_condition.as_ref().map(|s| &s[..]),
+                    );
+                }
+            }
+            ffi_type(env, prop.typ, &c_type).ok()
+        }
+        _ => None,
+    };
     let getter = if readable {
         Some(Property {
             name: name.clone(),
@@ -190,7 +230,9 @@ fn analyze_property(
             set_in_ref_mode: set_in_ref_mode,
             version: prop_version,
             deprecated_version: prop.deprecated_version,
+            cfg_condition: cfg_condition.clone(),
             bound: None,
+            object_ffi_type: object_ffi_type,
         })
     } else {
         None
@@ -211,7 +253,9 @@ fn analyze_property(
             set_in_ref_mode: set_in_ref_mode,
             version: prop_version,
             deprecated_version: prop.deprecated_version,
+            cfg_condition: cfg_condition.clone(),
             bound: bound,
+            object_ffi_type: None,
         })
     } else {
         None
@@ -242,6 +286,7 @@ fn analyze_property(
             is_action: false,
             version: prop_version,
             deprecated_version: prop.deprecated_version,
+            stability: prop.stability,
             doc: None,
             doc_deprecated: None,
         },
@@ -255,16 +300,19 @@ fn analyze_property(
     );
 
     let notify_signal = if trampoline_name.is_ok() {
-        imports.add_used_types(&used_types, prop_version);
-        if generate_trait {
-            imports.add("glib", prop_version);
-            imports.add("glib::object::Downcast", prop_version);
+        {
+            let cfg = cfg_condition.as_ref().map(|s| &s[..]);
+            imports.add_used_types_with_constraint(&used_types, prop_version, cfg);
+            if generate_trait {
+                imports.add_with_constraint("glib", prop_version, cfg);
+                imports.add_with_constraint("glib::object::Downcast", prop_version, cfg);
+            }
+            imports.add_with_constraint("glib::signal::connect", prop_version, cfg);
+            imports.add_with_constraint("glib::signal::SignalHandlerId", prop_version, cfg);
+            imports.add_with_constraint("std::mem::transmute", prop_version, cfg);
+            imports.add_with_constraint("std::boxed::Box as Box_", prop_version, cfg);
+            imports.add_with_constraint("glib_ffi", prop_version, cfg);
         }
-        imports.add("glib::signal::connect", prop_version);
-        imports.add("glib::signal::SignalHandlerId", prop_version);
-        imports.add("std::mem::transmute", prop_version);
-        imports.add("std::boxed::Box as Box_", prop_version);
-        imports.add("glib_ffi", prop_version);
 
         Some(signals::Info {
             connect_name: format!("connect_property_{}_notify", name_for_func),
@@ -273,6 +321,7 @@ fn analyze_property(
             action_emit_name: None,
             version: prop_version,
             deprecated_version: prop.deprecated_version,
+            cfg_condition: cfg_condition,
             doc_hidden: false,
         })
     } else {
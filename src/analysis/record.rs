@@ -5,6 +5,8 @@ use env::Env;
 use library;
 use nameutil::*;
 use super::*;
+use super::functions::default_from_new;
+use super::general::derive_uses;
 use super::imports::Imports;
 use super::info_base::InfoBase;
 use traits::*;
@@ -13,6 +15,20 @@ use traits::*;
 pub struct Info {
     pub base: InfoBase,
     pub glib_get_type: Option<String>,
+    pub boxed_inline: bool,
+    pub extra_derives: Vec<String>,
+    pub array_setters: Vec<ArraySetter>,
+    pub default_fields: Vec<String>,
+    pub identity_eq: bool,
+}
+
+/// A setter copying a slice into a fixed-size array field of an inline boxed record.
+#[derive(Debug, PartialEq)]
+pub struct ArraySetter {
+    pub name: String,
+    pub field_name: String,
+    pub element_type: &'static str,
+    pub size: u16,
 }
 
 impl Deref for Info {
@@ -52,6 +68,10 @@ pub fn new(env: &Env, obj: &GObject) -> Option<Info> {
         None => return None,
     };
 
+    if env.is_too_new(&full_name, obj.version.or(record.version)) {
+        return None;
+    }
+
     let mut imports = Imports::new();
     imports.add("glib::translate::*", None);
     imports.add("ffi", None);
@@ -60,6 +80,9 @@ pub fn new(env: &Env, obj: &GObject) -> Option<Info> {
     imports.add("std::ptr", None);
     if record.glib_get_type.is_some() {
         imports.add("gobject_ffi", None);
+        if env.config.generate_value_from {
+            imports.add("glib::Value", None);
+        }
     }
 
     let mut functions = functions::analyze(
@@ -81,6 +104,15 @@ pub fn new(env: &Env, obj: &GObject) -> Option<Info> {
         record.deprecated_version,
     );
 
+    let as_ref_str = as_ref_str::analyze(env, obj, &functions, &mut imports);
+    let eq_str = eq_str::analyze(env, obj, &functions, &mut imports);
+    let iterator_next = iterator_next::analyze(env, obj, &functions, &mut imports);
+    let index_getter = index_getter::analyze(env, obj, &functions, &mut imports);
+    let from_str = from_str::analyze(env, obj, &functions, &mut imports);
+    if let Some(ref borrowed_type) = obj.borrowed_type {
+        imports.add_used_type(borrowed_type, None);
+    }
+
     let is_shared = specials.get(&special_functions::Type::Ref).is_some()
         && specials.get(&special_functions::Type::Unref).is_some();
     if is_shared {
@@ -90,6 +122,62 @@ pub fn new(env: &Env, obj: &GObject) -> Option<Info> {
 
     special_functions::analyze_imports(&specials, &mut imports);
 
+    if obj.boxed_inline {
+        if record.glib_get_type.is_none() {
+            error!("Boxed inline type {} has no GType", full_name);
+            return None;
+        }
+        imports.add("glib::StaticType", None);
+        imports.add("glib::Type", None);
+        imports.add("glib::Value", None);
+        imports.add("glib::value::FromValue", None);
+        imports.add("glib::value::FromValueOptional", None);
+        imports.add("glib::value::SetValue", None);
+        for derive in derive_uses(&obj.extra_derives) {
+            imports.add(derive, None);
+        }
+    } else if let Err(msg) =
+        check_memory_management(&full_name, &specials, &record.glib_get_type)
+    {
+        error!("{}", msg);
+        return None;
+    } else if !obj.extra_derives.is_empty() {
+        // A derived `Clone` would share the pointer instead of calling the copy function
+        warn!(
+            "extra_derives of {} are ignored, only boxed_inline types derive traits",
+            full_name
+        );
+    }
+
+    let identity_eq = if obj.boxed_inline && obj.identity_eq {
+        error!(
+            "identity_eq for {} conflicts with boxed_inline, which is compared by value",
+            full_name
+        );
+        false
+    } else {
+        special_functions::check_identity_eq(&obj.name, obj.identity_eq, &specials)
+    };
+    if identity_eq {
+        imports.add("std::hash", None);
+    }
+
+    let array_setters = if obj.boxed_inline {
+        analyze_array_setters(env, record)
+    } else {
+        Vec::new()
+    };
+
+    let default_fields = if obj.boxed_inline
+        && !obj.manual_traits.iter().any(|t| t == "Default")
+        && !obj.extra_derives.iter().any(|d| d == "Default")
+        && default_from_new(&functions).is_none()
+    {
+        analyze_default_fields(&env.library, record)
+    } else {
+        Vec::new()
+    };
+
     //don't `use` yourself
     imports.remove(&name);
 
@@ -100,18 +188,187 @@ pub fn new(env: &Env, obj: &GObject) -> Option<Info> {
         type_id: record_tid,
         name: name,
         functions: functions,
+        generate_eq: special_functions::check_generate_eq(&obj.name, obj.generate_eq, &specials),
         specials: specials,
         imports: imports,
         version: version,
         deprecated_version: deprecated_version,
-        cfg_condition: obj.cfg_condition.clone(),
+        cfg_condition: env.config
+            .platform_cfg
+            .cfg_condition(obj.cfg_condition.clone(), &record.c_type),
         concurrency: obj.concurrency,
+        as_ref_str: as_ref_str,
+        eq_str: eq_str,
+        iterator_next: iterator_next,
+        index_getter: index_getter,
+        from_str: from_str,
+        borrowed_type: obj.borrowed_type.clone(),
     };
 
     let info = Info {
         base: base,
         glib_get_type: record.glib_get_type.clone(),
+        boxed_inline: obj.boxed_inline,
+        extra_derives: obj.extra_derives.clone(),
+        array_setters: array_setters,
+        default_fields: default_fields,
+        identity_eq: identity_eq,
     };
 
     Some(info)
 }
+
+fn analyze_array_setters(env: &Env, record: &library::Record) -> Vec<ArraySetter> {
+    record
+        .fields
+        .iter()
+        .filter(|field| !field.private && field.bits.is_none())
+        .filter_map(|field| match *env.type_(field.typ) {
+            library::Type::FixedArray(inner, size) => {
+                rust_type::direct_array_element_type(env, inner).map(|element_type| {
+                    ArraySetter {
+                        name: format!("set_{}", field.name),
+                        field_name: mangle_keywords(&*field.name).into_owned(),
+                        element_type: element_type,
+                        size: size,
+                    }
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Fields of an inline boxed record defaulted one by one, if all of them are
+/// numbers or enumerations. Zeroed pointers and nested structs aren't valid defaults.
+fn analyze_default_fields(library: &library::Library, record: &library::Record) -> Vec<String> {
+    let is_pod = record.fields.iter().all(|field| {
+        let is_ptr = field.c_type.as_ref().map_or(false, |c_type| c_type.ends_with('*'));
+        field.bits.is_none() && !is_ptr && is_pod_type(library, field.typ)
+    });
+    if !is_pod {
+        return Vec::new();
+    }
+    record
+        .fields
+        .iter()
+        .map(|field| mangle_keywords(&*field.name).into_owned())
+        .collect()
+}
+
+fn is_pod_type(library: &library::Library, tid: library::TypeId) -> bool {
+    use library::Fundamental::*;
+    match *library.type_(tid) {
+        library::Type::Fundamental(fund) => match fund {
+            Boolean | Int8 | UInt8 | Int16 | UInt16 | Int32 | UInt32 | Int64 | UInt64 | Char
+            | UChar | Short | UShort | Int | UInt | Long | ULong | Size | SSize | Float
+            | Double | UniChar | Type | IntPtr | UIntPtr => true,
+            _ => false,
+        },
+        library::Type::Enumeration(..) => true,
+        // `Default` is only implemented for arrays of up to 32 elements
+        library::Type::FixedArray(inner, size) => size <= 32 && is_pod_type(library, inner),
+        _ => false,
+    }
+}
+
+/// Checks that the record can be wrapped as a `Shared` or `Boxed` type.
+/// Without `copy`/`free` the type's GType is used via `g_boxed_copy`/`g_boxed_free`.
+fn check_memory_management(
+    full_name: &str,
+    specials: &special_functions::Infos,
+    glib_get_type: &Option<String>,
+) -> Result<(), String> {
+    use super::special_functions::Type;
+    let has = |type_| specials.get(&type_).is_some();
+
+    if (has(Type::Ref) && has(Type::Unref)) || (has(Type::Copy) && has(Type::Free))
+        || glib_get_type.is_some()
+    {
+        return Ok(());
+    }
+
+    let missing = [
+        (Type::Copy, "copy"),
+        (Type::Free, "free"),
+        (Type::Ref, "ref"),
+        (Type::Unref, "unref"),
+    ].iter()
+        .filter(|&&(type_, _)| !has(type_))
+        .map(|&(_, name)| name)
+        .collect::<Vec<_>>();
+    Err(format!(
+        "Missing memory management functions for {}: no {} and no GType, \
+         set its status to \"manual\" and implement it by hand",
+        full_name,
+        missing.join("/")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::special_functions::{Infos, Type};
+
+    #[test]
+    fn boxed_without_copy_and_gtype() {
+        let mut specials = Infos::new();
+        specials.insert(Type::Free, "gtk_foo_free".into());
+        assert_eq!(
+            check_memory_management("Gtk.Foo", &specials, &None),
+            Err(
+                "Missing memory management functions for Gtk.Foo: no copy/ref/unref and \
+                 no GType, set its status to \"manual\" and implement it by hand"
+                    .into()
+            )
+        );
+    }
+
+    #[test]
+    fn boxed_without_copy_with_gtype() {
+        let mut specials = Infos::new();
+        specials.insert(Type::Free, "gtk_foo_free".into());
+        assert_eq!(
+            check_memory_management("Gtk.Foo", &specials, &Some("gtk_foo_get_type".into())),
+            Ok(())
+        );
+    }
+
+    fn record_with(library: &library::Library, fields: &[(&str, &str)]) -> library::Record {
+        library::Record {
+            name: "Rectangle".into(),
+            c_type: "GdkRectangle".into(),
+            fields: fields
+                .iter()
+                .map(|&(name, typ)| library::Field {
+                    name: name.into(),
+                    typ: library.find_type(library::MAIN_NAMESPACE, typ).unwrap(),
+                    c_type: Some(typ.into()),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pod_record_defaults_field_wise() {
+        let library = library::Library::new("Gdk");
+        let record = record_with(&library, &[("x", "gint"), ("width", "gint"), ("type", "gint")]);
+        assert_eq!(
+            analyze_default_fields(&library, &record),
+            vec!["x", "width", "type_"]
+        );
+
+        let record = record_with(&library, &[("x", "gint"), ("data", "gpointer")]);
+        assert!(analyze_default_fields(&library, &record).is_empty());
+    }
+
+    #[test]
+    fn boxed_with_copy_and_free() {
+        let mut specials = Infos::new();
+        specials.insert(Type::Copy, "gtk_foo_copy".into());
+        specials.insert(Type::Free, "gtk_foo_free".into());
+        assert_eq!(check_memory_management("Gtk.Foo", &specials, &None), Ok(()));
+    }
+}
@@ -8,6 +8,10 @@ pub enum RecordType {
 
 impl RecordType {
     pub fn of(record: &library::Record) -> RecordType {
+        // `glib::Variant` is refcounted even when its ref functions aren't introspected
+        if record.c_type == "GVariant" {
+            return RecordType::Refcounted;
+        }
         let mut has_copy = false;
         let mut has_free = false;
         let mut has_ref = false;
@@ -37,3 +41,21 @@ impl RecordType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variant_is_refcounted() {
+        let record = library::Record {
+            name: "Variant".into(),
+            c_type: "GVariant".into(),
+            ..Default::default()
+        };
+        match RecordType::of(&record) {
+            RecordType::Refcounted => (),
+            _ => panic!("GVariant isn't refcounted"),
+        }
+    }
+}
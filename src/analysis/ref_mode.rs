@@ -42,7 +42,8 @@ impl RefMode {
             Interface(..) |
             List(..) |
             SList(..) |
-            CArray(..) => if direction == library::ParameterDirection::In {
+            CArray(..) |
+            FixedArray(..) => if direction == library::ParameterDirection::In {
                 RefMode::ByRef
             } else {
                 RefMode::None
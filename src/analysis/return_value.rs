@@ -1,8 +1,10 @@
 use analysis::ref_mode::RefMode;
 use analysis::rust_type::*;
 use analysis::imports::Imports;
+use analysis::function_parameters::is_bytes;
 use analysis::namespaces;
 use config;
+use config::{InvalidChar, StringType, TimeIntegration};
 use env::Env;
 use library::{self, Nullable, TypeId};
 
@@ -12,6 +14,16 @@ pub struct Info {
     pub base_tid: Option<library::TypeId>, // Some only if need downcast
     pub commented: bool,
     pub bool_return_is_error: Option<String>,
+    /// Returned `GLib.DateTime` converted to a Rust time type
+    pub date_time: Option<TimeIntegration>,
+    /// Returned `time_t` converted to a Rust time type
+    pub time_t: Option<TimeIntegration>,
+    /// Returned borrowed `GLib.Bytes` copied into a `Vec<u8>`
+    pub bytes_to_vec: bool,
+    /// Returned `gunichar` converted without `from_glib`
+    pub invalid_char: Option<InvalidChar>,
+    /// Type of a returned `utf8` string
+    pub string_type: Option<StringType>,
 }
 
 pub fn analyze(
@@ -81,6 +93,56 @@ pub fn analyze(
             Some(m.clone())
         });
 
+    let date_time = match parameter {
+        Some(ref par)
+            if func.kind != library::FunctionKind::Constructor && is_date_time(env, par.typ) =>
+        {
+            env.config.time_integration.rust_type().map(|_| env.config.time_integration)
+        }
+        _ => None,
+    };
+
+    let time_t = match parameter {
+        Some(ref par) if is_time_t(&par.c_type) => {
+            env.config.time_integration.rust_type().map(|_| env.config.time_integration)
+        }
+        _ => None,
+    };
+
+    let bytes_to_vec = match parameter {
+        Some(ref par) => {
+            func.kind != library::FunctionKind::Constructor && is_bytes(env, par.typ)
+                && par.transfer == library::Transfer::None
+        }
+        None => false,
+    };
+
+    let invalid_char = match parameter {
+        Some(ref par) if is_unichar(env, par.typ) => {
+            env.config.invalid_char.from_u32().map(|_| env.config.invalid_char)
+        }
+        _ => None,
+    };
+
+    let string_type = match parameter {
+        Some(ref par) if is_utf8(env, par.typ) => {
+            let string_type = configured_functions
+                .iter()
+                .filter_map(|f| f.string_type)
+                .next()
+                .unwrap_or(env.config.string_type);
+            if string_type == StringType::GString {
+                if env.namespaces.glib_ns_id == namespaces::MAIN {
+                    imports.add("GString", None);
+                } else {
+                    imports.add("glib::GString", None);
+                }
+            }
+            Some(string_type)
+        }
+        _ => None,
+    };
+
     let mut base_tid = None;
 
     if func.kind == library::FunctionKind::Constructor {
@@ -92,9 +154,19 @@ pub fn analyze(
             if par.typ != type_tid {
                 base_tid = Some(par.typ);
             }
+            // Constructors of InitiallyUnowned descendants return a floating
+            // reference, `from_glib_none` sinks it instead of stealing it.
+            let transfer = if env.class_hierarchy
+                .is_initially_unowned(&env.library, type_tid)
+            {
+                library::Transfer::None
+            } else {
+                par.transfer
+            };
             parameter = Some(library::Parameter {
                 typ: type_tid,
                 nullable: nullable_override.unwrap_or(Nullable(false)),
+                transfer: transfer,
                 ..par
             });
         }
@@ -105,6 +177,34 @@ pub fn analyze(
         base_tid: base_tid,
         commented: commented,
         bool_return_is_error: bool_return_error_message,
+        date_time: date_time,
+        time_t: time_t,
+        bytes_to_vec: bytes_to_vec,
+        invalid_char: invalid_char,
+        string_type: string_type,
+    }
+}
+
+fn is_utf8(env: &Env, type_id: library::TypeId) -> bool {
+    match *env.library.type_(type_id) {
+        library::Type::Fundamental(library::Fundamental::Utf8) => true,
+        _ => false,
+    }
+}
+
+pub fn is_date_time(env: &Env, type_id: library::TypeId) -> bool {
+    type_id.full_name(&env.library) == "GLib.DateTime"
+}
+
+/// The GIR types `time_t` as `glong`, only the C type tells them apart.
+pub fn is_time_t(c_type: &str) -> bool {
+    c_type == "time_t"
+}
+
+fn is_unichar(env: &Env, type_id: library::TypeId) -> bool {
+    match *env.library.type_(type_id) {
+        library::Type::Fundamental(library::Fundamental::UniChar) => true,
+        _ => false,
     }
 }
 
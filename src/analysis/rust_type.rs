@@ -1,6 +1,7 @@
 use std::result;
 
 use analysis::ref_mode::RefMode;
+use config::SizeType;
 use env::Env;
 use library::{self, Nullable};
 use super::conversion_type::ConversionType;
@@ -88,8 +89,8 @@ fn rust_type_full(
                 Long => ok("libc::c_long"),   //depends of target system
                 ULong => ok("libc::c_ulong"), //depends of target system
 
-                Size => ok("usize"),  //depends of target system
-                SSize => ok("isize"), //depends of target system
+                Size => ok(env.config.size_type.unsigned()),
+                SSize => ok(env.config.size_type.signed()),
 
                 Float => ok("f32"),
                 Double => ok("f64"),
@@ -114,15 +115,11 @@ fn rust_type_full(
         Alias(ref alias) => {
             rust_type_full(env, alias.typ, nullable, ref_mode).map_any(|_| alias.name.clone())
         }
-        Record(library::Record { ref c_type, .. }) if c_type == "GVariantType" => {
-            if ref_mode.is_ref() {
-                ok("VariantTy")
-            } else {
-                ok("VariantType")
-            }
-        }
         Enumeration(..) | Bitfield(..) | Record(..) | Union(..) | Class(..) | Interface(..) => {
-            let name = type_.get_name().to_owned();
+            let name = match *type_ {
+                Record(library::Record { ref c_type, .. }) => variant_type(c_type, ref_mode),
+                _ => None,
+            }.map_or_else(|| type_.get_name().to_owned(), |name| name.to_owned());
             if env.type_status(&type_id.full_name(&env.library)).ignored() {
                 Err(TypeError::Ignored(name))
             } else {
@@ -137,46 +134,29 @@ fn rust_type_full(
                 Class(..) | Interface(..) => RefMode::None,
                 _ => ref_mode,
             };
-            rust_type_full(env, inner_tid, Nullable(false), inner_ref_mode).map_any(
-                |s| if ref_mode.is_ref() {
-                    format!("[{}]", s)
-                } else {
-                    format!("Vec<{}>", s)
-                },
-            )
+            rust_type_full(env, inner_tid, Nullable(false), inner_ref_mode)
+                .map_any(|s| array_type(&s, ref_mode))
+        }
+        CArray(inner_tid) if is_byte_array_element(env, inner_tid) => {
+            skip_option = true;
+            Ok(array_type("u8", ref_mode))
         }
         CArray(inner_tid)
             if ConversionType::of(env, inner_tid) == ConversionType::Direct =>
         {
-            if let Fundamental(fund) = *env.library.type_(inner_tid) {
-                let array_type = match fund {
-                    Int8 => Some("i8"),
-                    UInt8 => Some("u8"),
-                    Int16 => Some("i16"),
-                    UInt16 => Some("u16"),
-                    Int32 => Some("i32"),
-                    UInt32 => Some("u32"),
-                    Int64 => Some("i64"),
-                    UInt64 => Some("u64"),
-
-                    Int => Some("i32"),  //maybe dependent on target system
-                    UInt => Some("u32"), //maybe dependent on target system
-
-                    Float => Some("f32"),
-                    Double => Some("f64"),
-                    _ => Option::None,
-                };
-
-                if let Some(s) = array_type {
-                    skip_option = true;
-                    if ref_mode.is_ref() {
-                        Ok(format!("[{}]", s))
-                    } else {
-                        Ok(format!("Vec<{}>", s))
-                    }
-                } else {
-                    Err(TypeError::Unimplemented(type_.get_name().to_owned()))
-                }
+            if let Some(s) = direct_array_element_type(env, inner_tid) {
+                skip_option = true;
+                Ok(array_type(s, ref_mode))
+            } else {
+                Err(TypeError::Unimplemented(type_.get_name().to_owned()))
+            }
+        }
+        FixedArray(inner_tid, size)
+            if ConversionType::of(env, inner_tid) == ConversionType::Direct =>
+        {
+            if let Some(s) = direct_array_element_type(env, inner_tid) {
+                skip_option = true;
+                Ok(fixed_array_type(s, size))
             } else {
                 Err(TypeError::Unimplemented(type_.get_name().to_owned()))
             }
@@ -214,6 +194,135 @@ fn rust_type_full(
     rust_type
 }
 
+pub fn direct_array_element_type(env: &Env, type_id: library::TypeId) -> Option<&'static str> {
+    use library::Fundamental::*;
+    if let library::Type::Fundamental(fund) = *env.library.type_(type_id) {
+        match fund {
+            Int8 => Some("i8"),
+            UInt8 => Some("u8"),
+            Int16 => Some("i16"),
+            UInt16 => Some("u16"),
+            Int32 => Some("i32"),
+            UInt32 => Some("u32"),
+            Int64 => Some("i64"),
+            UInt64 => Some("u64"),
+
+            Int => Some("i32"),  //maybe dependent on target system
+            UInt => Some("u32"), //maybe dependent on target system
+
+            Float => Some("f32"),
+            Double => Some("f64"),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Names of the variant records, which don't follow their C names
+fn variant_type(c_type: &str, ref_mode: RefMode) -> Option<&'static str> {
+    match c_type {
+        "GVariant" => Some("Variant"),
+        "GVariantType" => if ref_mode.is_ref() {
+            Some("VariantTy")
+        } else {
+            Some("VariantType")
+        },
+        _ => None,
+    }
+}
+
+/// `guint8*` and `guchar*` arrays are byte buffers and copied into a `Vec<u8>`
+/// as a whole, while `gint8*` ones stay typed arrays of `i8`.
+fn is_byte_array_element(env: &Env, type_id: library::TypeId) -> bool {
+    match *env.library.type_(type_id) {
+        library::Type::Fundamental(fund) => is_byte(fund),
+        _ => false,
+    }
+}
+
+fn is_byte(fund: library::Fundamental) -> bool {
+    use library::Fundamental::*;
+    match fund {
+        UInt8 | UChar => true,
+        _ => false,
+    }
+}
+
+/// Borrowed arrays are passed as slices, e.g. zero-terminated `gchar**` as `&[&str]`,
+/// returned ones are collected into a `Vec`.
+fn array_type(element_type: &str, ref_mode: RefMode) -> String {
+    if ref_mode.is_ref() {
+        format!("[{}]", element_type)
+    } else {
+        format!("Vec<{}>", element_type)
+    }
+}
+
+fn fixed_array_type(element_type: &str, size: u16) -> String {
+    format!("[{}; {}]", element_type, size)
+}
+
+/// Returns the native FFI type if `gsize`/`gssize` are mapped to other Rust types.
+pub fn size_cast(env: &Env, type_id: library::TypeId) -> Option<&'static str> {
+    use library::Fundamental::{SSize, Size};
+    if env.config.size_type == SizeType::Usize {
+        return None;
+    }
+    match *env.library.type_(type_id) {
+        library::Type::Fundamental(Size) => Some("usize"),
+        library::Type::Fundamental(SSize) => Some("isize"),
+        _ => None,
+    }
+}
+
+/// Whether the cast of the type to (or from) its `size_cast` is checked, see
+/// `checked_casts`.
+pub fn is_checked_size_cast(env: &Env, type_id: library::TypeId, to_glib: bool) -> bool {
+    match size_cast(env, type_id) {
+        Some(ffi_type) if env.config.checked_casts => {
+            let rust_type = rust_type(env, type_id).into_string();
+            if to_glib {
+                is_narrowing(&rust_type, ffi_type)
+            } else {
+                is_narrowing(ffi_type, &rust_type)
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Signedness and the minimal and maximal width in bits over the supported targets.
+fn int_range(type_: &str) -> Option<(bool, u8, u8)> {
+    match type_ {
+        "i8" => Some((true, 8, 8)),
+        "u8" => Some((false, 8, 8)),
+        "i16" | "libc::c_short" => Some((true, 16, 16)),
+        "u16" | "libc::c_ushort" => Some((false, 16, 16)),
+        "i32" | "libc::c_int" => Some((true, 32, 32)),
+        "u32" | "libc::c_uint" => Some((false, 32, 32)),
+        "i64" => Some((true, 64, 64)),
+        "u64" => Some((false, 64, 64)),
+        "isize" | "libc::c_long" => Some((true, 32, 64)),
+        "usize" | "libc::c_ulong" => Some((false, 32, 64)),
+        _ => None,
+    }
+}
+
+/// Whether an `as` cast between the integer types can lose values on some target.
+pub fn is_narrowing(from: &str, to: &str) -> bool {
+    match (int_range(from), int_range(to)) {
+        (Some((from_signed, _, from_max)), Some((to_signed, to_min, _))) => {
+            match (from_signed, to_signed) {
+                (true, false) => true,
+                (false, true) => to_min <= from_max,
+                _ => to_min < from_max,
+            }
+        }
+        _ => false,
+    }
+}
+
 pub fn used_rust_type(env: &Env, type_id: library::TypeId) -> Result {
     use library::Type::*;
     match *env.library.type_(type_id) {
@@ -285,7 +394,7 @@ pub fn parameter_rust_type(
             library::ParameterDirection::In | library::ParameterDirection::Return => rust_type,
             _ => Err(TypeError::Unimplemented(into_inner(rust_type))),
         },
-        CArray(..) => match direction {
+        CArray(..) | FixedArray(..) => match direction {
             library::ParameterDirection::In |
             library::ParameterDirection::Out |
             library::ParameterDirection::Return => rust_type,
@@ -308,3 +417,31 @@ fn format_parameter(rust_type: String, direction: library::ParameterDirection) -
 fn implemented_in_main_namespace(library: &library::Library, type_id: library::TypeId) -> bool {
     type_id.full_name(library) == "GLib.Error"
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn narrowing_int_casts() {
+        assert!(is_narrowing("usize", "i32"));
+        assert!(is_narrowing("usize", "u32"));
+        assert!(is_narrowing("u64", "usize"));
+        assert!(is_narrowing("i32", "u32"));
+        assert!(!is_narrowing("usize", "u64"));
+        assert!(!is_narrowing("u32", "i64"));
+        assert!(!is_narrowing("usize", "usize"));
+        assert!(!is_narrowing("usize", "f64"));
+    }
+
+    #[test]
+    fn byte_arrays() {
+        use library::Fundamental::*;
+        assert!(is_byte(UInt8));
+        assert!(is_byte(UChar));
+        assert!(!is_byte(Int8));
+        assert!(!is_byte(Char));
+        assert_eq!(array_type("u8", RefMode::None), "Vec<u8>");
+        assert_eq!(array_type("u8", RefMode::ByRef), "[u8]");
+    }
+}
@@ -39,4 +39,29 @@ impl SafetyAssertionMode {
 
         InMainThread
     }
+
+    /// Applies a per-function `main_thread_only` override.
+    pub fn with_main_thread_only(self, main_thread_only: Option<bool>) -> SafetyAssertionMode {
+        match main_thread_only {
+            Some(true) => SafetyAssertionMode::InMainThread,
+            Some(false) => SafetyAssertionMode::Skip,
+            None => self,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main_thread_only_overrides_default() {
+        use self::SafetyAssertionMode::*;
+        assert_eq!(None.with_main_thread_only(Some(true)), InMainThread);
+        assert_eq!(Skip.with_main_thread_only(Some(true)), InMainThread);
+        assert_eq!(InMainThread.with_main_thread_only(Some(false)), Skip);
+        assert_eq!(None.with_main_thread_only(Some(false)), Skip);
+        assert_eq!(InMainThread.with_main_thread_only(Option::None), InMainThread);
+        assert_eq!(None.with_main_thread_only(Option::None), None);
+    }
 }
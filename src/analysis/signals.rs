@@ -3,6 +3,7 @@ use config::gobjects::GObject;
 use env::Env;
 use library;
 use nameutil;
+use super::functions::stability_cfg_condition;
 use super::trampolines;
 use super::imports::Imports;
 use traits::*;
@@ -16,6 +17,7 @@ pub struct Info {
     pub trampoline_name: Result<String, Vec<String>>,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub cfg_condition: Option<String>,
     pub doc_hidden: bool,
 }
 
@@ -38,6 +40,14 @@ pub fn analyze(
         if env.is_totally_deprecated(signal.deprecated_version) {
             continue;
         }
+        let version = configured_signals
+            .iter()
+            .filter_map(|f| f.version)
+            .min()
+            .or(signal.version);
+        if env.is_too_new(&signal.name, version) {
+            continue;
+        }
 
         let info = analyze_signal(
             env,
@@ -74,6 +84,12 @@ fn analyze_signal(
         .min()
         .or(signal.version);
     let deprecated_version = signal.deprecated_version;
+    let cfg_condition = stability_cfg_condition(
+        None,
+        signal.stability,
+        env.config.unstable_feature.as_ref().map(|s| &s[..]),
+    );
+    let cfg = cfg_condition.as_ref().map(|s| &s[..]);
     let doc_hidden = configured_signals.iter().any(|f| f.doc_hidden);
 
     let connect_name = format!("connect_{}", nameutil::signal_to_snake(&signal.name));
@@ -89,9 +105,15 @@ fn analyze_signal(
         version,
     );
 
-    let action_emit_name = if signal.is_action {
+    let emit = need_emit(
+        signal.is_action,
+        configured_signals.iter().filter_map(|f| f.emit).next(),
+        env.config.emit_signals,
+    );
+    // The emit method reuses the types of the trampoline
+    let action_emit_name = if emit && trampoline_name.is_ok() {
         if !in_trait {
-            imports.add("glib::object::ObjectExt", version);
+            imports.add_with_constraint("glib::object::ObjectExt", version, cfg);
         }
         Some(format!("emit_{}", nameutil::signal_to_snake(&signal.name)))
     } else {
@@ -99,16 +121,16 @@ fn analyze_signal(
     };
 
     if trampoline_name.is_ok() {
-        imports.add_used_types(&used_types, version);
+        imports.add_used_types_with_constraint(&used_types, version, cfg);
         if in_trait {
-            imports.add("glib", version);
-            imports.add("glib::object::Downcast", version);
+            imports.add_with_constraint("glib", version, cfg);
+            imports.add_with_constraint("glib::object::Downcast", version, cfg);
         }
-        imports.add("glib::signal::connect", version);
-        imports.add("glib::signal::SignalHandlerId", version);
-        imports.add("std::mem::transmute", version);
-        imports.add("std::boxed::Box as Box_", version);
-        imports.add("glib_ffi", version);
+        imports.add_with_constraint("glib::signal::connect", version, cfg);
+        imports.add_with_constraint("glib::signal::SignalHandlerId", version, cfg);
+        imports.add_with_constraint("std::mem::transmute", version, cfg);
+        imports.add_with_constraint("std::boxed::Box as Box_", version, cfg);
+        imports.add_with_constraint("glib_ffi", version, cfg);
     }
 
     let info = Info {
@@ -118,7 +140,13 @@ fn analyze_signal(
         action_emit_name: action_emit_name,
         version: version,
         deprecated_version: deprecated_version,
+        cfg_condition: cfg_condition.clone(),
         doc_hidden: doc_hidden,
     };
     Some(info)
 }
+
+/// Action signals are meant to be emitted by user code, e.g. `activate`.
+fn need_emit(is_action: bool, configured: Option<bool>, emit_signals: bool) -> bool {
+    configured.unwrap_or(is_action || emit_signals)
+}
@@ -109,6 +109,37 @@ pub fn unhide(functions: &mut Vec<FuncInfo>, specials: &Infos, type_: Type) {
     }
 }
 
+/// `Eq` can't be derived from an `equal` function, which may not be a true equivalence,
+/// e.g. for types containing floats, so it has to be requested explicitly.
+pub fn check_generate_eq(type_name: &str, generate_eq: bool, specials: &Infos) -> bool {
+    if !generate_eq {
+        return false;
+    }
+    if specials.get(&Type::Equal).is_none() {
+        error!(
+            "generate_eq for {} requires an equal function implementing PartialEq",
+            type_name
+        );
+        return false;
+    }
+    true
+}
+
+/// Identity equality of records can't be combined with an equality by value.
+pub fn check_identity_eq(type_name: &str, identity_eq: bool, specials: &Infos) -> bool {
+    if !identity_eq {
+        return false;
+    }
+    if specials.get(&Type::Equal).is_some() || specials.get(&Type::Compare).is_some() {
+        error!(
+            "identity_eq for {} conflicts with its equal or compare function",
+            type_name
+        );
+        return false;
+    }
+    true
+}
+
 pub fn analyze_imports(specials: &Infos, imports: &mut Imports) {
     use self::Type::*;
     for type_ in specials.keys() {
@@ -119,3 +150,27 @@ pub fn analyze_imports(specials: &Infos, imports: &mut Imports) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_eq_requires_equal() {
+        let mut specials = Infos::new();
+        assert!(!check_generate_eq("Gdk.RGBA", false, &specials));
+        assert!(!check_generate_eq("Gdk.RGBA", true, &specials));
+        specials.insert(Type::Equal, "gdk_rgba_equal".into());
+        assert!(!check_generate_eq("Gdk.RGBA", false, &specials));
+        assert!(check_generate_eq("Gdk.RGBA", true, &specials));
+    }
+
+    #[test]
+    fn identity_eq_conflicts_with_equal() {
+        let mut specials = Infos::new();
+        assert!(!check_identity_eq("Gtk.Widget", false, &specials));
+        assert!(check_identity_eq("Gtk.Widget", true, &specials));
+        specials.insert(Type::Compare, "gtk_widget_compare".into());
+        assert!(!check_identity_eq("Gtk.Widget", true, &specials));
+    }
+}
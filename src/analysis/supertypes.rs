@@ -5,7 +5,15 @@ use analysis::namespaces;
 use env::Env;
 use super::general::StatusedTypeId;
 use super::imports::Imports;
-use library::TypeId;
+use library::{Library, Type, TypeId};
+use version::Version;
+
+/// Ancestor class a type implements `AsRef` for.
+#[derive(Debug)]
+pub struct Ancestor {
+    pub rust_type: String,
+    pub version: Option<Version>,
+}
 
 pub fn analyze(env: &Env, type_id: TypeId, imports: &mut Imports) -> Vec<StatusedTypeId> {
     let mut parents = Vec::new();
@@ -41,6 +49,51 @@ pub fn analyze(env: &Env, type_id: TypeId, imports: &mut Imports) -> Vec<Statuse
     parents
 }
 
+/// Analyzes the classes among `supertypes` for `impl AsRef<Ancestor>`.
+pub fn ancestors(env: &Env, supertypes: &[StatusedTypeId]) -> Vec<Ancestor> {
+    class_ancestors(&env.library, supertypes)
+        .into_iter()
+        .map(|p| if p.type_id.ns_id == namespaces::MAIN {
+            let version = env.config
+                .objects
+                .get(&p.type_id.full_name(&env.library))
+                .and_then(|o| o.version)
+                .or_else(|| match *env.library.type_(p.type_id) {
+                    Type::Class(ref klass) => klass.version,
+                    _ => None,
+                });
+            Ancestor {
+                rust_type: p.name.clone(),
+                version: version,
+            }
+        } else {
+            // The version features of other crates can't be checked here
+            Ancestor {
+                rust_type: format!(
+                    "{}::{}",
+                    env.namespaces[p.type_id.ns_id].crate_name,
+                    p.name
+                ),
+                version: None,
+            }
+        })
+        .collect()
+}
+
+fn class_ancestors<'a>(
+    library: &Library,
+    supertypes: &'a [StatusedTypeId],
+) -> Vec<&'a StatusedTypeId> {
+    supertypes
+        .iter()
+        .filter(|p| !p.status.ignored())
+        .filter(|p| match *library.type_(p.type_id) {
+            Type::Class(..) => true,
+            _ => false,
+        })
+        .collect()
+}
+
 pub fn dependencies(env: &Env, type_id: TypeId) -> Vec<TypeId> {
     let mut parents = Vec::new();
     let gobject_id = match env.library.find_type(0, "GObject.Object") {
@@ -63,3 +116,63 @@ pub fn dependencies(env: &Env, type_id: TypeId) -> Vec<TypeId> {
 
     parents
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use analysis::class_hierarchy;
+    use config::gobjects::GStatus;
+    use library::{Class, Interface, MAIN_NAMESPACE};
+
+    fn add_class(
+        library: &mut Library,
+        name: &str,
+        parent: Option<TypeId>,
+        implements: Vec<TypeId>,
+    ) -> TypeId {
+        library.add_type(
+            MAIN_NAMESPACE,
+            name,
+            Type::Class(Class {
+                name: name.into(),
+                c_type: format!("Gtk{}", name),
+                parent: parent,
+                implements: implements,
+                ..Default::default()
+            }),
+        )
+    }
+
+    #[test]
+    fn ancestors_are_the_parent_classes() {
+        let mut library = Library::new("Gtk");
+        let buildable = library.add_type(
+            MAIN_NAMESPACE,
+            "Buildable",
+            Type::Interface(Interface {
+                name: "Buildable".into(),
+                c_type: "GtkBuildable".into(),
+                ..Default::default()
+            }),
+        );
+        let widget = add_class(&mut library, "Widget", None, vec![buildable]);
+        let container = add_class(&mut library, "Container", Some(widget), vec![]);
+        let bin = add_class(&mut library, "Bin", Some(container), vec![]);
+        let hierarchy = class_hierarchy::run(&library);
+
+        let supertypes: Vec<StatusedTypeId> = hierarchy
+            .supertypes(bin)
+            .iter()
+            .map(|&tid| StatusedTypeId {
+                type_id: tid,
+                name: library.type_(tid).get_name(),
+                status: GStatus::Generate,
+            })
+            .collect();
+        let ancestors: Vec<TypeId> = class_ancestors(&library, &supertypes)
+            .iter()
+            .map(|p| p.type_id)
+            .collect();
+        assert_eq!(ancestors, vec![container, widget]);
+    }
+}
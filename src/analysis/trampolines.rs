@@ -7,7 +7,8 @@ use parser::is_empty_c_type;
 use super::bounds::{BoundType, Bounds};
 use super::conversion_type::ConversionType;
 use super::ffi_type::used_ffi_type;
-use super::rust_type::{bounds_rust_type, rust_type, used_rust_type};
+use super::functions::stability_cfg_condition;
+use super::rust_type::{bounds_rust_type, is_checked_size_cast, rust_type, used_rust_type};
 use super::trampoline_parameters::{self, Parameters};
 use traits::IntoString;
 use version::Version;
@@ -19,6 +20,7 @@ pub struct Trampoline {
     pub ret: library::Parameter,
     pub bounds: Bounds,
     pub version: Option<Version>,
+    pub cfg_condition: Option<String>,
     pub inhibit: bool,
     pub concurrency: library::Concurrency,
     pub is_notify: bool,
@@ -107,6 +109,16 @@ pub fn analyze(
         }
     }
 
+    let has_checked_cast = parameters
+        .rust_parameters
+        .iter()
+        .any(|par| is_checked_size_cast(env, par.typ, false))
+        || (signal.ret.typ != Default::default()
+            && is_checked_size_cast(env, signal.ret.typ, true));
+    if has_checked_cast {
+        used_types.push("::std::convert::TryFrom".into());
+    }
+
     let mut ret_nullable = signal.ret.nullable;
 
     if signal.ret.typ != Default::default() {
@@ -143,6 +155,11 @@ pub fn analyze(
         ret: ret,
         bounds: bounds,
         version: version,
+        cfg_condition: stability_cfg_condition(
+            None,
+            signal.stability,
+            env.config.unstable_feature.as_ref().map(|s| &s[..]),
+        ),
         inhibit: inhibit,
         concurrency: concurrency,
         is_notify: is_notify,
@@ -45,7 +45,10 @@ pub enum Chunk {
         condition: String,
         value: Box<Chunk>,
     },
-    ErrorResultReturn { value: Box<Chunk> },
+    ErrorResultReturn {
+        value: Box<Chunk>,
+        error_type: Option<String>,
+    },
     AssertInitializedAndInMainThread,
     AssertSkipInitialized,
     Connect {
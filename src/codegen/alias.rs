@@ -1,4 +1,4 @@
-use analysis::namespaces;
+use analysis::alias::{can_be_newtype, generated};
 use analysis::rust_type::rust_type;
 use codegen::general;
 use config::gobjects::GObject;
@@ -11,48 +11,204 @@ use std::path::Path;
 use traits::*;
 
 pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
-    let configs: Vec<&GObject> = env.config
-        .objects
-        .values()
-        .filter(|c| {
-            c.status.need_generate() && c.type_id.map_or(false, |tid| tid.ns_id == namespaces::MAIN)
-        })
-        .collect();
-    let mut has_any = false;
-    for config in &configs {
-        if let Type::Alias(_) = *env.library.type_(config.type_id.unwrap()) {
-            has_any = true;
-            break;
-        }
-    }
-
-    if !has_any {
+    let aliases = generated(env);
+    if aliases.is_empty() {
         return;
     }
 
     let path = root_path.join("alias.rs");
     file_saver::save_to_file(path, env.config.make_backup, |w| {
-        try!(general::start_comments(w, &env.config));
-        try!(writeln!(w, ""));
-        try!(writeln!(w, "#[allow(unused_imports)]"));
-        try!(writeln!(w, "use auto::*;"));
-        try!(writeln!(w, ""));
-
         mod_rs.push("\nmod alias;".into());
-        for config in &configs {
-            if let Type::Alias(ref alias) = *env.library.type_(config.type_id.unwrap()) {
-                mod_rs.push(format!("pub use self::alias::{};", alias.name));
-                try!(generate_alias(env, w, alias, config));
-            }
+        generate_aliases(env, w, &aliases, mod_rs)
+    });
+}
+
+fn generate_aliases(
+    env: &Env,
+    w: &mut Write,
+    aliases: &[(&Alias, &GObject)],
+    mod_rs: &mut Vec<String>,
+) -> Result<()> {
+    try!(general::start_comments(w, &env.config));
+    try!(general::uses(w, env, &env.analysis.alias_imports));
+    try!(writeln!(w, ""));
+
+    for &(alias, config) in aliases {
+        let version = config.version.or(alias.version);
+        if let Some(cfg) = general::version_condition_string(env, version, false, 0) {
+            mod_rs.push(cfg);
         }
+        mod_rs.push(format!("pub use self::alias::{};", alias.name));
+        try!(generate_alias(env, w, alias, config));
+    }
 
-        Ok(())
-    });
+    Ok(())
 }
 
-fn generate_alias(env: &Env, w: &mut Write, alias: &Alias, _: &GObject) -> Result<()> {
+fn generate_alias(env: &Env, w: &mut Write, alias: &Alias, config: &GObject) -> Result<()> {
     let typ = rust_type(env, alias.typ).into_string();
-    try!(writeln!(w, "pub type {} = {};", alias.name, typ));
+    let newtype = config.newtype && can_be_newtype(env, alias);
+    if config.newtype && !newtype {
+        warn!(
+            "Newtype configured for alias {} of non-numeric type {}",
+            config.name,
+            typ
+        );
+    }
+
+    try!(general::version_condition(
+        w,
+        env,
+        config.version.or(alias.version),
+        false,
+        0,
+    ));
+    try!(writeln!(w, "{}", declaration(&alias.name, &typ, newtype)));
+    if newtype {
+        try!(writeln!(w, ""));
+        try!(writeln!(
+            w,
+            "{}",
+            conversions(&alias.name, &typ, &alias.c_identifier)
+        ));
+    }
 
     Ok(())
 }
+
+/// The newtype is unwrapped and wrapped at the FFI boundary and can be converted from and
+/// to its integer for manual use.
+fn conversions(name: &str, typ: &str, c_type: &str) -> String {
+    format!(
+        "#[doc(hidden)]
+impl ToGlib for {name} {{
+    type GlibType = ffi::{c_type};
+
+    fn to_glib(&self) -> ffi::{c_type} {{
+        self.0
+    }}
+}}
+
+#[doc(hidden)]
+impl FromGlib<ffi::{c_type}> for {name} {{
+    fn from_glib(value: ffi::{c_type}) -> Self {{
+        {name}(value)
+    }}
+}}
+
+impl From<{typ}> for {name} {{
+    fn from(value: {typ}) -> Self {{
+        {name}(value)
+    }}
+}}
+
+impl From<{name}> for {typ} {{
+    fn from(value: {name}) -> Self {{
+        value.0
+    }}
+}}
+",
+        name = name,
+        typ = typ,
+        c_type = c_type
+    )
+}
+
+fn declaration(name: &str, typ: &str, newtype: bool) -> String {
+    if newtype {
+        format!(
+            "#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct {}(pub {});",
+            name,
+            typ
+        )
+    } else {
+        format!("pub type {} = {};", name, typ)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_env;
+
+    #[test]
+    fn transparent_alias() {
+        assert_eq!(declaration("Quark", "u32", false), "pub type Quark = u32;");
+    }
+
+    #[test]
+    fn newtype_alias() {
+        assert_eq!(
+            declaration("Pid", "i32", true),
+            "#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Pid(pub i32);"
+        );
+    }
+
+    #[test]
+    fn newtype_alias_conversions() {
+        let code = conversions("Pid", "i32", "GPid");
+        assert!(code.contains("impl ToGlib for Pid {\n    type GlibType = ffi::GPid;"));
+        assert!(code.contains("impl FromGlib<ffi::GPid> for Pid {"));
+        assert!(code.contains(
+            "impl From<i32> for Pid {\n    fn from(value: i32) -> Self {\n        Pid(value)"
+        ));
+        assert!(code.contains(
+            "impl From<Pid> for i32 {\n    fn from(value: Pid) -> Self {\n        value.0"
+        ));
+    }
+
+    const ALIASES: &'static str = r#"
+    <record name="Rectangle" c:type="TestRectangle">
+      <field name="x" writable="1">
+        <type name="gint" c:type="gint"/>
+      </field>
+    </record>
+    <alias name="Area" c:type="TestArea">
+      <type name="Rectangle" c:type="TestRectangle"/>
+    </alias>
+    <alias name="Region" c:type="TestRegion">
+      <type name="Area" c:type="TestArea"/>
+    </alias>"#;
+
+    const ALIASED_OBJECTS: &'static str = r#"
+[[object]]
+name = "Test.Rectangle"
+status = "generate"
+
+[[object]]
+name = "Test.Area"
+status = "generate"
+"#;
+
+    #[test]
+    fn alias_imports_aliased_type() {
+        let code = test_env::generated_object(ALIASES, "", "Region", ALIASED_OBJECTS);
+        assert!(code.contains("use Rectangle;\n"));
+        assert!(!code.contains("use auto::*;"));
+        assert!(!code.contains("use Area;"));
+        assert!(code.contains("pub type Region = Area;"));
+    }
+
+    #[test]
+    fn alias_imports_follow_module_path() {
+        let options = "module_path = \"ffi::generated\"";
+        let code = test_env::generated_object(ALIASES, options, "Region", ALIASED_OBJECTS);
+        assert!(code.contains("use ffi::generated::Rectangle;\n"));
+        assert!(!code.contains("use Rectangle;"));
+        assert!(code.contains("pub type Area = Rectangle;"));
+    }
+
+    #[test]
+    fn newtype_ffi_conversions_are_identity() {
+        // `#[repr(transparent)]` makes the newtype passable as the FFI type itself,
+        // the conversions only wrap and unwrap the single field
+        assert!(declaration("Pid", "i32", true).contains("#[repr(transparent)]\n"));
+        let code = conversions("Pid", "i32", "GPid");
+        assert!(code.contains("fn to_glib(&self) -> ffi::GPid {\n        self.0\n    }"));
+        assert!(code.contains("fn from_glib(value: ffi::GPid) -> Self {\n        Pid(value)\n    }"));
+    }
+}
@@ -6,8 +6,8 @@ const LANGUAGE_SEP_END: &'static str = "\" -->";
 const LANGUAGE_BLOCK_BEGIN: &'static str = "|[";
 const LANGUAGE_BLOCK_END: &'static str = "\n]|";
 
-pub fn reformat_doc(input: &str, symbols: &symbols::Info) -> String {
-    code_blocks_transformation(input, symbols)
+pub fn reformat_doc(input: &str, symbols: &symbols::Info, doc_links: bool) -> String {
+    code_blocks_transformation(input, symbols, doc_links)
 }
 
 fn try_split<'a>(src: &'a str, needle: &str) -> (&'a str, Option<&'a str>) {
@@ -17,13 +17,17 @@ fn try_split<'a>(src: &'a str, needle: &str) -> (&'a str, Option<&'a str>) {
     }
 }
 
-fn code_blocks_transformation(mut input: &str, symbols: &symbols::Info) -> String {
+fn code_blocks_transformation(
+    mut input: &str,
+    symbols: &symbols::Info,
+    doc_links: bool,
+) -> String {
     let mut out = String::with_capacity(input.len());
 
     loop {
         input = match try_split(input, LANGUAGE_BLOCK_BEGIN) {
             (before, Some(after)) => {
-                out.push_str(&format(before, symbols));
+                out.push_str(&format(before, symbols, doc_links));
                 if let (before, Some(after)) =
                     try_split(get_language(after, &mut out), LANGUAGE_BLOCK_END)
                 {
@@ -35,7 +39,7 @@ fn code_blocks_transformation(mut input: &str, symbols: &symbols::Info) -> Strin
                 }
             }
             (before, None) => {
-                out.push_str(&format(before, symbols));
+                out.push_str(&format(before, symbols, doc_links));
                 return out;
             }
         };
@@ -61,11 +65,11 @@ lazy_static! {
     static ref SPACES: Regex = Regex::new(r"[ ][ ]+").unwrap();
 }
 
-fn format(mut input: &str, symbols: &symbols::Info) -> String {
+fn format(mut input: &str, symbols: &symbols::Info, doc_links: bool) -> String {
     let mut ret = String::with_capacity(input.len());
     loop {
         let (before, after) = try_split(input, "`");
-        ret.push_str(&replace_c_types(before, symbols));
+        ret.push_str(&replace_c_types(before, symbols, doc_links));
         if let Some(after) = after {
             ret.push_str("`");
             let (before, after) = try_split(after, "`");
@@ -83,23 +87,111 @@ fn format(mut input: &str, symbols: &symbols::Info) -> String {
     }
 }
 
-fn replace_c_types(entry: &str, symbols: &symbols::Info) -> String {
-    let lookup = |s: &str| -> String {
-        symbols
-            .by_c_name(s)
-            .map(|s| s.full_rust_name())
-            .unwrap_or_else(|| s.into())
+fn replace_c_types(entry: &str, symbols: &symbols::Info, doc_links: bool) -> String {
+    // Known symbols become intra-doc links, unresolved ones stay plain code
+    let lookup = |s: &str, suffix: &str| -> String {
+        match symbols.by_c_name(s).map(|s| s.full_rust_name()) {
+            Some(ref name) if doc_links && suffix.is_empty() && !is_builtin(name) => {
+                format!("[`{}`]", name)
+            }
+            Some(name) => format!("`{}{}`", name, suffix),
+            None => format!("`{}{}`", s, suffix),
+        }
     };
     let out = SYMBOL.replace_all(entry, |caps: &Captures| {
         format!(
-            "{}`{}{}`",
+            "{}{}",
             &caps[1],
-            lookup(&caps[2]),
-            caps.get(3).map(|m| m.as_str()).unwrap_or("")
+            lookup(&caps[2], caps.get(3).map(|m| m.as_str()).unwrap_or(""))
         )
     });
-    let out = GDK_GTK.replace_all(&out, |caps: &Captures| format!("`{}`", lookup(&caps[0])));
-    let out = FUNCTION.replace_all(&out, |caps: &Captures| format!("`{}`", lookup(&caps[1])));
+    let out = GDK_GTK.replace_all(&out, |caps: &Captures| lookup(&caps[0], ""));
+    let out = FUNCTION.replace_all(&out, |caps: &Captures| lookup(&caps[1], ""));
     let out = TAGS.replace_all(&out, "`$0`");
     SPACES.replace_all(&out, " ").into_owned()
 }
+
+fn is_builtin(name: &str) -> bool {
+    name == "None" || name == "true" || name == "false"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use analysis::{namespaces, symbols};
+    use library::*;
+
+    fn parameter(name: &str, typ: TypeId, c_type: &str, instance_parameter: bool) -> Parameter {
+        Parameter {
+            name: name.into(),
+            typ: typ,
+            c_type: c_type.into(),
+            instance_parameter: instance_parameter,
+            direction: ParameterDirection::In,
+            transfer: Transfer::None,
+            caller_allocates: false,
+            nullable: Nullable(false),
+            allow_none: false,
+            array_length: None,
+            is_error: false,
+            doc: None,
+        }
+    }
+
+    fn make_symbols() -> symbols::Info {
+        let mut library = Library::new("Gtk");
+        library.add_namespace("GLib");
+        let widget_tid = library.find_or_stub_type(MAIN_NAMESPACE, "Widget");
+        let show = Function {
+            name: "show".into(),
+            c_identifier: Some("gtk_widget_show".into()),
+            kind: FunctionKind::Method,
+            parameters: vec![parameter("widget", widget_tid, "GtkWidget*", true)],
+            ret: parameter("", TypeId::tid_none(), "void", false),
+            throws: false,
+            version: None,
+            deprecated_version: None,
+            stability: Default::default(),
+            doc: None,
+            doc_deprecated: None,
+        };
+        library.add_type(
+            MAIN_NAMESPACE,
+            "Widget",
+            Type::Class(Class {
+                name: "Widget".into(),
+                c_type: "GtkWidget".into(),
+                functions: vec![show],
+                ..Default::default()
+            }),
+        );
+        let namespaces = namespaces::run(&library, &Default::default());
+        symbols::run(&library, &namespaces)
+    }
+
+    #[test]
+    fn doc_links_to_known_function() {
+        let symbols = make_symbols();
+        assert_eq!(
+            reformat_doc("Calls gtk_widget_show() on it.", &symbols, true),
+            "Calls [`Widget::show`] on it."
+        );
+        assert_eq!(
+            reformat_doc("A #GtkWidget or %NULL.", &symbols, true),
+            "A [`Widget`] or `None`."
+        );
+    }
+
+    #[test]
+    fn doc_links_unresolved_stay_plain() {
+        let symbols = make_symbols();
+        assert_eq!(
+            reformat_doc("Calls gtk_widget_hide() on it.", &symbols, true),
+            "Calls `gtk_widget_hide` on it."
+        );
+        assert_eq!(
+            reformat_doc("Calls gtk_widget_show() on it.", &symbols, false),
+            "Calls `Widget::show` on it."
+        );
+    }
+}
@@ -167,7 +167,7 @@ fn create_object_doc(w: &mut Write, env: &Env, info: &analysis::object::Info) ->
             try!(write!(w, "`[Deprecated since {}]` ", ver));
         }
         if let Some(doc) = doc {
-            try!(writeln!(w, "{}", reformat_doc(doc, &symbols)));
+            try!(writeln!(w, "{}", reformat_doc(doc, &symbols, env.config.generate_doc_links)));
         } else {
             try!(writeln!(w, ""));
         }
@@ -239,14 +239,21 @@ fn create_object_doc(w: &mut Write, env: &Env, info: &analysis::object::Info) ->
         } else {
             ty.clone()
         };
-        try!(create_fn_doc(w, env, function, Some(Box::new(ty))));
+        try!(create_fn_doc(
+            w,
+            env,
+            function,
+            Some(Box::new(ty)),
+            doc_ignore_parameters(&info.functions, function)
+        ));
     }
     for signal in signals {
         try!(create_fn_doc(
             w,
             env,
             signal,
-            Some(Box::new(ty_ext.clone()))
+            Some(Box::new(ty_ext.clone())),
+            &[]
         ));
     }
     for property in properties {
@@ -270,7 +277,7 @@ fn create_record_doc(w: &mut Write, env: &Env, info: &analysis::record::Info) ->
             if let Some(ver) = info.deprecated_version {
                 try!(write!(w, "`[Deprecated since {}]` ", ver));
             }
-            try!(writeln!(w, "{}", reformat_doc(doc, &symbols)));
+            try!(writeln!(w, "{}", reformat_doc(doc, &symbols, env.config.generate_doc_links)));
         }
         if let Some(ver) = info.deprecated_version {
             try!(writeln!(w, "\n# Deprecated since {}\n", ver));
@@ -278,7 +285,7 @@ fn create_record_doc(w: &mut Write, env: &Env, info: &analysis::record::Info) ->
             try!(writeln!(w, "\n# Deprecated\n"));
         }
         if let Some(ref doc) = record.doc_deprecated {
-            try!(writeln!(w, "{}", reformat_doc(doc, &symbols)));
+            try!(writeln!(w, "{}", reformat_doc(doc, &symbols, env.config.generate_doc_links)));
         }
         if let Some(version) = info.version {
             try!(writeln!(w, "\nFeature: `{}`", version.to_feature()));
@@ -291,7 +298,13 @@ fn create_record_doc(w: &mut Write, env: &Env, info: &analysis::record::Info) ->
         ..ty
     };
     for function in &record.functions {
-        try!(create_fn_doc(w, env, function, Some(Box::new(ty.clone()))));
+        try!(create_fn_doc(
+            w,
+            env,
+            function,
+            Some(Box::new(ty.clone())),
+            doc_ignore_parameters(&info.functions, function)
+        ));
     }
     Ok(())
 }
@@ -302,7 +315,7 @@ fn create_enum_doc(w: &mut Write, env: &Env, enum_: &Enumeration) -> Result<()>
 
     try!(write_item_doc(w, &ty, |w| {
         if let Some(ref doc) = enum_.doc {
-            try!(writeln!(w, "{}", reformat_doc(doc, &symbols)));
+            try!(writeln!(w, "{}", reformat_doc(doc, &symbols, env.config.generate_doc_links)));
         }
         if let Some(ver) = enum_.deprecated_version {
             try!(writeln!(w, "\n# Deprecated since {}\n", ver));
@@ -310,7 +323,7 @@ fn create_enum_doc(w: &mut Write, env: &Env, enum_: &Enumeration) -> Result<()>
             try!(writeln!(w, "\n# Deprecated\n"));
         }
         if let Some(ref doc) = enum_.doc_deprecated {
-            try!(writeln!(w, "{}", reformat_doc(doc, &symbols)));
+            try!(writeln!(w, "{}", reformat_doc(doc, &symbols, env.config.generate_doc_links)));
         }
         Ok(())
     }));
@@ -325,7 +338,11 @@ fn create_enum_doc(w: &mut Write, env: &Env, enum_: &Enumeration) -> Result<()>
             sub_ty.parent = Some(Box::new(ty.clone()));
             try!(write_item_doc(w, &sub_ty, |w| {
                 if let Some(ref doc) = member.doc {
-                    try!(writeln!(w, "{}", reformat_doc(doc, &symbols)));
+                    try!(writeln!(
+                        w,
+                        "{}",
+                        reformat_doc(doc, &symbols, env.config.generate_doc_links)
+                    ));
                 }
                 Ok(())
             }));
@@ -355,11 +372,24 @@ fn fix_param_names<'a>(doc: &'a str, self_name: &Option<String>) -> Cow<'a, str>
     })
 }
 
+/// Parameters of `function` kept out of its docs, as analyzed for the bindings
+fn doc_ignore_parameters<'a>(
+    infos: &'a [analysis::functions::Info],
+    function: &Function,
+) -> &'a [String] {
+    infos
+        .iter()
+        .find(|info| Some(&info.glib_name) == function.c_identifier.as_ref())
+        .map(|info| &info.doc_ignore_parameters[..])
+        .unwrap_or(&[])
+}
+
 fn create_fn_doc<T>(
     w: &mut Write,
     env: &Env,
     fn_: &T,
     parent: Option<Box<TypeStruct>>,
+    ignored_parameters: &[String],
 ) -> Result<()>
 where
     T: FunctionLikeType + ToStripperType,
@@ -385,7 +415,11 @@ where
             try!(writeln!(
                 w,
                 "{}",
-                reformat_doc(&fix_param_names(doc, &self_name), &symbols)
+                reformat_doc(
+                    &fix_param_names(doc, &self_name),
+                    &symbols,
+                    env.config.generate_doc_links
+                )
             ));
         }
         if let &Some(version) = fn_.version() {
@@ -402,7 +436,11 @@ where
             try!(writeln!(
                 w,
                 "{}",
-                reformat_doc(&fix_param_names(doc, &self_name), &symbols)
+                reformat_doc(
+                    &fix_param_names(doc, &self_name),
+                    &symbols,
+                    env.config.generate_doc_links
+                )
             ));
         }
 
@@ -410,16 +448,20 @@ where
             if parameter.instance_parameter || parameter.name.is_empty() {
                 continue;
             }
+            let name = nameutil::mangle_keywords(&parameter.name[..]);
+            if ignored_parameters.iter().any(|ignored| *ignored == name) {
+                continue;
+            }
             if let Some(ref doc) = parameter.doc {
-                try!(writeln!(
-                    w,
-                    "## `{}`",
-                    nameutil::mangle_keywords(&parameter.name[..])
-                ));
+                try!(writeln!(w, "## `{}`", name));
                 try!(writeln!(
                     w,
                     "{}",
-                    reformat_doc(&fix_param_names(doc, &self_name), &symbols)
+                    reformat_doc(
+                        &fix_param_names(doc, &self_name),
+                        &symbols,
+                        env.config.generate_doc_links
+                    )
                 ));
             }
         }
@@ -429,7 +471,11 @@ where
             try!(writeln!(
                 w,
                 "{}",
-                reformat_doc(&fix_param_names(doc, &self_name), &symbols)
+                reformat_doc(
+                    &fix_param_names(doc, &self_name),
+                    &symbols,
+                    env.config.generate_doc_links
+                )
             ));
         }
         Ok(())
@@ -469,7 +515,11 @@ fn create_property_doc(
                 try!(writeln!(
                     w,
                     "{}",
-                    reformat_doc(&fix_param_names(doc, &None), &symbols)
+                    reformat_doc(
+                        &fix_param_names(doc, &None),
+                        &symbols,
+                        env.config.generate_doc_links
+                    )
                 ));
             }
             if let Some(version) = property.version {
@@ -486,7 +536,11 @@ fn create_property_doc(
                 try!(writeln!(
                     w,
                     "{}",
-                    reformat_doc(&fix_param_names(doc, &None), &symbols)
+                    reformat_doc(
+                        &fix_param_names(doc, &None),
+                        &symbols,
+                        env.config.generate_doc_links
+                    )
                 ));
             }
             Ok(())
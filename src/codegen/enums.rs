@@ -1,11 +1,13 @@
-use analysis::namespaces;
+use analysis::{self, namespaces};
 use case::CaseExt;
-use codegen::general::{self, version_condition, version_condition_string};
+use codegen::general::{self, cfg_and_version_condition, cfg_and_version_condition_string,
+                       version_condition, version_condition_no_doc,
+                       version_condition_no_doc_string};
 use config::gobjects::GObject;
 use env::Env;
 use file_saver;
 use library::*;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::io::prelude::*;
 use std::io::Result;
 use std::path::Path;
@@ -19,6 +21,10 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
         .filter(|c| {
             c.status.need_generate() && c.type_id.map_or(false, |tid| tid.ns_id == namespaces::MAIN)
         })
+        .filter(|c| match *env.library.type_(c.type_id.unwrap()) {
+            Type::Enumeration(ref enum_) => !env.is_too_new(&c.name, c.version.or(enum_.version)),
+            _ => true,
+        })
         .collect();
     let mut has_get_quark = false;
     let mut has_any = false;
@@ -26,7 +32,7 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
     for config in &configs {
         if let Type::Enumeration(ref enum_) = *env.library.type_(config.type_id.unwrap()) {
             has_any = true;
-            if get_error_quark_name(enum_).is_some() {
+            if error_domain_quark(enum_, config).is_some() {
                 has_get_quark = true;
             }
             if enum_.glib_get_type.is_some() {
@@ -75,6 +81,28 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
             }
             try!(writeln!(w, "use glib::translate::*;"));
         }
+        if has_any && (env.config.generate_int_conversions || env.config.generate_nick_conversions)
+        {
+            try!(writeln!(w, "use std::convert::TryFrom;"));
+        }
+        if has_any && env.config.generate_nick_conversions {
+            if env.namespaces.glib_ns_id == namespaces::MAIN {
+                try!(writeln!(w, "use error::BoolError;"));
+            } else {
+                try!(writeln!(w, "use glib::error::BoolError;"));
+            }
+        }
+        let derive_uses: BTreeSet<&str> = configs
+            .iter()
+            .filter(|c| match *env.library.type_(c.type_id.unwrap()) {
+                Type::Enumeration(..) => true,
+                _ => false,
+            })
+            .flat_map(|c| analysis::general::derive_uses(&c.extra_derives))
+            .collect();
+        for derive in derive_uses {
+            try!(writeln!(w, "use {};", derive));
+        }
         try!(writeln!(w, ""));
 
         if has_any {
@@ -82,7 +110,10 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
         }
         for config in &configs {
             if let Type::Enumeration(ref enum_) = *env.library.type_(config.type_id.unwrap()) {
-                if let Some(cfg) = version_condition_string(env, enum_.version, false, 0) {
+                let cfg_condition = &config.cfg_condition;
+                if let Some(cfg) =
+                    cfg_and_version_condition_string(env, cfg_condition, enum_.version, false, 0)
+                {
                     mod_rs.push(cfg);
                 }
                 mod_rs.push(format!("pub use self::enums::{};", enum_.name));
@@ -99,6 +130,7 @@ fn generate_enum(env: &Env, w: &mut Write, enum_: &Enumeration, config: &GObject
         name: String,
         c_name: String,
         value: String,
+        nick: String,
         version: Option<Version>,
     }
 
@@ -112,42 +144,47 @@ fn generate_enum(env: &Env, w: &mut Write, enum_: &Enumeration, config: &GObject
         if is_alias || ignore || vals.contains(&member.value) {
             continue;
         }
+        let version = member_config.iter().filter_map(|m| m.version).min();
+        // Members are as old as their enum unless configured otherwise
+        if env.is_too_new(&member.c_identifier, version.or(config.version).or(enum_.version)) {
+            continue;
+        }
         vals.insert(member.value.clone());
-        let version = member_config.iter().filter_map(|m| m.version).next();
         members.push(Member {
             name: member.name.to_camel(),
             c_name: member.c_identifier.clone(),
             value: member.value.clone(),
+            nick: member.nick.clone().unwrap_or_else(|| member.name.clone()),
             version: version,
         });
     }
 
-    try!(version_condition(w, env, enum_.version, false, 0));
-    try!(writeln!(
-        w,
-        "#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]"
-    ));
-    if config.must_use {
-        try!(writeln!(
-            w,
-            "#[must_use]"
-        ));
-    }
+    try!(cfg_and_version_condition(w, env, &config.cfg_condition, enum_.version, false, 0));
+    let serde = config.serde.unwrap_or(env.config.serde);
+    try!(enum_attributes(w, config, serde));
+    try!(general::doc_alias(w, &enum_.c_type, "", 0));
     try!(writeln!(w, "pub enum {} {{", enum_.name));
     for member in &members {
         try!(version_condition(w, env, member.version, false, 1));
+        if serde {
+            try!(serde_rename(w, &member.nick));
+        }
         try!(writeln!(w, "\t{},", member.name));
     }
+    try!(writeln!(w, "    #[doc(hidden)]"));
+    if serde {
+        // Unknown values can't be serialized and unknown nicks fail to deserialize
+        try!(writeln!(w, "    #[cfg_attr(feature = \"serde\", serde(skip))]"));
+    }
     try!(writeln!(
         w,
         "{}",
-        "    #[doc(hidden)]
-    __Unknown(i32),
+        "    __Unknown(i32),
 }
 "
     ));
 
-    try!(version_condition(w, env, enum_.version, false, 0));
+    try!(cfg_and_version_condition(w, env, &config.cfg_condition, enum_.version, false, 0));
     try!(writeln!(
         w,
         "#[doc(hidden)]
@@ -160,7 +197,7 @@ impl ToGlib for {name} {{
         ffi_name = enum_.c_type
     ));
     for member in &members {
-        try!(version_condition(w, env, member.version, false, 3));
+        try!(version_condition_no_doc(w, env, member.version, false, 3));
         try!(writeln!(
             w,
             "\t\t\t{}::{} => ffi::{},",
@@ -169,11 +206,7 @@ impl ToGlib for {name} {{
             member.c_name
         ));
     }
-    try!(writeln!(
-        w,
-        "\t\t\t{}::__Unknown(value) => value",
-        enum_.name
-    ));
+    try!(writeln!(w, "{}", unknown_to_glib_arm(&enum_.name)));
     try!(writeln!(
         w,
         "{}",
@@ -189,7 +222,7 @@ impl ToGlib for {name} {{
         ""
     };
 
-    try!(version_condition(w, env, enum_.version, false, 0));
+    try!(cfg_and_version_condition(w, env, &config.cfg_condition, enum_.version, false, 0));
     try!(writeln!(
         w,
         "#[doc(hidden)]
@@ -201,7 +234,7 @@ impl FromGlib<ffi::{ffi_name}> for {name} {{
         assert = assert
     ));
     for member in &members {
-        try!(version_condition(w, env, member.version, false, 3));
+        try!(version_condition_no_doc(w, env, member.version, false, 3));
         try!(writeln!(
             w,
             "\t\t\t{} => {}::{},",
@@ -210,11 +243,7 @@ impl FromGlib<ffi::{ffi_name}> for {name} {{
             member.name
         ));
     }
-    try!(writeln!(
-        w,
-        "\t\t\tvalue => {}::__Unknown(value),",
-        enum_.name
-    ));
+    try!(writeln!(w, "{}", unknown_from_glib_arm(&enum_.name)));
     try!(writeln!(
         w,
         "{}",
@@ -223,61 +252,41 @@ impl FromGlib<ffi::{ffi_name}> for {name} {{
 }
 "
     ));
-    if let Some(ref get_quark) = get_error_quark_name(enum_) {
-        let get_quark = get_quark.replace("-", "_");
-        let has_failed_member = members.iter().any(|m| m.name == "Failed");
-
-        try!(version_condition(w, env, enum_.version, false, 0));
-        try!(writeln!(
+    if let Some(ref quark) = error_domain_quark(enum_, config) {
+        let enum_cfg =
+            cfg_and_version_condition_string(env, &config.cfg_condition, enum_.version, false, 0);
+        let domain_members: Vec<IntMember> = members
+            .iter()
+            .map(|m| IntMember {
+                value: &m.value,
+                name: &m.name,
+                cfg: version_condition_no_doc_string(env, m.version, false, 3),
+            })
+            .collect();
+        try!(generate_error_domain(
             w,
-            "impl ErrorDomain for {name} {{
-    fn domain() -> glib_ffi::GQuark {{
-        {assert}unsafe {{ ffi::{get_quark}() }}
-    }}
-
-    fn code(self) -> i32 {{
-        self.to_glib()
-    }}
-
-    fn from(code: i32) -> Option<Self> {{
-        {assert}match code {{",
-            name = enum_.name,
-            get_quark = get_quark,
-            assert = assert
+            &enum_.name,
+            enum_cfg.as_ref().map(|s| &s[..]),
+            quark,
+            assert,
+            &domain_members
         ));
 
-        for member in &members {
-            try!(version_condition(w, env, member.version, false, 3));
-            try!(writeln!(
-                w,
-                "\t\t\t{} => Some({}::{}),",
-                member.value,
-                enum_.name,
-                member.name
-            ));
-        }
-        if has_failed_member {
-            try!(writeln!(w, "\t\t\t_ => Some({}::Failed),", enum_.name));
-        } else {
-            try!(writeln!(
-                w,
-                "\t\t\tvalue => Some({}::__Unknown(value)),",
-                enum_.name
-            ));
+        if config.into_glib_error {
+            let error = if env.namespaces.glib_ns_id == namespaces::MAIN {
+                "::Error"
+            } else {
+                "::glib::Error"
+            };
+            if let Some(ref cfg) = enum_cfg {
+                try!(writeln!(w, "{}", cfg));
+            }
+            try!(writeln!(w, "{}\n", into_glib_error_impl(&enum_.name, error)));
         }
-
-        try!(writeln!(
-            w,
-            "{}",
-            "        }
-    }
-}
-"
-        ));
     }
 
     if let Some(ref get_type) = enum_.glib_get_type {
-        try!(version_condition(w, env, enum_.version, false, 0));
+        try!(cfg_and_version_condition(w, env, &config.cfg_condition, enum_.version, false, 0));
         try!(writeln!(
             w,
             "impl StaticType for {name} {{
@@ -290,7 +299,7 @@ impl FromGlib<ffi::{ffi_name}> for {name} {{
         ));
         try!(writeln!(w, ""));
 
-        try!(version_condition(w, env, enum_.version, false, 0));
+        try!(cfg_and_version_condition(w, env, &config.cfg_condition, enum_.version, false, 0));
         try!(writeln!(
             w,
             "impl<'a> FromValueOptional<'a> for {name} {{
@@ -302,7 +311,7 @@ impl FromGlib<ffi::{ffi_name}> for {name} {{
         ));
         try!(writeln!(w, ""));
 
-        try!(version_condition(w, env, enum_.version, false, 0));
+        try!(cfg_and_version_condition(w, env, &config.cfg_condition, enum_.version, false, 0));
         try!(writeln!(
             w,
             "impl<'a> FromValue<'a> for {name} {{
@@ -314,7 +323,7 @@ impl FromGlib<ffi::{ffi_name}> for {name} {{
         ));
         try!(writeln!(w, ""));
 
-        try!(version_condition(w, env, enum_.version, false, 0));
+        try!(cfg_and_version_condition(w, env, &config.cfg_condition, enum_.version, false, 0));
         try!(writeln!(
             w,
             "impl SetValue for {name} {{
@@ -325,16 +334,686 @@ impl FromGlib<ffi::{ffi_name}> for {name} {{
             name = enum_.name,
         ));
         try!(writeln!(w, ""));
+
+        if env.config.generate_value_from {
+            try!(cfg_and_version_condition(w, env, &config.cfg_condition, enum_.version, false, 0));
+            try!(writeln!(w, "{}", general::value_from_impl(&enum_.name)));
+            try!(writeln!(w, ""));
+        }
+    }
+
+    if env.config.generate_int_conversions {
+        let enum_cfg =
+            cfg_and_version_condition_string(env, &config.cfg_condition, enum_.version, false, 0);
+        let int_members: Vec<IntMember> = members
+            .iter()
+            .map(|m| IntMember {
+                value: &m.value,
+                name: &m.name,
+                cfg: version_condition_no_doc_string(env, m.version, false, 3),
+            })
+            .collect();
+        try!(generate_int_conversions(
+            w,
+            &enum_.name,
+            enum_cfg.as_ref().map(|s| &s[..]),
+            &int_members
+        ));
+    }
+
+    if env.config.generate_nick_conversions {
+        let enum_cfg =
+            cfg_and_version_condition_string(env, &config.cfg_condition, enum_.version, false, 0);
+        let nick_members: Vec<NickMember> = members
+            .iter()
+            .map(|m| NickMember {
+                nick: &m.nick,
+                name: &m.name,
+                cfg: version_condition_no_doc_string(env, m.version, false, 3),
+            })
+            .collect();
+        try!(generate_nick_conversion(
+            w,
+            &enum_.name,
+            enum_cfg.as_ref().map(|s| &s[..]),
+            &nick_members
+        ));
     }
 
     Ok(())
 }
 
-fn get_error_quark_name(enum_: &Enumeration) -> Option<String> {
+fn enum_attributes(w: &mut Write, config: &GObject, serde: bool) -> Result<()> {
+    let derives = general::merge_derives(
+        &["Clone", "Copy", "Debug", "Eq", "PartialEq", "Hash"],
+        &config.extra_derives,
+    );
+    try!(general::write_derives(w, &derives, 0));
+    if serde {
+        try!(general::derive_serde(w, 0));
+    }
+    if config.must_use {
+        try!(writeln!(
+            w,
+            "#[must_use]"
+        ));
+    }
+    if config.non_exhaustive {
+        try!(writeln!(w, "#[non_exhaustive]"));
+    }
+    Ok(())
+}
+
+/// Values without a member are kept in the hidden `__Unknown` variant by `from_glib`,
+/// and `to_glib` gives them back unchanged.
+fn unknown_to_glib_arm(name: &str) -> String {
+    format!("\t\t\t{}::__Unknown(value) => value", name)
+}
+
+fn unknown_from_glib_arm(name: &str) -> String {
+    format!("\t\t\tvalue => {}::__Unknown(value),", name)
+}
+
+/// Members are (de)serialized by their nick, as in `GEnumValue`.
+fn serde_rename(w: &mut Write, nick: &str) -> Result<()> {
+    writeln!(
+        w,
+        "\t#[cfg_attr(feature = \"serde\", serde(rename = \"{}\"))]",
+        nick
+    )
+}
+
+struct IntMember<'a> {
+    value: &'a str,
+    name: &'a str,
+    cfg: Option<String>,
+}
+
+impl<'a> IntMember<'a> {
+    fn cfg(&self) -> Option<&str> {
+        self.cfg.as_ref().map(|s| &s[..])
+    }
+}
+
+/// Writes the arms of the `match` of a conversion, each member arm under the
+/// version condition of its member, and closes the conversion.
+fn write_match_arms<'a, I>(w: &mut Write, arms: I, fallback: &str) -> Result<()>
+where
+    I: Iterator<Item = (Option<&'a str>, String)>,
+{
+    for (cfg, arm) in arms {
+        if let Some(cfg) = cfg {
+            try!(writeln!(w, "{}", cfg));
+        }
+        try!(writeln!(w, "\t\t\t{}", arm));
+    }
+    try!(writeln!(w, "\t\t\t{}", fallback));
+    writeln!(
+        w,
+        "{}",
+        "        }
+    }
+}
+"
+    )
+}
+
+/// A `const fn` can only return values known without calling into C,
+/// which are the integer literals of the GIR.
+fn is_const_eligible(members: &[IntMember]) -> bool {
+    members.iter().all(|m| m.value.parse::<i32>().is_ok())
+}
+
+/// Generates conversions between the enum and its `i32` representation,
+/// with unknown values rejected by `TryFrom`.
+fn generate_int_conversions(
+    w: &mut Write,
+    name: &str,
+    cfg: Option<&str>,
+    members: &[IntMember],
+) -> Result<()> {
+    if is_const_eligible(members) {
+        if let Some(cfg) = cfg {
+            try!(writeln!(w, "{}", cfg));
+        }
+        try!(writeln!(
+            w,
+            "impl {name} {{
+    pub const fn to_i32(self) -> i32 {{
+        match self {{",
+            name = name
+        ));
+        let arms = members
+            .iter()
+            .map(|m| (m.cfg(), format!("{}::{} => {},", name, m.name, m.value)));
+        let fallback = format!("{}::__Unknown(value) => value,", name);
+        try!(write_match_arms(w, arms, &fallback));
+    }
+
+    if let Some(cfg) = cfg {
+        try!(writeln!(w, "{}", cfg));
+    }
+    try!(writeln!(
+        w,
+        "impl From<{name}> for i32 {{
+    fn from(value: {name}) -> i32 {{
+        value.to_glib()
+    }}
+}}
+",
+        name = name
+    ));
+
+    if let Some(cfg) = cfg {
+        try!(writeln!(w, "{}", cfg));
+    }
+    try!(writeln!(
+        w,
+        "impl TryFrom<i32> for {name} {{
+    type Error = i32;
+
+    fn try_from(value: i32) -> ::std::result::Result<Self, i32> {{
+        match value {{",
+        name = name
+    ));
+    let arms = members
+        .iter()
+        .map(|m| (m.cfg(), format!("{} => Ok({}::{}),", m.value, name, m.name)));
+    write_match_arms(w, arms, "value => Err(value),")
+}
+
+struct NickMember<'a> {
+    nick: &'a str,
+    name: &'a str,
+    cfg: Option<String>,
+}
+
+impl<'a> NickMember<'a> {
+    fn cfg(&self) -> Option<&str> {
+        self.cfg.as_ref().map(|s| &s[..])
+    }
+}
+
+/// Generates `TryFrom<&str>` parsing the GIR nicks of the members.
+fn generate_nick_conversion(
+    w: &mut Write,
+    name: &str,
+    cfg: Option<&str>,
+    members: &[NickMember],
+) -> Result<()> {
+    if let Some(cfg) = cfg {
+        try!(writeln!(w, "{}", cfg));
+    }
+    try!(writeln!(
+        w,
+        "impl<'a> TryFrom<&'a str> for {name} {{
+    type Error = BoolError;
+
+    fn try_from(nick: &'a str) -> ::std::result::Result<Self, BoolError> {{
+        match nick {{",
+        name = name
+    ));
+    let arms = members.iter().map(|m| {
+        (
+            m.cfg(),
+            format!("\"{}\" => Ok({}::{}),", m.nick, name, m.name),
+        )
+    });
+    let fallback = format!("_ => Err(BoolError(\"Unknown nick of {}\")),", name);
+    write_match_arms(w, arms, &fallback)
+}
+
+/// Generates the `ErrorDomain` impl mapping error codes to members, so the enum
+/// can be matched against `glib::Error`s.
+fn generate_error_domain(
+    w: &mut Write,
+    name: &str,
+    cfg: Option<&str>,
+    quark: &str,
+    assert: &str,
+    members: &[IntMember],
+) -> Result<()> {
+    if let Some(cfg) = cfg {
+        try!(writeln!(w, "{}", cfg));
+    }
+    try!(writeln!(
+        w,
+        "impl ErrorDomain for {name} {{
+    fn domain() -> glib_ffi::GQuark {{
+        {assert}unsafe {{ {quark} }}
+    }}
+
+    fn code(self) -> i32 {{
+        self.to_glib()
+    }}
+
+    fn from(code: i32) -> Option<Self> {{
+        {assert}match code {{",
+        name = name,
+        quark = quark,
+        assert = assert
+    ));
+    let arms = members.iter().map(|m| {
+        (
+            m.cfg(),
+            format!("{} => Some({}::{}),", m.value, name, m.name),
+        )
+    });
+    let fallback = if members.iter().any(|m| m.name == "Failed") {
+        format!("_ => Some({}::Failed),", name)
+    } else {
+        format!("value => Some({}::__Unknown(value)),", name)
+    };
+    write_match_arms(w, arms, &fallback)
+}
+
+/// Converting into a `glib::Error` can't fail, the other way around the domain
+/// may not match, so that's left to `glib::Error::kind`.
+fn into_glib_error_impl(name: &str, error: &str) -> String {
+    format!(
+        "impl From<{name}> for {error} {{
+    fn from(value: {name}) -> Self {{
+        {error}::new(value, &format!(\"{{:?}}\", value))
+    }}
+}}",
+        name = name,
+        error = error
+    )
+}
+
+/// The expression getting the error domain quark: a configured domain is
+/// registered by its name, otherwise the `quark` function of the enum is called.
+/// The GIR `error-domain` names the quark function by convention.
+fn error_domain_quark(enum_: &Enumeration, config: &GObject) -> Option<String> {
+    if let Some(ref domain) = config.error_domain {
+        return Some(format!(
+            "glib_ffi::g_quark_from_static_string(b\"{}\\0\".as_ptr() as *const _)",
+            domain
+        ));
+    }
     enum_
         .functions
         .iter()
         .find(|f| f.name == "quark")
         .and_then(|f| f.c_identifier.clone())
         .or_else(|| enum_.error_domain.clone())
+        .map(|get_quark| format!("ffi::{}()", get_quark.replace("-", "_")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_env;
+
+    #[test]
+    fn non_exhaustive_enum_attribute() {
+        let mut config = GObject {
+            name: "Gtk.Align".into(),
+            ..Default::default()
+        };
+        let mut buf = Vec::new();
+        enum_attributes(&mut buf, &config, false).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]\n"
+        );
+
+        config.must_use = true;
+        config.non_exhaustive = true;
+        let mut buf = Vec::new();
+        enum_attributes(&mut buf, &config, false).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]\n#[must_use]\n#[non_exhaustive]\n"
+        );
+    }
+
+    #[test]
+    fn error_domain_converts_into_glib_error() {
+        let expected = "impl From<FileError> for ::glib::Error {
+    fn from(value: FileError) -> Self {
+        ::glib::Error::new(value, &format!(\"{:?}\", value))
+    }
+}";
+        assert_eq!(into_glib_error_impl("FileError", "::glib::Error"), expected);
+    }
+
+    #[test]
+    fn extra_derives_enum_attributes() {
+        let config = GObject {
+            name: "Gtk.Align".into(),
+            extra_derives: vec!["Hash".into(), "PartialOrd".into()],
+            ..Default::default()
+        };
+        let mut buf = Vec::new();
+        enum_attributes(&mut buf, &config, false).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd)]\n"
+        );
+    }
+
+    #[test]
+    fn int_conversions_round_trip_members() {
+        let members = vec![
+            IntMember {
+                value: "0",
+                name: "None",
+                cfg: None,
+            },
+            IntMember {
+                value: "1",
+                name: "Bold",
+                cfg: Some("\t\t\t#[cfg(any(feature = \"v3_10\", feature = \"dox\"))]".into()),
+            },
+        ];
+        let mut buf = Vec::new();
+        generate_int_conversions(&mut buf, "Style", None, &members).unwrap();
+        let expected = "impl Style {
+    pub const fn to_i32(self) -> i32 {
+        match self {
+\t\t\tStyle::None => 0,
+\t\t\t#[cfg(any(feature = \"v3_10\", feature = \"dox\"))]
+\t\t\tStyle::Bold => 1,
+\t\t\tStyle::__Unknown(value) => value,
+        }
+    }
+}
+
+impl From<Style> for i32 {
+    fn from(value: Style) -> i32 {
+        value.to_glib()
+    }
+}
+
+impl TryFrom<i32> for Style {
+    type Error = i32;
+
+    fn try_from(value: i32) -> ::std::result::Result<Self, i32> {
+        match value {
+\t\t\t0 => Ok(Style::None),
+\t\t\t#[cfg(any(feature = \"v3_10\", feature = \"dox\"))]
+\t\t\t1 => Ok(Style::Bold),
+\t\t\tvalue => Err(value),
+        }
+    }
+}
+
+";
+        assert_eq!(String::from_utf8(buf).unwrap(), expected);
+    }
+
+    #[test]
+    fn const_fn_needs_literal_values() {
+        let literal = IntMember {
+            value: "-1",
+            name: "Invalid",
+            cfg: None,
+        };
+        let expression = IntMember {
+            value: "GTK_STYLE_MAX",
+            name: "Max",
+            cfg: None,
+        };
+        assert!(is_const_eligible(&[literal]));
+        assert!(!is_const_eligible(&[expression]));
+    }
+
+    #[test]
+    fn error_domain_maps_codes_to_members() {
+        let members = vec![
+            IntMember {
+                value: "0",
+                name: "Failed",
+                cfg: None,
+            },
+            IntMember {
+                value: "1",
+                name: "NotFound",
+                cfg: Some("\t\t\t#[cfg(any(feature = \"v2_44\", feature = \"dox\"))]".into()),
+            },
+        ];
+        let mut buf = Vec::new();
+        generate_error_domain(
+            &mut buf,
+            "IOErrorEnum",
+            None,
+            "ffi::g_io_error_quark()",
+            "",
+            &members,
+        ).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "impl ErrorDomain for IOErrorEnum {
+    fn domain() -> glib_ffi::GQuark {
+        unsafe { ffi::g_io_error_quark() }
+    }
+
+    fn code(self) -> i32 {
+        self.to_glib()
+    }
+
+    fn from(code: i32) -> Option<Self> {
+        match code {
+\t\t\t0 => Some(IOErrorEnum::Failed),
+\t\t\t#[cfg(any(feature = \"v2_44\", feature = \"dox\"))]
+\t\t\t1 => Some(IOErrorEnum::NotFound),
+\t\t\t_ => Some(IOErrorEnum::Failed),
+        }
+    }
+}
+
+"
+        );
+    }
+
+    #[test]
+    fn error_domain_quark_from_config_or_gir() {
+        let mut enum_ = Enumeration {
+            name: "IOErrorEnum".into(),
+            c_type: "GIOErrorEnum".into(),
+            members: Vec::new(),
+            functions: Vec::new(),
+            version: None,
+            deprecated_version: None,
+            stability: Default::default(),
+            doc: None,
+            doc_deprecated: None,
+            error_domain: Some("g-io-error-quark".into()),
+            glib_get_type: None,
+        };
+        let mut config = GObject::default();
+        assert_eq!(
+            error_domain_quark(&enum_, &config),
+            Some("ffi::g_io_error_quark()".into())
+        );
+
+        config.error_domain = Some("my-error".into());
+        assert_eq!(
+            error_domain_quark(&enum_, &config),
+            Some(
+                "glib_ffi::g_quark_from_static_string(b\"my-error\\0\".as_ptr() as *const _)"
+                    .into()
+            )
+        );
+
+        enum_.error_domain = None;
+        config.error_domain = None;
+        assert_eq!(error_domain_quark(&enum_, &config), None);
+    }
+
+    #[test]
+    fn nick_conversion_matches_nicks() {
+        let members = vec![
+            NickMember {
+                nick: "start",
+                name: "Start",
+                cfg: None,
+            },
+            NickMember {
+                nick: "baseline",
+                name: "Baseline",
+                cfg: Some("\t\t\t#[cfg(any(feature = \"v3_10\", feature = \"dox\"))]".into()),
+            },
+        ];
+        let mut buf = Vec::new();
+        generate_nick_conversion(&mut buf, "Align", None, &members).unwrap();
+        let expected = "impl<'a> TryFrom<&'a str> for Align {
+    type Error = BoolError;
+
+    fn try_from(nick: &'a str) -> ::std::result::Result<Self, BoolError> {
+        match nick {
+\t\t\t\"start\" => Ok(Align::Start),
+\t\t\t#[cfg(any(feature = \"v3_10\", feature = \"dox\"))]
+\t\t\t\"baseline\" => Ok(Align::Baseline),
+\t\t\t_ => Err(BoolError(\"Unknown nick of Align\")),
+        }
+    }
+}
+
+";
+        assert_eq!(String::from_utf8(buf).unwrap(), expected);
+    }
+
+    const STYLE: &'static str = r#"
+    <enumeration name="Style" c:type="TestStyle">
+      <member name="none" value="0" c:identifier="TEST_STYLE_NONE" glib:nick="none"/>
+      <member name="bold" value="1" c:identifier="TEST_STYLE_BOLD" glib:nick="bold"/>
+    </enumeration>"#;
+
+    const BOLD_MEMBER: &'static str = r#"
+    [[object.member]]
+    name = "bold"
+    version = "1.2""#;
+
+    #[test]
+    fn doc_cfg_is_not_emitted_on_match_arms() {
+        let options = "emit_doc_cfg = true
+generate_int_conversions = true
+generate_nick_conversions = true";
+        let code = test_env::generated_object(STYLE, options, "Style", BOLD_MEMBER);
+        let cfg = "#[cfg(any(feature = \"v1_2\", feature = \"dox\"))]";
+        let doc_cfg = "#[cfg_attr(feature = \"dox\", doc(cfg(feature = \"v1_2\")))]";
+        assert!(code.contains(&format!("    {}\n    {}\n    Bold,\n", cfg, doc_cfg)));
+        assert_eq!(code.matches(doc_cfg).count(), 1);
+        let arms = [
+            "Style::Bold => ffi::TEST_STYLE_BOLD,",
+            "1 => Style::Bold,",
+            "1 => Ok(Style::Bold),",
+            "\"bold\" => Ok(Style::Bold),",
+        ];
+        for arm in &arms {
+            let gated = format!("            {}\n            {}\n", cfg, arm);
+            assert!(code.contains(&gated), "{} is not gated", arm);
+        }
+    }
+
+    #[test]
+    fn try_from_is_imported_only_for_enums() {
+        let options = "generate_int_conversions = true\ngenerate_nick_conversions = true";
+        let code = test_env::generated_object(STYLE, options, "Style", BOLD_MEMBER);
+        assert!(code.contains("use std::convert::TryFrom;\n"));
+        assert!(code.contains("use glib::error::BoolError;\n"));
+
+        // A namespace without enums
+        let code = test_env::generated_file("", options, "", "enums.rs");
+        assert!(!code.contains("TryFrom"));
+        assert!(!code.contains("BoolError"));
+    }
+
+    #[test]
+    fn members_above_max_cfg_version_are_omitted() {
+        let options = "max_cfg_version = \"1.1\"";
+        let code = test_env::generated_object(STYLE, options, "Style", BOLD_MEMBER);
+        assert!(code.contains("pub enum Style {\n"));
+        assert!(code.contains("    None,\n"));
+        assert!(!code.contains("Bold"));
+
+        let code =
+            test_env::generated_object(STYLE, "max_cfg_version = \"1.2\"", "Style", BOLD_MEMBER);
+        assert!(code.contains("    Bold,\n"));
+
+        // Like for functions the oldest configured version counts
+        let object = r#"
+    [[object.member]]
+    pattern = "b.*"
+    version = "1.1""#;
+        let object = format!("{}{}", object, BOLD_MEMBER);
+        let code = test_env::generated_object(STYLE, options, "Style", &object);
+        assert!(code.contains("    Bold,\n"));
+
+        let object = format!("version = \"1.2\"{}", BOLD_MEMBER);
+        let code = test_env::generated_object(STYLE, options, "Style", &object);
+        assert!(!code.contains("pub enum Style"));
+    }
+
+    /// Evaluates the generated `match` following `header` for `input`,
+    /// arms binding `value` substitute the matched part in their result.
+    fn eval_match(code: &str, header: &str, input: &str) -> String {
+        let start = code.find(header).unwrap() + header.len();
+        let arms = &code[start..code[start..].find("        }\n").unwrap() + start];
+        for arm in arms.lines().map(|line| line.trim()) {
+            if arm.starts_with('#') || !arm.contains(" => ") {
+                continue;
+            }
+            let mut parts = arm.trim_right_matches(',').split(" => ");
+            let (pattern, result) = (parts.next().unwrap(), parts.next().unwrap());
+            match pattern.find("value") {
+                Some(pos) => {
+                    let (prefix, suffix) = (&pattern[..pos], &pattern[pos + "value".len()..]);
+                    if input.starts_with(prefix) && input.ends_with(suffix) {
+                        let bound = &input[prefix.len()..input.len() - suffix.len()];
+                        return result.replace("value", bound);
+                    }
+                }
+                None if pattern == input => return result.to_owned(),
+                None => (),
+            }
+        }
+        panic!("No arm of `{}` matches {}", header, input);
+    }
+
+    #[test]
+    fn unknown_value_round_trips() {
+        let code = test_env::generated_object(STYLE, "", "Style", BOLD_MEMBER);
+        let from_glib = "fn from_glib(value: ffi::TestStyle) -> Self {";
+        let to_glib = "fn to_glib(&self) -> ffi::TestStyle {";
+
+        let unknown = eval_match(&code, from_glib, "42");
+        assert_eq!(unknown, "Style::__Unknown(42)");
+        assert_eq!(eval_match(&code, to_glib, &unknown), "42");
+
+        let unknown = eval_match(&code, from_glib, "-7");
+        assert_eq!(eval_match(&code, to_glib, &unknown), "-7");
+
+        let bold = eval_match(&code, from_glib, "1");
+        assert_eq!(bold, "Style::Bold");
+        assert_eq!(eval_match(&code, to_glib, &bold), "ffi::TEST_STYLE_BOLD");
+    }
+
+    #[test]
+    fn serde_derives_are_feature_gated() {
+        assert!(!test_env::generated_object(STYLE, "", "Style", BOLD_MEMBER).contains("serde"));
+
+        for &(options, object) in &[("serde = true", ""), ("", "serde = true")] {
+            let object = format!("{}{}", object, BOLD_MEMBER);
+            let code = test_env::generated_object(STYLE, options, "Style", &object);
+            assert!(code.contains(
+                "#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = \"serde\", derive(Serialize, Deserialize))]
+"
+            ));
+            assert!(code.contains(
+                "    #[cfg_attr(feature = \"serde\", serde(rename = \"none\"))]\n    None,\n"
+            ));
+            assert!(code.contains(
+                "    #[cfg_attr(feature = \"serde\", serde(rename = \"bold\"))]\n    Bold,\n"
+            ));
+            assert!(code.contains(
+                "    #[doc(hidden)]
+    #[cfg_attr(feature = \"serde\", serde(skip))]
+    __Unknown(i32),"
+            ));
+        }
+    }
 }
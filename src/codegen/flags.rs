@@ -1,9 +1,11 @@
-use analysis::namespaces;
-use codegen::general::{self, version_condition, version_condition_string};
+use analysis::{self, namespaces};
+use codegen::general::{self, cfg_and_version_condition, cfg_and_version_condition_string,
+                       version_condition};
 use config::gobjects::GObject;
 use env::Env;
 use file_saver;
 use library::*;
+use std::collections::BTreeSet;
 use std::io::prelude::*;
 use std::io::Result;
 use std::path::Path;
@@ -19,14 +21,21 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
                 c.status.need_generate()
                     && c.type_id.map_or(false, |tid| tid.ns_id == namespaces::MAIN)
             })
+            .filter(|c| match *env.library.type_(c.type_id.unwrap()) {
+                Type::Bitfield(ref flags) => !env.is_too_new(&c.name, c.version.or(flags.version)),
+                _ => true,
+            })
             .collect();
 
         let mut has_get_type = false;
+        let mut has_try_from = false;
         for config in &configs {
             if let Type::Bitfield(ref flags) = *env.library.type_(config.type_id.unwrap()) {
                 if flags.glib_get_type.is_some() {
                     has_get_type = true;
-                    break;
+                }
+                if env.config.generate_int_conversions && !config.truncate_bits {
+                    has_try_from = true;
                 }
             }
         }
@@ -57,6 +66,20 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
             }
             try!(writeln!(w, "use glib::translate::*;"));
         }
+        if has_try_from {
+            try!(writeln!(w, "use std::convert::TryFrom;"));
+        }
+        let derive_uses: BTreeSet<&str> = configs
+            .iter()
+            .filter(|c| match *env.library.type_(c.type_id.unwrap()) {
+                Type::Bitfield(..) => true,
+                _ => false,
+            })
+            .flat_map(|c| analysis::general::derive_uses(&c.extra_derives))
+            .collect();
+        for derive in derive_uses {
+            try!(writeln!(w, "use {};", derive));
+        }
         try!(writeln!(w, ""));
 
         let mut first = true;
@@ -66,7 +89,10 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
                     mod_rs.push("\nmod flags;".into());
                     first = false;
                 }
-                if let Some(cfg) = version_condition_string(env, flags.version, false, 0) {
+                let cfg_condition = &config.cfg_condition;
+                if let Some(cfg) =
+                    cfg_and_version_condition_string(env, cfg_condition, flags.version, false, 0)
+                {
                     mod_rs.push(cfg);
                 }
                 mod_rs.push(format!("pub use self::flags::{};", flags.name));
@@ -78,8 +104,22 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
     });
 }
 
+const BITFLAGS_DERIVES: &[&str] = &[
+    "Copy",
+    "PartialEq",
+    "Eq",
+    "Clone",
+    "PartialOrd",
+    "Ord",
+    "Hash",
+    "Debug",
+];
+
+// Flags are `bitflags!` types, so `|`, `&`, `^`, `-` and `!` are provided by the macro
+// and keep the value within the bits of the known members. Unknown bits coming from C
+// are dropped by `from_bits_truncate` in `FromGlib`.
 fn generate_flags(env: &Env, w: &mut Write, flags: &Bitfield, config: &GObject) -> Result<()> {
-    try!(version_condition(w, env, flags.version, false, 0));
+    try!(cfg_and_version_condition(w, env, &config.cfg_condition, flags.version, false, 0));
     try!(writeln!(w, "bitflags! {{"));
     if config.must_use {
         try!(writeln!(
@@ -87,6 +127,20 @@ fn generate_flags(env: &Env, w: &mut Write, flags: &Bitfield, config: &GObject)
             "    #[must_use]"
         ));
     }
+    try!(general::doc_alias(w, &flags.c_type, "", 1));
+    // Only derives not already implemented by `bitflags!`, `Default` is implemented
+    // as the empty set or by hand
+    let derives: Vec<&str> = general::merge_derives(BITFLAGS_DERIVES, &config.extra_derives)
+        [BITFLAGS_DERIVES.len()..]
+        .iter()
+        .cloned()
+        .filter(|&derive| derive != "Default")
+        .collect();
+    try!(general::write_derives(w, &derives, 1));
+    if config.serde.unwrap_or(env.config.serde) {
+        // Flags are (de)serialized by their bits
+        try!(general::derive_serde(w, 1));
+    }
     try!(writeln!(w, "    pub struct {}: u32 {{", flags.name));
     for member in &flags.members {
         let member_config = config.members.matched(&member.name);
@@ -95,9 +149,14 @@ fn generate_flags(env: &Env, w: &mut Write, flags: &Bitfield, config: &GObject)
             continue;
         }
 
+        let version = member_config.iter().filter_map(|m| m.version).min();
+        // Members are as old as their flags unless configured otherwise
+        if env.is_too_new(&member.c_identifier, version.or(config.version).or(flags.version)) {
+            continue;
+        }
+
         let name = member.name.to_uppercase();
         let val: i64 = member.value.parse().unwrap();
-        let version = member_config.iter().filter_map(|m| m.version).next();
         try!(version_condition(w, env, version, false, 2));
         try!(writeln!(w, "\t\tconst {} = {};", name, val as u32));
     }
@@ -110,7 +169,7 @@ fn generate_flags(env: &Env, w: &mut Write, flags: &Bitfield, config: &GObject)
 "
     ));
 
-    try!(version_condition(w, env, flags.version, false, 0));
+    try!(cfg_and_version_condition(w, env, &config.cfg_condition, flags.version, false, 0));
     try!(writeln!(
         w,
         "#[doc(hidden)]
@@ -132,7 +191,7 @@ impl ToGlib for {name} {{
         ""
     };
 
-    try!(version_condition(w, env, flags.version, false, 0));
+    try!(cfg_and_version_condition(w, env, &config.cfg_condition, flags.version, false, 0));
     try!(writeln!(
         w,
         "#[doc(hidden)]
@@ -147,8 +206,24 @@ impl FromGlib<ffi::{ffi_name}> for {name} {{
         assert = assert
     ));
 
+    if need_default(config) {
+        try!(cfg_and_version_condition(w, env, &config.cfg_condition, flags.version, false, 0));
+        try!(generate_default(w, &flags.name));
+    }
+
+    if env.config.generate_int_conversions {
+        let flags_cfg =
+            cfg_and_version_condition_string(env, &config.cfg_condition, flags.version, false, 0);
+        try!(generate_int_conversions(
+            w,
+            &flags.name,
+            flags_cfg.as_ref().map(|s| &s[..]),
+            config.truncate_bits
+        ));
+    }
+
     if let Some(ref get_type) = flags.glib_get_type {
-        try!(version_condition(w, env, flags.version, false, 0));
+        try!(cfg_and_version_condition(w, env, &config.cfg_condition, flags.version, false, 0));
         try!(writeln!(
             w,
             "impl StaticType for {name} {{
@@ -161,7 +236,7 @@ impl FromGlib<ffi::{ffi_name}> for {name} {{
         ));
         try!(writeln!(w, ""));
 
-        try!(version_condition(w, env, flags.version, false, 0));
+        try!(cfg_and_version_condition(w, env, &config.cfg_condition, flags.version, false, 0));
         try!(writeln!(
             w,
             "impl<'a> FromValueOptional<'a> for {name} {{
@@ -173,7 +248,7 @@ impl FromGlib<ffi::{ffi_name}> for {name} {{
         ));
         try!(writeln!(w, ""));
 
-        try!(version_condition(w, env, flags.version, false, 0));
+        try!(cfg_and_version_condition(w, env, &config.cfg_condition, flags.version, false, 0));
         try!(writeln!(
             w,
             "impl<'a> FromValue<'a> for {name} {{
@@ -186,7 +261,7 @@ impl FromGlib<ffi::{ffi_name}> for {name} {{
         ));
         try!(writeln!(w, ""));
 
-        try!(version_condition(w, env, flags.version, false, 0));
+        try!(cfg_and_version_condition(w, env, &config.cfg_condition, flags.version, false, 0));
         try!(writeln!(
             w,
             "impl SetValue for {name} {{
@@ -198,7 +273,231 @@ impl FromGlib<ffi::{ffi_name}> for {name} {{
         ));
 
         try!(writeln!(w, ""));
+
+        if env.config.generate_value_from {
+            try!(cfg_and_version_condition(w, env, &config.cfg_condition, flags.version, false, 0));
+            try!(writeln!(w, "{}", general::value_from_impl(&flags.name)));
+            try!(writeln!(w, ""));
+        }
     }
 
     Ok(())
 }
+
+/// The empty set, unless `Default` is implemented manually.
+fn need_default(config: &GObject) -> bool {
+    !config.manual_traits.iter().any(|t| t == "Default")
+}
+
+fn generate_default(w: &mut Write, name: &str) -> Result<()> {
+    writeln!(
+        w,
+        "impl Default for {name} {{
+    fn default() -> Self {{
+        {name}::empty()
+    }}
+}}
+",
+        name = name
+    )
+}
+
+/// Generates conversions between the flags and their `u32` bits. Bits outside the
+/// known members are rejected by `TryFrom`, or dropped if `truncate` is set.
+fn generate_int_conversions(
+    w: &mut Write,
+    name: &str,
+    cfg: Option<&str>,
+    truncate: bool,
+) -> Result<()> {
+    if let Some(cfg) = cfg {
+        try!(writeln!(w, "{}", cfg));
+    }
+    try!(writeln!(
+        w,
+        "impl From<{name}> for u32 {{
+    fn from(value: {name}) -> u32 {{
+        value.bits()
+    }}
+}}
+",
+        name = name
+    ));
+
+    if let Some(cfg) = cfg {
+        try!(writeln!(w, "{}", cfg));
+    }
+    if truncate {
+        writeln!(
+            w,
+            "impl From<u32> for {name} {{
+    fn from(value: u32) -> Self {{
+        {name}::from_bits_truncate(value)
+    }}
+}}
+",
+            name = name
+        )
+    } else {
+        writeln!(
+            w,
+            "impl TryFrom<u32> for {name} {{
+    type Error = u32;
+
+    fn try_from(value: u32) -> ::std::result::Result<Self, u32> {{
+        {name}::from_bits(value).ok_or(value)
+    }}
+}}
+",
+            name = name
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_env;
+
+    #[test]
+    fn flags_default_to_empty_set() {
+        assert!(need_default(&GObject::default()));
+        let mut w = Vec::new();
+        generate_default(&mut w, "StateFlags").unwrap();
+        assert_eq!(
+            String::from_utf8(w).unwrap(),
+            "impl Default for StateFlags {
+    fn default() -> Self {
+        StateFlags::empty()
+    }
+}
+
+"
+        );
+    }
+
+    #[test]
+    fn manual_default_suppresses_generated() {
+        let config = GObject {
+            manual_traits: vec!["Default".to_owned()],
+            ..Default::default()
+        };
+        assert!(!need_default(&config));
+    }
+
+    #[test]
+    fn int_conversions_reject_unknown_bits() {
+        let mut w = Vec::new();
+        generate_int_conversions(&mut w, "StateFlags", None, false).unwrap();
+        assert_eq!(
+            String::from_utf8(w).unwrap(),
+            "impl From<StateFlags> for u32 {
+    fn from(value: StateFlags) -> u32 {
+        value.bits()
+    }
+}
+
+impl TryFrom<u32> for StateFlags {
+    type Error = u32;
+
+    fn try_from(value: u32) -> ::std::result::Result<Self, u32> {
+        StateFlags::from_bits(value).ok_or(value)
+    }
+}
+
+"
+        );
+    }
+
+    #[test]
+    fn int_conversions_truncate_unknown_bits() {
+        let mut w = Vec::new();
+        generate_int_conversions(
+            &mut w,
+            "StateFlags",
+            Some("#[cfg(any(feature = \"v3_10\", feature = \"dox\"))]"),
+            true,
+        ).unwrap();
+        let code = String::from_utf8(w).unwrap();
+        assert!(code.contains(
+            "#[cfg(any(feature = \"v3_10\", feature = \"dox\"))]
+impl From<u32> for StateFlags {
+    fn from(value: u32) -> Self {
+        StateFlags::from_bits_truncate(value)
+    }
+}
+"
+        ));
+        assert!(!code.contains("TryFrom"));
+    }
+
+    const STATE_FLAGS: &'static str = r#"
+    <bitfield name="StateFlags" c:type="TestStateFlags">
+      <member name="normal" value="0" c:identifier="TEST_STATE_FLAG_NORMAL"/>
+      <member name="active" value="1" c:identifier="TEST_STATE_FLAG_ACTIVE"/>
+      <member name="prelight" value="2" c:identifier="TEST_STATE_FLAG_PRELIGHT"/>
+      <member name="focused" value="-2147483648" c:identifier="TEST_STATE_FLAG_FOCUSED"/>
+    </bitfield>"#;
+
+    #[test]
+    fn try_from_is_imported_only_if_implemented() {
+        let options = "generate_int_conversions = true";
+        let code = test_env::generated_object(STATE_FLAGS, options, "StateFlags", "");
+        assert!(code.contains("use std::convert::TryFrom;\n"));
+        assert!(code.contains("impl TryFrom<u32> for StateFlags {\n"));
+
+        let object = "truncate_bits = true";
+        let code = test_env::generated_object(STATE_FLAGS, options, "StateFlags", object);
+        assert!(!code.contains("TryFrom"));
+        assert!(code.contains("impl From<u32> for StateFlags {\n"));
+
+        assert!(!test_env::generated_object(STATE_FLAGS, "", "StateFlags", "").contains("TryFrom"));
+    }
+
+    #[test]
+    fn default_is_not_derived_twice() {
+        let object = "extra_derives = [\"Default\", \"my_crate::MyDerive\"]";
+        let code = test_env::generated_object(STATE_FLAGS, "", "StateFlags", object);
+        assert!(code.contains("    #[derive(MyDerive)]\n    pub struct StateFlags: u32 {\n"));
+        assert_eq!(code.matches("Default").count(), 1);
+        assert!(code.contains("impl Default for StateFlags {\n"));
+    }
+
+    #[test]
+    fn operators_combine_member_bits() {
+        use std::collections::HashMap;
+
+        let code = test_env::generated_object(STATE_FLAGS, "", "StateFlags", "");
+        // `|` and `&` come from `bitflags!` and work on the bits of the constants
+        assert!(code.contains("bitflags! {\n"));
+        assert!(!code.contains("BitOr"));
+        let bits: HashMap<&str, u32> = code.lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if !line.starts_with("const ") {
+                    return None;
+                }
+                let mut parts = line["const ".len()..].trim_right_matches(';').split(" = ");
+                Some((parts.next().unwrap(), parts.next().unwrap().parse().unwrap()))
+            })
+            .collect();
+        assert_eq!(bits["NORMAL"], 0);
+        assert_eq!(bits["ACTIVE"] | bits["PRELIGHT"], 0b11);
+        assert_eq!((bits["ACTIVE"] | bits["PRELIGHT"]) & bits["PRELIGHT"], 0b10);
+        assert_eq!(bits["ACTIVE"] & bits["PRELIGHT"], 0);
+        // The sign bit of the C value is kept
+        assert_eq!(bits["FOCUSED"] | bits["ACTIVE"], 0x8000_0001);
+        assert_eq!((bits["FOCUSED"] | bits["ACTIVE"]) & bits["FOCUSED"], 0x8000_0000);
+    }
+
+    #[test]
+    fn serde_derives_are_feature_gated() {
+        assert!(!test_env::generated_object(STATE_FLAGS, "", "StateFlags", "").contains("serde"));
+
+        let code = test_env::generated_object(STATE_FLAGS, "serde = true", "StateFlags", "");
+        assert!(code.contains(
+            "    #[cfg_attr(feature = \"serde\", derive(Serialize, Deserialize))]
+    pub struct StateFlags: u32 {"
+        ));
+    }
+}
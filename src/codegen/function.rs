@@ -6,11 +6,13 @@ use analysis::functions::Visibility;
 use analysis::namespaces;
 use chunk::{ffi_function_todo, Chunk};
 use env::Env;
+use library;
 use super::function_body_chunk;
-use super::general::{cfg_condition, doc_hidden, not_version_condition, version_condition};
+use super::general::{cfg_condition, doc_alias, doc_hidden, not_version_condition,
+                     replaced_version_condition, version_window_condition};
 use super::parameter::ToParameter;
 use super::return_value::{out_parameters_as_return, ToReturnValue};
-use writer::primitives::tabs;
+use writer::primitives::{format_block, tabs};
 use writer::ToCode;
 
 pub fn generate(
@@ -45,20 +47,30 @@ pub fn generate(
 
     try!(writeln!(w, ""));
     try!(cfg_condition(w, &analysis.cfg_condition, commented, indent));
-    try!(version_condition(
+    try!(version_window_condition(
         w,
         env,
         analysis.version,
+        analysis.removed_version,
         commented,
         indent,
     ));
     try!(not_version_condition(
         w,
+        env,
         analysis.not_version,
         commented,
         indent,
     ));
+    try!(replaced_version_condition(
+        w,
+        env,
+        analysis.replaced_in,
+        commented,
+        indent,
+    ));
     try!(doc_hidden(w, analysis.doc_hidden, comment_prefix, indent));
+    try!(doc_alias(w, &analysis.glib_name, comment_prefix, indent));
     try!(writeln!(
         w,
         "{}{}{}{}{}",
@@ -70,7 +82,19 @@ pub fn generate(
     ));
 
     if !only_declaration {
-        let body = body_chunk(env, analysis).to_code(env);
+        match env.config.trace_macro {
+            Some(ref macro_path) if !commented => {
+                let args = debug_args(env, analysis);
+                for s in trace_lines(macro_path, &analysis.glib_name, &args) {
+                    try!(writeln!(w, "{}{}", tabs(indent + 1), s));
+                }
+            }
+            _ => (),
+        }
+        let body = match analysis.body_override {
+            Some(ref body) if !commented => body_override_lines(body),
+            _ => body_chunk(env, analysis).to_code(env),
+        };
         for s in body {
             try!(writeln!(w, "{}{}", tabs(indent), s));
         }
@@ -79,6 +103,51 @@ pub fn generate(
     Ok(())
 }
 
+/// Traces the call with the parameters implementing `Debug`, behind the `trace` feature.
+fn trace_lines(macro_path: &str, glib_name: &str, args: &[&str]) -> Vec<String> {
+    let format: Vec<String> = args.iter().map(|arg| format!("{} = {{:?}}", arg)).collect();
+    let values: String = args.iter().map(|arg| format!(", {}", arg)).collect();
+    vec![
+        "#[cfg(feature = \"trace\")]".to_owned(),
+        format!(
+            "{}(\"{}({})\"{});",
+            macro_path,
+            glib_name,
+            format.join(", "),
+            values
+        ),
+    ]
+}
+
+/// Parameters whose Rust type implements `Debug`; generic ones are skipped.
+fn debug_args<'a>(env: &Env, analysis: &'a analysis::functions::Info) -> Vec<&'a str> {
+    analysis
+        .parameters
+        .rust_parameters
+        .iter()
+        .filter(|par| {
+            let c_par = &analysis.parameters.c_parameters[par.ind_c];
+            !c_par.instance_parameter
+                && analysis.bounds.get_parameter_alias_info(&par.name).is_none()
+                && is_debug(env, par.typ)
+        })
+        .map(|par| &par.name[..])
+        .collect()
+}
+
+fn is_debug(env: &Env, typ: library::TypeId) -> bool {
+    use library::Type::*;
+    match *env.library.type_(typ) {
+        Fundamental(library::Fundamental::Pointer) |
+        Fundamental(library::Fundamental::VarArgs) |
+        Fundamental(library::Fundamental::Unsupported) => false,
+        Fundamental(_) | Enumeration(..) | Bitfield(..) => true,
+        Class(..) | Interface(..) | Record(..) => true,
+        Alias(ref alias) => is_debug(env, alias.typ),
+        _ => false,
+    }
+}
+
 pub fn declaration(env: &Env, analysis: &analysis::functions::Info) -> String {
     let outs_as_return = !analysis.outs.is_empty();
     let return_str = if outs_as_return {
@@ -104,6 +173,11 @@ pub fn declaration(env: &Env, analysis: &analysis::functions::Info) -> String {
         let s = c_par.to_parameter(env, &analysis.bounds);
         param_str.push_str(&s);
     }
+    let return_str = if analysis.chain {
+        chain_return(&param_str).to_owned()
+    } else {
+        return_str
+    };
 
     format!(
         "fn {}{}({}){}",
@@ -114,6 +188,17 @@ pub fn declaration(env: &Env, analysis: &analysis::functions::Info) -> String {
     )
 }
 
+/// A chained method returns its receiver the way it's taken.
+fn chain_return(param_str: &str) -> &'static str {
+    if param_str.starts_with("&mut self") {
+        " -> &mut Self"
+    } else if param_str.starts_with("&self") {
+        " -> &Self"
+    } else {
+        " -> Self"
+    }
+}
+
 pub fn bounds(bounds: &Bounds) -> String {
     use analysis::bounds::BoundType::*;
     if bounds.is_empty() {
@@ -154,7 +239,9 @@ pub fn body_chunk(env: &Env, analysis: &analysis::functions::Info) -> Chunk {
         .assertion(analysis.assertion)
         .ret(&analysis.ret)
         .transformations(&analysis.parameters.transformations)
-        .outs_mode(analysis.outs.mode);
+        .outs_mode(analysis.outs.mode)
+        .error_type(analysis.error_type.as_ref())
+        .chain(analysis.chain);
 
     for par in &analysis.parameters.c_parameters {
         if outs_as_return && analysis.outs.iter().any(|p| p.name == par.name) {
@@ -166,3 +253,644 @@ pub fn body_chunk(env: &Env, analysis: &analysis::functions::Info) -> Chunk {
 
     builder.generate()
 }
+
+/// Splits a configured body into lines indented like a generated body,
+/// stripping the snippet's common indentation.
+fn body_override_lines(body: &str) -> Vec<String> {
+    let lines: Vec<&str> = body.trim_matches('\n').lines().collect();
+    let indent = lines
+        .iter()
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| s.len() - s.trim_left().len())
+        .min()
+        .unwrap_or(0);
+    let lines: Vec<String> = lines
+        .iter()
+        .map(|s| if s.trim().is_empty() {
+            String::new()
+        } else {
+            s[indent..].trim_right().to_owned()
+        })
+        .collect();
+    format_block("", "}", &lines)
+        .into_iter()
+        .map(|s| if s.trim().is_empty() { String::new() } else { s })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use analysis::bounds::BoundType;
+    use test_env;
+
+    #[test]
+    fn chained_method_returns_receiver() {
+        assert_eq!(chain_return("&mut self, value: &Value"), " -> &mut Self");
+        assert_eq!(chain_return("&self, value: &Value"), " -> &Self");
+        assert_eq!(chain_return("self"), " -> Self");
+    }
+
+    #[test]
+    fn body_override_is_wrapped_and_reindented() {
+        let body = "
+        unsafe {
+            from_glib_full(ffi::gtk_foo_new())
+        }
+
+        // trailing comment
+";
+        let lines = body_override_lines(body);
+        assert_eq!(
+            lines,
+            vec![
+                "    unsafe {",
+                "        from_glib_full(ffi::gtk_foo_new())",
+                "    }",
+                "",
+                "    // trailing comment",
+                "}",
+            ]
+        );
+    }
+
+    #[test]
+    fn is_a_bound_for_parent_parameter() {
+        let mut bounds: Bounds = Default::default();
+        bounds.add_parameter("parent", "Widget", BoundType::IsA(None));
+        bounds.add_parameter("child", "Widget", BoundType::IsA(None));
+        assert_eq!(super::bounds(&bounds), "<P: IsA<Widget>, Q: IsA<Widget>>");
+    }
+
+    const FUNCTIONS: &'static str = r#"
+    <class name="Widget" c:type="TestWidget" parent="GObject.InitiallyUnowned"
+           glib:type-name="TestWidget" glib:get-type="test_widget_get_type">
+    </class>
+    <enumeration name="FileError" c:type="TestFileError" glib:error-domain="test-file-error-quark">
+      <member name="failed" value="0" c:identifier="TEST_FILE_ERROR_FAILED"/>
+      <member name="exists" value="1" c:identifier="TEST_FILE_ERROR_EXISTS"/>
+    </enumeration>
+    <function name="set_value" c:identifier="test_set_value">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="value" transfer-ownership="none">
+          <type name="GLib.Variant" c:type="GVariant*"/>
+        </parameter>
+        <parameter name="value_type" transfer-ownership="none">
+          <type name="GLib.VariantType" c:type="const GVariantType*"/>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="peek_value" c:identifier="test_peek_value">
+      <return-value transfer-ownership="none">
+        <type name="GLib.Variant" c:type="GVariant*"/>
+      </return-value>
+    </function>
+    <function name="dup_value" c:identifier="test_dup_value">
+      <return-value transfer-ownership="full">
+        <type name="GLib.Variant" c:type="GVariant*"/>
+      </return-value>
+    </function>
+    <function name="set_deadline" c:identifier="test_set_deadline">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="deadline" transfer-ownership="none" nullable="1">
+          <type name="GLib.DateTime" c:type="GDateTime*"/>
+        </parameter>
+        <parameter name="mtime" transfer-ownership="none">
+          <type name="glong" c:type="time_t"/>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="get_mtime" c:identifier="test_get_mtime">
+      <return-value transfer-ownership="none">
+        <type name="glong" c:type="time_t"/>
+      </return-value>
+    </function>
+    <function name="write_bytes" c:identifier="test_write_bytes">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="bytes" transfer-ownership="none">
+          <type name="GLib.Bytes" c:type="GBytes*"/>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="write_data" c:identifier="test_write_data">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="data" transfer-ownership="none">
+          <array length="1" zero-terminated="0" c:type="const guint8*">
+            <type name="guint8" c:type="guint8"/>
+          </array>
+        </parameter>
+        <parameter name="len" transfer-ownership="none">
+          <type name="gsize" c:type="gsize"/>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="peek_bytes" c:identifier="test_peek_bytes">
+      <return-value transfer-ownership="none">
+        <type name="GLib.Bytes" c:type="GBytes*"/>
+      </return-value>
+    </function>
+    <function name="dup_bytes" c:identifier="test_dup_bytes">
+      <return-value transfer-ownership="full">
+        <type name="GLib.Bytes" c:type="GBytes*"/>
+      </return-value>
+    </function>
+    <function name="load" c:identifier="test_load">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="cancellable" transfer-ownership="none">
+          <type name="Gio.Cancellable" c:type="GCancellable*"/>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="iterate" c:identifier="test_iterate">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="context" transfer-ownership="none">
+          <type name="GLib.MainContext" c:type="GMainContext*"/>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="set_key" c:identifier="test_set_key">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="key" transfer-ownership="none">
+          <array zero-terminated="0" c:type="guint8*" fixed-size="4">
+            <type name="guint8" c:type="guint8"/>
+          </array>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="get_key" c:identifier="test_get_key">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="key" direction="out" caller-allocates="1" transfer-ownership="none">
+          <array zero-terminated="0" c:type="guint8*" fixed-size="4">
+            <type name="guint8" c:type="guint8"/>
+          </array>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="get_items" c:identifier="test_get_items">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="items" direction="out" caller-allocates="0" transfer-ownership="full">
+          <array length="1" zero-terminated="0" c:type="gchar***">
+            <type name="utf8" c:type="gchar*"/>
+          </array>
+        </parameter>
+        <parameter name="n_items" direction="out" caller-allocates="0" transfer-ownership="full">
+          <type name="guint" c:type="guint*"/>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="resize" c:identifier="test_resize">
+      <return-value transfer-ownership="none">
+        <type name="gssize" c:type="gssize"/>
+      </return-value>
+      <parameters>
+        <parameter name="size" transfer-ownership="none">
+          <type name="gsize" c:type="gsize"/>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="set_names" c:identifier="test_set_names">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="names" transfer-ownership="none">
+          <array c:type="gchar**">
+            <type name="utf8" c:type="gchar*"/>
+          </array>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="get_names" c:identifier="test_get_names">
+      <return-value transfer-ownership="full">
+        <array c:type="gchar**">
+          <type name="utf8" c:type="gchar*"/>
+        </array>
+      </return-value>
+    </function>
+    <function name="append_names" c:identifier="test_append_names">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="names" transfer-ownership="none">
+          <type name="GLib.List" c:type="GList*">
+            <type name="utf8" c:type="gchar*"/>
+          </type>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="set_labels" c:identifier="test_set_labels">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="names" transfer-ownership="container">
+          <type name="GLib.SList" c:type="GSList*">
+            <type name="utf8" c:type="gchar*"/>
+          </type>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="take_names" c:identifier="test_take_names">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="names" transfer-ownership="full">
+          <type name="GLib.List" c:type="GList*">
+            <type name="utf8" c:type="gchar*"/>
+          </type>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="set_opacity" c:identifier="test_set_opacity">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="opacity" transfer-ownership="none">
+          <type name="gdouble" c:type="gdouble"/>
+        </parameter>
+        <parameter name="cancellable" transfer-ownership="none">
+          <type name="Gio.Cancellable" c:type="GCancellable*"/>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="show" c:identifier="test_show">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+    </function>
+    <function name="dup_widgets" c:identifier="test_dup_widgets">
+      <return-value transfer-ownership="full">
+        <array length="0" zero-terminated="0" c:type="TestWidget**">
+          <type name="Widget" c:type="TestWidget*"/>
+        </array>
+      </return-value>
+      <parameters>
+        <parameter name="n_widgets" direction="out" caller-allocates="0" transfer-ownership="full">
+          <type name="guint" c:type="guint*"/>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="get_widgets" c:identifier="test_get_widgets">
+      <return-value transfer-ownership="container">
+        <array length="0" zero-terminated="0" c:type="TestWidget**">
+          <type name="Widget" c:type="TestWidget*"/>
+        </array>
+      </return-value>
+      <parameters>
+        <parameter name="n_widgets" direction="out" caller-allocates="0" transfer-ownership="full">
+          <type name="guint" c:type="guint*"/>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="peek_widgets" c:identifier="test_peek_widgets">
+      <return-value transfer-ownership="none">
+        <array length="0" zero-terminated="0" c:type="TestWidget**">
+          <type name="Widget" c:type="TestWidget*"/>
+        </array>
+      </return-value>
+      <parameters>
+        <parameter name="n_widgets" direction="out" caller-allocates="0" transfer-ownership="full">
+          <type name="guint" c:type="guint*"/>
+        </parameter>
+      </parameters>
+    </function>
+    <function name="save" c:identifier="test_save">
+      <return-value transfer-ownership="none">
+        <type name="gboolean" c:type="gboolean"/>
+      </return-value>
+    </function>
+    <function name="create_widget" c:identifier="test_create_widget">
+      <return-value transfer-ownership="floating">
+        <type name="Widget" c:type="TestWidget*"/>
+      </return-value>
+    </function>
+    <function name="dup_widget" c:identifier="test_dup_widget">
+      <return-value transfer-ownership="full">
+        <type name="Widget" c:type="TestWidget*"/>
+      </return-value>
+    </function>
+    <function name="lookup" c:identifier="test_lookup" throws="1">
+      <return-value transfer-ownership="full" nullable="1">
+        <type name="Widget" c:type="TestWidget*"/>
+      </return-value>
+    </function>
+    <function name="find" c:identifier="test_find" throws="1">
+      <return-value transfer-ownership="full">
+        <type name="Widget" c:type="TestWidget*"/>
+      </return-value>
+    </function>"#;
+
+    const OPTIONS: &'static str = r#"manual = [
+    "GLib.Bytes",
+    "GLib.DateTime",
+    "GLib.MainContext",
+    "GLib.Variant",
+    "GLib.VariantType",
+    "Gio.Cancellable",
+    "Test.Widget",
+]
+into_option_params = false"#;
+
+    #[test]
+    fn variant_parameter_is_borrowed() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains(
+            "\npub fn set_value(value: &glib::Variant, value_type: &glib::VariantTy) {"
+        ));
+        assert!(code.contains("value.to_glib_none().0"));
+    }
+
+    #[test]
+    fn variant_return_follows_transfer() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains("\npub fn peek_value() -> Option<glib::Variant> {"));
+        assert!(code.contains("from_glib_none(ffi::test_peek_value())"));
+        assert!(code.contains("\npub fn dup_value() -> Option<glib::Variant> {"));
+        assert!(code.contains("from_glib_full(ffi::test_dup_value())"));
+    }
+
+    #[test]
+    fn ignored_variant_type_is_commented() {
+        let gir = r#"
+    <record name="Variant" c:type="GVariant">
+    </record>
+    <function name="set_value" c:identifier="test_set_value">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="value" transfer-ownership="none">
+          <type name="Variant" c:type="GVariant*"/>
+        </parameter>
+      </parameters>
+    </function>"#;
+        let objects = r#"
+[[object]]
+name = "Test.Variant"
+status = "ignore""#;
+        let code = test_env::generated_object(gir, "", "*", objects);
+        assert!(code.contains("//pub fn set_value("));
+    }
+
+    #[test]
+    fn time_parameters_and_time_t_are_converted() {
+        let options = format!("{}\ntime_integration = \"chrono\"", OPTIONS);
+        let code = test_env::generated_object(FUNCTIONS, &options, "*", "");
+        let time = "::chrono::DateTime<::chrono::Utc>";
+        assert!(code.contains(&format!(
+            "\npub fn set_deadline(deadline: Option<{}>, mtime: {}) {{",
+            time, time
+        )));
+        assert!(code.contains(
+            "deadline.map(|t| { let (secs, micros) = (t.timestamp(), \
+             i64::from(t.timestamp_subsec_micros())); \
+             glib::DateTime::new_from_unix_utc(secs).add(micros)\
+             .expect(\"time out of the range of DateTime\") }).to_glib_none().0"
+        ));
+        assert!(code.contains("(mtime.timestamp()) as _"));
+        assert!(code.contains(&format!("\npub fn get_mtime() -> {} {{", time)));
+        assert!(code.contains(
+            "{ let secs = i64::from(ffi::test_get_mtime()); \
+             ::chrono::TimeZone::timestamp(&::chrono::Utc, secs, 0) }"
+        ));
+
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains("\npub fn set_deadline(deadline: Option<&glib::DateTime>"));
+        assert!(!code.contains("chrono"));
+    }
+
+    #[test]
+    fn byte_parameters_are_slices() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains("\npub fn write_bytes(bytes: &[u8]) {"));
+        assert!(code.contains("glib::Bytes::from(bytes).to_glib_none().0"));
+        assert!(code.contains("\npub fn write_data(data: &[u8]) {"));
+        assert!(code.contains("data.len()"));
+    }
+
+    #[test]
+    fn bytes_return_follows_transfer() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains("\npub fn peek_bytes() -> Option<Vec<u8>> {"));
+        assert!(code.contains(
+            "Option::<glib::Bytes>::from_glib_none(ffi::test_peek_bytes())\
+             .map(|bytes| bytes.to_vec())"
+        ));
+        assert!(code.contains("\npub fn dup_bytes() -> Option<glib::Bytes> {"));
+        assert!(code.contains("from_glib_full(ffi::test_dup_bytes())"));
+    }
+
+    #[test]
+    fn cancellable_is_optional_is_a() {
+        let options = format!("{}\ngenerate_without_cancellable = true", OPTIONS);
+        let code = test_env::generated_object(FUNCTIONS, &options, "*", "");
+        assert!(code.contains(
+            "\npub fn load<P: IsA<gio::Cancellable>>(cancellable: Option<&P>) {"
+        ));
+        assert!(code.contains("cancellable.to_glib_none().0"));
+        assert!(code.contains("\npub fn load_without_cancellable() {"));
+        assert!(code.contains("ffi::test_load(ptr::null_mut())"));
+    }
+
+    #[test]
+    fn main_context_is_optional() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains("\npub fn iterate(context: Option<&glib::MainContext>) {"));
+        // `None` is passed as NULL, the thread-default main context
+        assert!(code.contains("ffi::test_iterate(context.to_glib_none().0)"));
+    }
+
+    #[test]
+    fn fixed_size_arrays_are_rust_arrays() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains("\npub fn set_key(key: &[u8; 4]) {"));
+        assert!(code.contains("ffi::test_set_key(key.as_ptr() as *mut _)"));
+        assert!(code.contains("\npub fn get_key() -> [u8; 4] {"));
+        assert!(code.contains("let mut key: [u8; 4] = mem::uninitialized();"));
+        assert!(code.contains("ffi::test_get_key(key.as_mut_ptr())"));
+    }
+
+    #[test]
+    fn full_transfer_out_array_is_owned_vec() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains("\npub fn get_items() -> Vec<String> {"));
+        assert!(code.contains("ffi::test_get_items(&mut items, &mut n_items);"));
+        assert!(code.contains("FromGlibContainer::from_glib_full_num(items, n_items as usize)"));
+    }
+
+    #[test]
+    fn size_types_are_casted_at_ffi_boundary() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains("\npub fn resize(size: usize) -> isize {"));
+        assert!(code.contains("ffi::test_resize(size)"));
+
+        let options = format!("{}\nsize_type = \"u64\"", OPTIONS);
+        let code = test_env::generated_object(FUNCTIONS, &options, "*", "");
+        assert!(code.contains("\npub fn resize(size: u64) -> i64 {"));
+        assert!(code.contains("(ffi::test_resize(size as usize) as i64)"));
+    }
+
+    #[test]
+    fn zero_terminated_string_arrays() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains("\npub fn set_names(names: &[&str]) {"));
+        assert!(code.contains("ffi::test_set_names(names.to_glib_none().0)"));
+        assert!(code.contains("\npub fn get_names() -> Vec<String> {"));
+        assert!(code.contains("FromGlibPtrContainer::from_glib_full(ffi::test_get_names())"));
+    }
+
+    #[test]
+    fn lists_are_built_from_slices_per_transfer() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains("\npub fn append_names(names: &[&str]) {"));
+        // The temporary list is freed after the call
+        assert!(code.contains("ffi::test_append_names(names.to_glib_none().0)"));
+        assert!(code.contains("\npub fn set_labels(names: &[&str]) {"));
+        // Only the list is taken over, the elements stay borrowed
+        assert!(code.contains("ffi::test_set_labels(names.to_glib_container().0)"));
+        // The list and copies of the elements are taken over
+        assert!(code.contains("ffi::test_take_names(names.to_glib_full())"));
+    }
+
+    #[test]
+    fn trace_line_behind_feature() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(!code.contains("trace"));
+
+        let options = format!("{}\ntrace_macro = \"log::trace!\"", OPTIONS);
+        let code = test_env::generated_object(FUNCTIONS, &options, "*", "");
+        // The generic cancellable isn't known to implement `Debug`
+        assert!(code.contains(
+            "cancellable: Option<&P>) {
+    #[cfg(feature = \"trace\")]
+    log::trace!(\"test_set_opacity(opacity = {:?})\", opacity);
+"
+        ));
+        assert!(code.contains(
+            "\npub fn show() {
+    #[cfg(feature = \"trace\")]
+    log::trace!(\"test_show()\");
+"
+        ));
+    }
+
+    #[test]
+    fn object_array_returns_follow_transfer() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        let conversions = [
+            // The array and the references of its elements are owned
+            ("dup_widgets", "full"),
+            // Only the array is owned, the elements get a new reference
+            ("get_widgets", "container"),
+            ("peek_widgets", "none"),
+        ];
+        for &(name, transfer) in &conversions {
+            assert!(code.contains(&format!("\npub fn {}() -> Vec<Widget> {{", name)));
+            assert!(code.contains(&format!(
+                "FromGlibContainer::from_glib_{}_num(ffi::test_{}(&mut n_widgets), \
+                 n_widgets as usize)",
+                transfer, name
+            )));
+        }
+    }
+
+    #[test]
+    fn false_return_is_bool_error() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains("\npub fn save() -> bool {"));
+
+        let objects = r#"
+    [[object.function]]
+    name = "save"
+        [object.function.return]
+        bool_return_is_error = "Failed to save""#;
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", objects);
+        assert!(code.contains("\npub fn save() -> Result<(), glib::error::BoolError> {"));
+        assert!(code.contains(
+            "glib::error::BoolError::from_glib(ffi::test_save(), \"Failed to save\")"
+        ));
+    }
+
+    #[test]
+    fn floating_return_is_sunk() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains("\npub fn create_widget() -> Option<Widget> {"));
+        assert!(code.contains("from_glib_none(ffi::test_create_widget())"));
+        // Only returns annotated as floating are sunk
+        assert!(code.contains("\npub fn dup_widget() -> Option<Widget> {"));
+        assert!(code.contains("from_glib_full(ffi::test_dup_widget())"));
+    }
+
+    #[test]
+    fn nullable_throwing_return_stays_optional() {
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", "");
+        assert!(code.contains("\npub fn lookup() -> Result<Option<Widget>, Error> {"));
+        // `Ok(Some(_))`, `Ok(None)` for a NULL without an error, or `Err(_)`
+        assert!(code.contains("let ret = ffi::test_lookup(&mut error);"));
+        assert!(code.contains(
+            "if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }"
+        ));
+        assert!(code.contains("\npub fn find() -> Result<Widget, Error> {"));
+
+        let objects = r#"
+    [[object.function]]
+    name = "find"
+        [object.function.return]
+        nullable = true"#;
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", objects);
+        assert!(code.contains("\npub fn find() -> Result<Option<Widget>, Error> {"));
+    }
+
+    #[test]
+    fn throwing_function_returns_error_type() {
+        let objects = r#"
+    [[object.function]]
+    name = "find"
+    error_type = "FileError""#;
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", objects);
+        assert!(code.contains("use FileError;"));
+        assert!(code.contains("\npub fn find() -> Result<Widget, FileError> {"));
+        assert!(code.contains(
+            "let error: Error = from_glib_full(error); \
+             Err(error.kind::<FileError>().unwrap_or(FileError::Failed))"
+        ));
+
+        // Without a `failed` member errors of other domains can't be returned
+        let objects = r#"
+    [[object.function]]
+    name = "find"
+    error_type = "Widget""#;
+        let code = test_env::generated_object(FUNCTIONS, OPTIONS, "*", objects);
+        assert!(code.contains("\npub fn find() -> Result<Widget, Error> {"));
+    }
+}
@@ -1,14 +1,16 @@
 use analysis::conversion_type::ConversionType;
 use analysis::function_parameters::CParameter as AnalysisCParameter;
-use analysis::function_parameters::{Transformation, TransformationType};
+use analysis::function_parameters::{is_fixed_array, Transformation, TransformationType};
 use analysis::out_parameters::Mode;
 use analysis::return_value;
 use analysis::rust_type::rust_type;
 use analysis::safety_assertion_mode::SafetyAssertionMode;
 use chunk::{Chunk, TupleMode};
 use chunk::parameter_ffi_call_out;
+use codegen::translate_to_glib::int_cast;
 use env::Env;
 use library;
+use traits::IntoString;
 
 #[derive(Clone)]
 enum Parameter {
@@ -21,10 +23,11 @@ enum Parameter {
     },
 }
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 enum OutMemMode {
     Uninitialized,
     UninitializedNamed(String),
+    UninitializedFixedArray(String),
     NullPtr,
     NullMutPtr,
 }
@@ -44,6 +47,8 @@ pub struct Builder {
     ret: ReturnValue,
     outs_as_return: bool,
     outs_mode: Mode,
+    error_type: Option<String>,
+    chain: bool,
     assertion: SafetyAssertionMode,
 }
 
@@ -80,15 +85,14 @@ impl Builder {
                         if fund == library::Fundamental::Utf8
                             || fund == library::Fundamental::Filename =>
                     {
-                        if parameter.transfer == library::Transfer::Full {
-                            NullMutPtr
-                        } else {
-                            NullPtr
-                        }
+                        string_out_mem_mode(parameter.transfer)
                     }
                     _ => NullMutPtr,
                 }
             },
+            ConversionType::Direct if is_fixed_array(env, parameter.typ) => {
+                UninitializedFixedArray(rust_type(env, parameter.typ).into_string())
+            }
             _ => Uninitialized,
         };
         self.parameters.push(Parameter::Out {
@@ -108,6 +112,14 @@ impl Builder {
         self.outs_mode = mode;
         self
     }
+    pub fn error_type(&mut self, error_type: Option<&String>) -> &mut Builder {
+        self.error_type = error_type.cloned();
+        self
+    }
+    pub fn chain(&mut self, chain: bool) -> &mut Builder {
+        self.chain = chain;
+        self
+    }
     pub fn generate(&self) -> Chunk {
         let mut body = Vec::new();
 
@@ -132,6 +144,9 @@ impl Builder {
         self.add_in_array_lengths(&mut chunks);
         self.add_assertion(&mut chunks);
         chunks.push(unsafe_);
+        if self.chain {
+            chunks.push(Chunk::Custom("self".into()));
+        }
         Chunk::BlockHalf(chunks)
     }
     fn add_assertion(&self, chunks: &mut Vec<Chunk>) {
@@ -176,11 +191,15 @@ impl Builder {
                 ref array_name,
                 ref array_length_name,
                 ref array_length_type,
+                checked,
             } = trans.transformation_type
             {
                 if let In = self.parameters[trans.ind_c] {
-                    let value =
-                        Chunk::Custom(format!("{}.len() as {}", array_name, array_length_type));
+                    let value = Chunk::Custom(int_cast(
+                        &format!("{}.len()", array_name),
+                        array_length_type,
+                        checked,
+                    ));
                     chunks.push(Chunk::Let {
                         name: array_length_name.clone(),
                         is_mut: false,
@@ -218,6 +237,10 @@ impl Builder {
                 In => Chunk::FfiCallParameter {
                     transformation_type: trans.transformation_type.clone(),
                 },
+                Out {
+                    ref parameter,
+                    mem_mode: OutMemMode::UninitializedFixedArray(_),
+                } => Chunk::Custom(format!("{}.as_mut_ptr()", parameter.name)),
                 Out { ref parameter, .. } => Chunk::FfiCallOutParameter {
                     par: parameter.clone(),
                 },
@@ -259,11 +282,16 @@ impl Builder {
             } = *par
             {
                 let val = self.get_uninitialized(mem_mode);
+                let type_ = if let OutMemMode::UninitializedFixedArray(ref type_) = *mem_mode {
+                    Some(Box::new(Chunk::Custom(type_.clone())))
+                } else {
+                    None
+                };
                 let chunk = Chunk::Let {
                     name: parameter.name.clone(),
                     is_mut: true,
                     value: Box::new(val),
-                    type_: None,
+                    type_: type_,
                 };
                 v.push(chunk);
             }
@@ -272,7 +300,7 @@ impl Builder {
     fn get_uninitialized(&self, mem_mode: &OutMemMode) -> Chunk {
         use self::OutMemMode::*;
         match *mem_mode {
-            Uninitialized => Chunk::Uninitialized,
+            Uninitialized | UninitializedFixedArray(_) => Chunk::Uninitialized,
             UninitializedNamed(ref name) => Chunk::UninitializedNamed { name: name.clone() },
             NullPtr => Chunk::NullPtr,
             NullMutPtr => Chunk::NullMutPtr,
@@ -403,6 +431,7 @@ impl Builder {
                 }
                 ret = Chunk::ErrorResultReturn {
                     value: Box::new(ret),
+                    error_type: self.error_type.clone(),
                 };
                 (call, Some(ret))
             }
@@ -431,3 +460,37 @@ impl Builder {
             .next()
     }
 }
+
+/// Owned string outs are freed by the caller, borrowed ones are only read.
+fn string_out_mem_mode(transfer: library::Transfer) -> OutMemMode {
+    if transfer == library::Transfer::Full {
+        OutMemMode::NullMutPtr
+    } else {
+        OutMemMode::NullPtr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use library::Transfer;
+
+    #[test]
+    fn string_out_mem_mode_depends_on_transfer() {
+        assert_eq!(string_out_mem_mode(Transfer::None), OutMemMode::NullPtr);
+        assert_eq!(string_out_mem_mode(Transfer::Full), OutMemMode::NullMutPtr);
+    }
+
+    #[test]
+    fn chained_body_returns_self() {
+        let mut builder = Builder::new();
+        builder.glib_name("gtk_list_store_append_value").chain(true);
+        match builder.generate() {
+            Chunk::BlockHalf(ref chunks) => match chunks.last() {
+                Some(&Chunk::Custom(ref s)) => assert_eq!(s, "self"),
+                _ => panic!("Chained body doesn't end with self"),
+            },
+            _ => panic!("Body isn't a block"),
+        }
+    }
+}
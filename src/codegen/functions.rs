@@ -1,8 +1,10 @@
+use std::io::{Result, Write};
 use std::path::Path;
 
+use analysis::init_function::{Info as InitInfo, Kind as InitKind};
 use env::Env;
 use file_saver;
-use codegen::general;
+use codegen::general::{self, version_condition, version_condition_string};
 use codegen::function;
 
 pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
@@ -12,6 +14,7 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
         Some(ref functions) => functions,
         None => return,
     };
+    let init = env.analysis.init_function.as_ref();
 
     let path = root_path.join("functions.rs");
     file_saver::save_to_file(path, env.config.make_backup, |w| {
@@ -23,9 +26,148 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
         mod_rs.push("\npub mod functions;".into());
 
         for func_analysis in &functions.functions {
+            // Replaced by the wrapper passing the process arguments
+            if let Some(init) = init {
+                if init.kind == InitKind::Argv && func_analysis.name == init.name {
+                    continue;
+                }
+            }
             try!(function::generate(w, env, func_analysis, false, false, 0));
         }
 
+        if let Some(init) = init {
+            if init.kind == InitKind::Argv {
+                try!(writeln!(w, ""));
+                try!(version_condition(w, env, init.version, false, 0));
+                try!(generate_argv_init(w, init));
+            }
+            if let Some(cfg) = version_condition_string(env, init.version, false, 0) {
+                mod_rs.push(cfg);
+            }
+            mod_rs.push(init_reexport(&init.name));
+        }
+
         Ok(())
     });
 }
+
+fn init_reexport(name: &str) -> String {
+    format!("pub use self::functions::{};", name)
+}
+
+fn generate_argv_init(w: &mut Write, init: &InitInfo) -> Result<()> {
+    try!(writeln!(w, "pub fn {}() {{", init.name));
+    try!(writeln!(w, "\t#[cfg(unix)]"));
+    try!(writeln!(
+        w,
+        "\tlet args: Vec<CString> = env::args_os().map(|arg| CString::new(arg.as_bytes()).unwrap()).collect();"
+    ));
+    try!(writeln!(w, "\t#[cfg(not(unix))]"));
+    try!(writeln!(
+        w,
+        "\tlet args: Vec<CString> = env::args_os().map(|arg| CString::new(arg.to_string_lossy().as_bytes()).unwrap()).collect();"
+    ));
+    try!(writeln!(
+        w,
+        "\tlet mut argv: Vec<*mut _> = args.iter().map(|arg| arg.as_ptr() as *mut _).collect();"
+    ));
+    // `argv[argc]` is NULL as for `main`
+    try!(writeln!(w, "\targv.push(ptr::null_mut());"));
+    try!(writeln!(w, "\tlet mut argc = args.len() as _;"));
+    try!(writeln!(w, "\tlet mut argv_ptr = argv.as_mut_ptr();"));
+    try!(writeln!(w, "\tunsafe {{"));
+    try!(writeln!(
+        w,
+        "\t\tffi::{}(&mut argc, &mut argv_ptr);",
+        init.glib_name
+    ));
+    try!(writeln!(w, "\t}}"));
+    try!(writeln!(w, "}}"));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_env;
+
+    fn argv_init() -> InitInfo {
+        InitInfo {
+            name: "init".into(),
+            glib_name: "gtk_init".into(),
+            kind: InitKind::Argv,
+            version: None,
+        }
+    }
+
+    #[test]
+    fn init_function_is_reexported_at_root() {
+        assert_eq!(init_reexport("init"), "pub use self::functions::init;");
+    }
+
+    #[test]
+    fn argv_init_wrapper_takes_no_parameters() {
+        let mut w = Vec::new();
+        generate_argv_init(&mut w, &argv_init()).unwrap();
+        let code = String::from_utf8(w).unwrap();
+        assert!(code.starts_with("pub fn init() {\n"));
+        assert!(code.contains("\t#[cfg(unix)]\n\tlet args: Vec<CString> = env::args_os()"));
+        assert!(code.contains("CString::new(arg.as_bytes())"));
+        assert!(!code.contains("env::args()"));
+        assert!(code.contains("\t\tffi::gtk_init(&mut argc, &mut argv_ptr);\n"));
+    }
+
+    #[test]
+    fn argv_init_wrapper_terminates_argv() {
+        let mut w = Vec::new();
+        generate_argv_init(&mut w, &argv_init()).unwrap();
+        let code = String::from_utf8(w).unwrap();
+        assert!(code.contains("\targv.push(ptr::null_mut());\n\tlet mut argc = args.len() as _;\n"));
+    }
+
+    const INIT: &'static str = r#"
+    <function name="init" c:identifier="test_init">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="argc" direction="inout" caller-allocates="0"
+                   transfer-ownership="full">
+          <type name="gint" c:type="int*"/>
+        </parameter>
+        <parameter name="argv" direction="inout" caller-allocates="0"
+                   transfer-ownership="full" nullable="1">
+          <array length="0" c:type="char***">
+            <type name="utf8" c:type="char*"/>
+          </array>
+        </parameter>
+      </parameters>
+    </function>"#;
+
+    const INIT_FUNCTION: &'static str = "init_function = \"init\"";
+
+    #[test]
+    fn argv_init_is_wrapped_at_root() {
+        let code = test_env::generated_object(INIT, INIT_FUNCTION, "*", "");
+        assert!(code.contains("pub fn init() {"));
+        assert!(code.contains("#[cfg(unix)]\nuse std::os::unix::ffi::OsStrExt;"));
+        assert!(code.contains("use std::ptr;"));
+        let objects = "[[object]]\nname = \"Test.*\"\nstatus = \"generate\"";
+        let mod_rs = test_env::generated_file(INIT, INIT_FUNCTION, objects, "mod.rs");
+        assert!(mod_rs.contains("pub use self::functions::init;\n"));
+    }
+
+    #[test]
+    fn ignored_init_is_not_wrapped() {
+        let ignore = r#"
+[[object.function]]
+name = "init"
+ignore = true"#;
+        let code = test_env::generated_object(INIT, INIT_FUNCTION, "*", ignore);
+        assert!(!code.contains("fn init("));
+        assert!(!code.contains("test_init"));
+        let objects = format!("[[object]]\nname = \"Test.*\"\nstatus = \"generate\"{}", ignore);
+        let mod_rs = test_env::generated_file(INIT, INIT_FUNCTION, &objects, "mod.rs");
+        assert!(!mod_rs.contains("pub use self::functions::init;"));
+    }
+}
@@ -5,7 +5,7 @@ use analysis;
 use analysis::general::StatusedTypeId;
 use analysis::imports::Imports;
 use analysis::namespaces;
-use config::Config;
+use config::{Config, VersionGating};
 use env::Env;
 use gir_version::VERSION;
 use version::Version;
@@ -25,8 +25,9 @@ pub fn start_comments(w: &mut Write, conf: &Config) -> Result<()> {
 
 pub fn uses(w: &mut Write, env: &Env, imports: &Imports) -> Result<()> {
     try!(writeln!(w, ""));
-    for (name, &version) in imports.iter() {
-        try!(version_condition(w, env, version, false, 0));
+    for (name, conditions) in imports.iter() {
+        try!(cfg_condition(w, &conditions.constraint, false, 0));
+        try!(version_condition(w, env, conditions.version, false, 0));
         if env.namespaces.glib_ns_id == namespaces::MAIN && name == "glib_ffi" {
             try!(writeln!(w, "use ffi as {};", name));
         } else {
@@ -37,6 +38,8 @@ pub fn uses(w: &mut Write, env: &Env, imports: &Imports) -> Result<()> {
     Ok(())
 }
 
+/// Wraps an object type, the `Object` form of `glib_wrapper!` implements `Clone` by
+/// adding a reference so no `Clone` implementation is generated here.
 pub fn define_object_type(
     w: &mut Write,
     env: &Env,
@@ -46,6 +49,7 @@ pub fn define_object_type(
     glib_func_name: &str,
     parents: &[StatusedTypeId],
 ) -> Result<()> {
+    // Supertypes include the implemented interfaces, which are listed like parent classes
     let mut external_parents = false;
     let parents: Vec<String> = parents
         .iter()
@@ -54,15 +58,36 @@ pub fn define_object_type(
             p.name.clone()
         } else {
             external_parents = true;
-            format!(
-                "{krate}::{name} => {krate}_ffi::{ffi_name}",
-                krate = env.namespaces[p.type_id.ns_id].crate_name,
-                name = p.name,
-                ffi_name = env.library.type_(p.type_id).get_glib_name().unwrap()
+            let ns = &env.namespaces[p.type_id.ns_id];
+            external_parent(
+                &ns.crate_name,
+                &p.name,
+                &ns.ffi_crate_name,
+                env.library.type_(p.type_id).get_glib_name().unwrap(),
             )
         })
         .collect();
 
+    write_object_type(
+        w,
+        type_name,
+        glib_name,
+        glib_class_name,
+        glib_func_name,
+        &parents,
+        external_parents,
+    )
+}
+
+fn write_object_type(
+    w: &mut Write,
+    type_name: &str,
+    glib_name: &str,
+    glib_class_name: &Option<&str>,
+    glib_func_name: &str,
+    parents: &[String],
+    external_parents: bool,
+) -> Result<()> {
     let (separator, class_name) = {
         if let &Some(s) = glib_class_name {
             (", ".to_string(), format!("ffi::{}", s))
@@ -73,6 +98,7 @@ pub fn define_object_type(
 
     try!(writeln!(w, ""));
     try!(writeln!(w, "glib_wrapper! {{"));
+    try!(doc_alias(w, glib_name, "", 1));
     if parents.is_empty() {
         try!(writeln!(
             w,
@@ -115,6 +141,11 @@ pub fn define_object_type(
     Ok(())
 }
 
+/// Parents from other crates are given with the path to their FFI type.
+fn external_parent(krate: &str, name: &str, ffi_krate: &str, ffi_name: &str) -> String {
+    format!("{}::{} => {}::{}", krate, name, ffi_krate, ffi_name)
+}
+
 pub fn define_boxed_type(
     w: &mut Write,
     type_name: &str,
@@ -125,6 +156,7 @@ pub fn define_boxed_type(
 ) -> Result<()> {
     try!(writeln!(w, ""));
     try!(writeln!(w, "glib_wrapper! {{"));
+    try!(doc_alias(w, glib_name, "", 1));
     try!(writeln!(
         w,
         "\tpub struct {}(Boxed<ffi::{}>);",
@@ -148,6 +180,323 @@ pub fn define_boxed_type(
     Ok(())
 }
 
+pub fn define_auto_boxed_type(
+    w: &mut Write,
+    type_name: &str,
+    glib_name: &str,
+    get_type_fn: &str,
+) -> Result<()> {
+    try!(writeln!(w, ""));
+    try!(writeln!(w, "glib_wrapper! {{"));
+    try!(doc_alias(w, glib_name, "", 1));
+    try!(writeln!(
+        w,
+        "\tpub struct {}(Boxed<ffi::{}>);",
+        type_name,
+        glib_name
+    ));
+    try!(writeln!(w, ""));
+    try!(writeln!(w, "\tmatch fn {{"));
+    try!(writeln!(
+        w,
+        "\t\tcopy => |ptr| gobject_ffi::g_boxed_copy(ffi::{}(), ptr as *mut _) as *mut ffi::{},",
+        get_type_fn,
+        glib_name
+    ));
+    try!(writeln!(
+        w,
+        "\t\tfree => |ptr| gobject_ffi::g_boxed_free(ffi::{}(), ptr as *mut _),",
+        get_type_fn
+    ));
+    try!(writeln!(w, "\t\tget_type => || ffi::{}(),", get_type_fn));
+    try!(writeln!(w, "\t}}"));
+    try!(writeln!(w, "}}"));
+
+    Ok(())
+}
+
+pub fn define_fundamental_type(
+    w: &mut Write,
+    type_name: &str,
+    glib_name: &str,
+    ref_fn: &str,
+    unref_fn: &str,
+    get_type_fn: &str,
+) -> Result<()> {
+    try!(writeln!(w, ""));
+    try!(writeln!(w, "glib_wrapper! {{"));
+    try!(doc_alias(w, glib_name, "", 1));
+    try!(writeln!(
+        w,
+        "\tpub struct {}(Fundamental<ffi::{}>);",
+        type_name,
+        glib_name
+    ));
+    try!(writeln!(w, ""));
+    try!(writeln!(w, "\tmatch fn {{"));
+    try!(writeln!(w, "\t\tref => |ptr| ffi::{}(ptr),", ref_fn));
+    try!(writeln!(w, "\t\tunref => |ptr| ffi::{}(ptr),", unref_fn));
+    try!(writeln!(w, "\t\tget_type => || ffi::{}(),", get_type_fn));
+    try!(writeln!(w, "\t}}"));
+    try!(writeln!(w, "}}"));
+
+    Ok(())
+}
+
+/// Wraps a plain old data boxed type by value, the GType is only used for `Value`s.
+pub fn define_boxed_inline_type(
+    w: &mut Write,
+    type_name: &str,
+    glib_name: &str,
+    get_type_fn: &str,
+    extra_derives: &[String],
+) -> Result<()> {
+    try!(writeln!(w, ""));
+    try!(write_derives(w, &merge_derives(&["Clone", "Copy"], extra_derives), 0));
+    try!(writeln!(
+        w,
+        "#[repr(C)]
+#[doc(alias = \"{glib_name}\")]
+pub struct {name}(ffi::{glib_name});
+
+#[doc(hidden)]
+impl Uninitialized for {name} {{
+    #[inline]
+    unsafe fn uninitialized() -> Self {{
+        mem::zeroed()
+    }}
+}}
+
+#[doc(hidden)]
+impl<'a> ToGlibPtr<'a, *const ffi::{glib_name}> for {name} {{
+    type Storage = &'a Self;
+
+    #[inline]
+    fn to_glib_none(&'a self) -> Stash<'a, *const ffi::{glib_name}, Self> {{
+        Stash(&self.0, self)
+    }}
+}}
+
+#[doc(hidden)]
+impl<'a> ToGlibPtr<'a, *mut ffi::{glib_name}> for {name} {{
+    type Storage = &'a Self;
+
+    #[inline]
+    fn to_glib_none(&'a self) -> Stash<'a, *mut ffi::{glib_name}, Self> {{
+        Stash(&self.0 as *const _ as *mut _, self)
+    }}
+}}
+
+#[doc(hidden)]
+impl<'a> ToGlibPtrMut<'a, *mut ffi::{glib_name}> for {name} {{
+    type Storage = &'a mut Self;
+
+    #[inline]
+    fn to_glib_none_mut(&'a mut self) -> StashMut<'a, *mut ffi::{glib_name}, Self> {{
+        let ptr = &mut self.0 as *mut _;
+        StashMut(ptr, self)
+    }}
+}}
+
+#[doc(hidden)]
+impl FromGlibPtrNone<*const ffi::{glib_name}> for {name} {{
+    #[inline]
+    unsafe fn from_glib_none(ptr: *const ffi::{glib_name}) -> Self {{
+        assert!(!ptr.is_null());
+        {name}(*ptr)
+    }}
+}}
+
+#[doc(hidden)]
+impl FromGlibPtrNone<*mut ffi::{glib_name}> for {name} {{
+    #[inline]
+    unsafe fn from_glib_none(ptr: *mut ffi::{glib_name}) -> Self {{
+        from_glib_none(ptr as *const _)
+    }}
+}}
+
+#[doc(hidden)]
+impl FromGlibPtrFull<*mut ffi::{glib_name}> for {name} {{
+    #[inline]
+    unsafe fn from_glib_full(ptr: *mut ffi::{glib_name}) -> Self {{
+        let res = from_glib_none(ptr as *const _);
+        gobject_ffi::g_boxed_free(ffi::{get_type}(), ptr as *mut _);
+        res
+    }}
+}}
+
+impl StaticType for {name} {{
+    fn static_type() -> Type {{
+        unsafe {{ from_glib(ffi::{get_type}()) }}
+    }}
+}}
+
+impl<'a> FromValueOptional<'a> for {name} {{
+    unsafe fn from_value_optional(value: &Value) -> Option<Self> {{
+        from_glib_none(gobject_ffi::g_value_get_boxed(value.to_glib_none().0) as *const ffi::{glib_name})
+    }}
+}}
+
+impl<'a> FromValue<'a> for {name} {{
+    unsafe fn from_value(value: &Value) -> Self {{
+        from_glib_none(gobject_ffi::g_value_get_boxed(value.to_glib_none().0) as *const ffi::{glib_name})
+    }}
+}}
+
+impl SetValue for {name} {{
+    unsafe fn set_value(value: &mut Value, this: &Self) {{
+        let ptr: *const ffi::{glib_name} = this.to_glib_none().0;
+        gobject_ffi::g_value_set_boxed(value.to_glib_none_mut().0, ptr as glib_ffi::gconstpointer)
+    }}
+}}",
+        name = type_name,
+        glib_name = glib_name,
+        get_type = get_type_fn
+    ));
+
+    Ok(())
+}
+
+/// Lets the Rust side hand its reference over to C, e.g. for `transfer full` arguments.
+pub fn define_into_glib_ptr(w: &mut Write, type_name: &str, glib_name: &str) -> Result<()> {
+    try!(writeln!(w, ""));
+    try!(writeln!(w, "impl {} {{", type_name));
+    try!(writeln!(
+        w,
+        "\t/// Transfers the ownership of the underlying `{}` to the caller.",
+        glib_name
+    ));
+    try!(writeln!(
+        w,
+        "\tpub unsafe fn into_glib_ptr(self) -> *mut ffi::{} {{",
+        glib_name
+    ));
+    // `to_glib_full()` would add a reference or copy the boxed value
+    try!(writeln!(w, "\t\tlet ptr = mut_override(self.to_glib_none().0);"));
+    try!(writeln!(w, "\t\tmem::forget(self);"));
+    try!(writeln!(w, "\t\tptr"));
+    try!(writeln!(w, "\t}}"));
+    try!(writeln!(w, "}}"));
+
+    Ok(())
+}
+
+/// Complements glib's `From<&T> for Value` of types with a GType, so owned
+/// values can be converted with `.into()`.
+pub fn value_from_impl(type_name: &str) -> String {
+    format!(
+        "impl From<{name}> for Value {{
+    #[inline]
+    fn from(v: {name}) -> Value {{
+        Value::from(&v)
+    }}
+}}",
+        name = type_name
+    )
+}
+
+/// Raw pointer access for interop with code the bindings don't cover.
+/// `ptr_mut` is the pointer mutability of the wrapper's storage, e.g. `const` for boxed types.
+pub fn define_ptr_accessors(
+    w: &mut Write,
+    type_name: &str,
+    glib_name: &str,
+    ptr_mut: &str,
+) -> Result<()> {
+    try!(writeln!(w, ""));
+    try!(writeln!(w, "impl {} {{", type_name));
+    try!(writeln!(
+        w,
+        "\t/// Returns the underlying `{}`, that is only valid while `self` is alive.",
+        glib_name
+    ));
+    try!(writeln!(w, "\tpub fn as_ptr(&self) -> *mut ffi::{} {{", glib_name));
+    try!(writeln!(
+        w,
+        "\t\tlet ptr: *{} ffi::{} = self.to_glib_none().0;",
+        ptr_mut,
+        glib_name
+    ));
+    if ptr_mut == "mut" {
+        try!(writeln!(w, "\t\tptr"));
+    } else {
+        try!(writeln!(w, "\t\tptr as *mut _"));
+    }
+    try!(writeln!(w, "\t}}"));
+    try!(writeln!(w, ""));
+    try!(writeln!(
+        w,
+        "\t/// Wraps a borrowed `{}`, taking a new reference or copy of it.",
+        glib_name
+    ));
+    try!(writeln!(
+        w,
+        "\tpub unsafe fn from_ptr(ptr: *mut ffi::{}) -> Self {{",
+        glib_name
+    ));
+    try!(writeln!(w, "\t\tfrom_glib_none(ptr)"));
+    try!(writeln!(w, "\t}}"));
+    try!(writeln!(w, "}}"));
+
+    Ok(())
+}
+
+/// Catches wrappers of manual interop code dropped with a null or misaligned pointer
+/// in debug builds. The `Drop` impl exists in all builds, so the wrapper can be used
+/// the same way in both, while the checks compile out of release builds. The field's
+/// own `Drop` frees the pointer afterwards.
+pub fn define_drop_assert(
+    w: &mut Write,
+    type_name: &str,
+    glib_name: &str,
+    ptr_mut: &str,
+) -> Result<()> {
+    try!(writeln!(w, ""));
+    try!(writeln!(w, "impl Drop for {} {{", type_name));
+    try!(writeln!(w, "\tfn drop(&mut self) {{"));
+    try!(writeln!(w, "\t\t#[cfg(debug_assertions)]"));
+    try!(writeln!(w, "\t\t{{"));
+    try!(writeln!(
+        w,
+        "\t\t\tlet ptr: *{} ffi::{} = self.to_glib_none().0;",
+        ptr_mut,
+        glib_name
+    ));
+    try!(writeln!(
+        w,
+        "\t\t\tassert!(!ptr.is_null(), \"Dropping {} with a null pointer\");",
+        type_name
+    ));
+    try!(writeln!(
+        w,
+        "\t\t\tassert_eq!(ptr as usize % ::std::mem::align_of::<ffi::{}>(), 0, \
+         \"Dropping {} with a misaligned pointer\");",
+        glib_name,
+        type_name
+    ));
+    try!(writeln!(w, "\t\t}}"));
+    try!(writeln!(w, "\t}}"));
+    try!(writeln!(w, "}}"));
+
+    Ok(())
+}
+
+/// Delegates to glib's weak references, e.g. for breaking reference cycles.
+pub fn define_downgrade(w: &mut Write, type_name: &str) -> Result<()> {
+    try!(writeln!(w, ""));
+    try!(writeln!(w, "impl {} {{", type_name));
+    try!(writeln!(
+        w,
+        "\t/// Creates a weak reference, that doesn't keep the object alive."
+    ));
+    try!(writeln!(w, "\tpub fn downgrade(&self) -> WeakRef<Self> {{"));
+    try!(writeln!(w, "\t\tObjectExt::downgrade(self)"));
+    try!(writeln!(w, "\t}}"));
+    try!(writeln!(w, "}}"));
+
+    Ok(())
+}
+
 pub fn define_shared_type(
     w: &mut Write,
     type_name: &str,
@@ -158,6 +507,7 @@ pub fn define_shared_type(
 ) -> Result<()> {
     try!(writeln!(w, ""));
     try!(writeln!(w, "glib_wrapper! {{"));
+    try!(doc_alias(w, glib_name, "", 1));
     try!(writeln!(
         w,
         "\tpub struct {}(Shared<ffi::{}>);",
@@ -197,21 +547,141 @@ pub fn version_condition_string(
     indent: usize,
 ) -> Option<String> {
     match version {
-        Some(v) if v > env.config.min_cfg_version => {
-            let comment = if commented { "//" } else { "" };
-            Some(format!(
-                "{}{}#[cfg(any({}, feature = \"dox\"))]",
-                tabs(indent),
-                comment,
-                v.to_cfg()
-            ))
-        }
+        Some(v) if v > env.config.min_cfg_version => Some(version_cfg_attrs(
+            v,
+            env.config.version_gating,
+            env.config.emit_doc_cfg,
+            commented,
+            indent,
+        )),
+        _ => None,
+    }
+}
+
+/// Like `version_condition`, but without `doc(cfg)`, for match arms and other places that
+/// aren't documented.
+pub fn version_condition_no_doc(
+    w: &mut Write,
+    env: &Env,
+    version: Option<Version>,
+    commented: bool,
+    indent: usize,
+) -> Result<()> {
+    if let Some(s) = version_condition_no_doc_string(env, version, commented, indent) {
+        try!(writeln!(w, "{}", s));
+    }
+    Ok(())
+}
+
+pub fn version_condition_no_doc_string(
+    env: &Env,
+    version: Option<Version>,
+    commented: bool,
+    indent: usize,
+) -> Option<String> {
+    match version {
+        Some(v) if v > env.config.min_cfg_version => Some(version_cfg_attrs(
+            v,
+            env.config.version_gating,
+            false,
+            commented,
+            indent,
+        )),
         _ => None,
     }
 }
 
+/// Gates an item available from `version` until it's removed in `removed_version`.
+pub fn version_window_condition(
+    w: &mut Write,
+    env: &Env,
+    version: Option<Version>,
+    removed_version: Option<Version>,
+    commented: bool,
+    indent: usize,
+) -> Result<()> {
+    let removed_version = match removed_version {
+        Some(removed_version) => removed_version,
+        None => return version_condition(w, env, version, commented, indent),
+    };
+    let since = match version {
+        Some(v) if v > env.config.min_cfg_version => Some(v),
+        _ => None,
+    };
+    writeln!(
+        w,
+        "{}",
+        window_cfg_attrs(
+            since,
+            removed_version,
+            env.config.version_gating,
+            env.config.emit_doc_cfg,
+            commented,
+            indent,
+        )
+    )
+}
+
+fn window_cfg_attrs(
+    since: Option<Version>,
+    removed_version: Version,
+    gating: VersionGating,
+    emit_doc_cfg: bool,
+    commented: bool,
+    indent: usize,
+) -> String {
+    let comment = if commented { "//" } else { "" };
+    let window = removed_version.to_window_cfg(since);
+    let cfg = format!("{}{}#[cfg({})]", tabs(indent), comment, gated(&window, gating));
+    if !emit_doc_cfg {
+        return cfg;
+    }
+    format!(
+        "{}\n{}{}#[cfg_attr(feature = \"dox\", doc(cfg({})))]",
+        cfg,
+        tabs(indent),
+        comment,
+        window
+    )
+}
+
+fn version_cfg_attrs(
+    version: Version,
+    gating: VersionGating,
+    emit_doc_cfg: bool,
+    commented: bool,
+    indent: usize,
+) -> String {
+    let comment = if commented { "//" } else { "" };
+    let cfg = format!(
+        "{}{}#[cfg({})]",
+        tabs(indent),
+        comment,
+        gated(&version.to_cfg(), gating)
+    );
+    if !emit_doc_cfg {
+        return cfg;
+    }
+    format!(
+        "{}\n{}{}#[cfg_attr(feature = \"dox\", doc(cfg({})))]",
+        cfg,
+        tabs(indent),
+        comment,
+        version.to_cfg()
+    )
+}
+
+/// Makes a version condition hold in the docs, unless gated by features only.
+pub fn gated(condition: &str, gating: VersionGating) -> String {
+    match gating {
+        VersionGating::Dox => format!("any({}, feature = \"dox\")", condition),
+        VersionGating::Features => condition.to_owned(),
+    }
+}
+
 pub fn not_version_condition(
     w: &mut Write,
+    env: &Env,
     version: Option<Version>,
     commented: bool,
     indent: usize,
@@ -219,16 +689,42 @@ pub fn not_version_condition(
     if let Some(v) = version {
         let comment = if commented { "//" } else { "" };
         let s = format!(
-            "{}{}#[cfg(any(not({}), feature = \"dox\"))]",
+            "{}{}#[cfg({})]",
             tabs(indent),
             comment,
-            v.to_cfg()
+            gated(&format!("not({})", v.to_cfg()), env.config.version_gating)
         );
         try!(writeln!(w, "{}", s));
     }
     Ok(())
 }
 
+/// Gates a function replaced by another signature in `version`, as the exact
+/// negation of the condition of the newer signature.
+pub fn replaced_version_condition(
+    w: &mut Write,
+    env: &Env,
+    version: Option<Version>,
+    commented: bool,
+    indent: usize,
+) -> Result<()> {
+    if let Some(v) = version {
+        let comment = if commented { "//" } else { "" };
+        try!(writeln!(
+            w,
+            "{}{}{}",
+            tabs(indent),
+            comment,
+            replaced_version_cfg(v, env.config.version_gating)
+        ));
+    }
+    Ok(())
+}
+
+fn replaced_version_cfg(version: Version, gating: VersionGating) -> String {
+    format!("#[cfg(not({}))]", gated(&version.to_cfg(), gating))
+}
+
 pub fn cfg_condition(
     w: &mut Write,
     cfg_condition: &Option<String>,
@@ -261,6 +757,42 @@ pub fn cfg_condition_string(
     }
 }
 
+/// The configured `cfg_condition` of a type or function followed by its version cfg.
+pub fn cfg_and_version_condition(
+    w: &mut Write,
+    env: &Env,
+    cfg_condition: &Option<String>,
+    version: Option<Version>,
+    commented: bool,
+    indent: usize,
+) -> Result<()> {
+    let s = cfg_and_version_condition_string(env, cfg_condition, version, commented, indent);
+    if let Some(s) = s {
+        try!(writeln!(w, "{}", s));
+    }
+    Ok(())
+}
+
+pub fn cfg_and_version_condition_string(
+    env: &Env,
+    cfg_condition: &Option<String>,
+    version: Option<Version>,
+    commented: bool,
+    indent: usize,
+) -> Option<String> {
+    join_conditions(
+        cfg_condition_string(cfg_condition, commented, indent),
+        version_condition_string(env, version, commented, indent),
+    )
+}
+
+fn join_conditions(cfg_condition: Option<String>, version_cfg: Option<String>) -> Option<String> {
+    match (cfg_condition, version_cfg) {
+        (Some(cfg), Some(version)) => Some(format!("{}\n{}", cfg, version)),
+        (cfg, version) => cfg.or(version),
+    }
+}
+
 pub fn doc_hidden(
     w: &mut Write,
     doc_hidden: bool,
@@ -274,6 +806,50 @@ pub fn doc_hidden(
     }
 }
 
+/// Makes the item findable in the docs by the name of the C symbol it's generated for.
+pub fn doc_alias(w: &mut Write, c_name: &str, comment_prefix: &str, indent: usize) -> Result<()> {
+    writeln!(
+        w,
+        "{}{}#[doc(alias = \"{}\")]",
+        tabs(indent),
+        comment_prefix,
+        c_name
+    )
+}
+
+/// Appends the configured `extra_derives` to the automatic derives of a type.
+/// Derives given by path, e.g. `"my_crate::MyDerive"`, are imported and used by name.
+pub fn merge_derives<'a>(automatic: &[&'a str], extra_derives: &'a [String]) -> Vec<&'a str> {
+    let mut derives: Vec<&str> = automatic.to_vec();
+    for derive in extra_derives {
+        let name = derive_name(derive);
+        if !derives.contains(&name) {
+            derives.push(name);
+        }
+    }
+    derives
+}
+
+fn derive_name(derive: &str) -> &str {
+    derive.rsplit("::").next().unwrap()
+}
+
+pub fn write_derives(w: &mut Write, derives: &[&str], indent: usize) -> Result<()> {
+    if derives.is_empty() {
+        return Ok(());
+    }
+    writeln!(w, "{}#[derive({})]", tabs(indent), derives.join(", "))
+}
+
+/// Derives the `serde` traits if the `serde` feature of the generated crate is enabled.
+pub fn derive_serde(w: &mut Write, indent: usize) -> Result<()> {
+    writeln!(
+        w,
+        "{}#[cfg_attr(feature = \"serde\", derive(Serialize, Deserialize))]",
+        tabs(indent)
+    )
+}
+
 pub fn write_vec<T: Display>(w: &mut Write, v: &[T]) -> Result<()> {
     for s in v {
         try!(writeln!(w, "{}", s));
@@ -281,15 +857,32 @@ pub fn write_vec<T: Display>(w: &mut Write, v: &[T]) -> Result<()> {
     Ok(())
 }
 
+/// Defaults each field of an inline boxed type, all of them numbers.
+pub fn define_field_wise_default(
+    w: &mut Write,
+    type_name: &str,
+    glib_name: &str,
+    fields: &[String],
+) -> Result<()> {
+    try!(writeln!(w, ""));
+    try!(writeln!(w, "impl Default for {} {{", type_name));
+    try!(writeln!(w, "    fn default() -> Self {{"));
+    try!(writeln!(w, "        {}(ffi::{} {{", type_name, glib_name));
+    for field in fields {
+        try!(writeln!(w, "            {}: Default::default(),", field));
+    }
+    try!(writeln!(w, "        }})"));
+    try!(writeln!(w, "    }}"));
+    writeln!(w, "}}")
+}
+
 pub fn declare_default_from_new(
     w: &mut Write,
     env: &Env,
     name: &str,
     functions: &[analysis::functions::Info],
 ) -> Result<()> {
-    if let Some(func) = functions.iter().find(|f| {
-        !f.visibility.hidden() && f.name == "new" && f.parameters.rust_parameters.is_empty()
-    }) {
+    if let Some(func) = analysis::functions::default_from_new(functions) {
         try!(writeln!(w, ""));
         try!(version_condition(w, env, func.version, false, 0));
         try!(writeln!(w, "impl Default for {} {{", name));
@@ -301,3 +894,276 @@ pub fn declare_default_from_new(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pod_boxed_type_defaults_field_wise() {
+        let mut buf = Vec::new();
+        define_field_wise_default(
+            &mut buf,
+            "Rectangle",
+            "GdkRectangle",
+            &["x".to_owned(), "y".to_owned()],
+        ).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "
+impl Default for Rectangle {
+    fn default() -> Self {
+        Rectangle(ffi::GdkRectangle {
+            x: Default::default(),
+            y: Default::default(),
+        })
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn removed_item_is_gated_to_window() {
+        let since = Some(Version::Full(3, 10, 0));
+        let removed = Version::Full(3, 20, 0);
+        assert_eq!(
+            window_cfg_attrs(since, removed, VersionGating::Features, false, false, 1),
+            "\t#[cfg(all(feature = \"v3_10\", not(feature = \"v3_20\")))]"
+        );
+        assert_eq!(
+            window_cfg_attrs(since, removed, VersionGating::Dox, true, false, 0),
+            "#[cfg(any(all(feature = \"v3_10\", not(feature = \"v3_20\")), feature = \"dox\"))]
+#[cfg_attr(feature = \"dox\", doc(cfg(all(feature = \"v3_10\", not(feature = \"v3_20\")))))]"
+        );
+    }
+
+    #[test]
+    fn replaced_signature_is_gated_exclusively() {
+        let version = Version::Full(3, 20, 0);
+        let newer = version_cfg_attrs(version, VersionGating::Dox, false, false, 1);
+        assert_eq!(newer, "\t#[cfg(any(feature = \"v3_20\", feature = \"dox\"))]");
+        assert_eq!(
+            replaced_version_cfg(version, VersionGating::Dox),
+            "#[cfg(not(any(feature = \"v3_20\", feature = \"dox\")))]"
+        );
+        assert_eq!(
+            replaced_version_cfg(version, VersionGating::Features),
+            "#[cfg(not(feature = \"v3_20\"))]"
+        );
+    }
+
+    #[test]
+    fn configured_cfg_condition_is_gated_with_version() {
+        let cfg = cfg_condition_string(&Some("target_os = \"linux\"".to_owned()), false, 1);
+        assert_eq!(
+            cfg,
+            Some("\t#[cfg(any(target_os = \"linux\", feature = \"dox\"))]".to_owned())
+        );
+        let version = Some("\t#[cfg(any(feature = \"v3_22\", feature = \"dox\"))]".to_owned());
+        assert_eq!(
+            join_conditions(cfg.clone(), version.clone()),
+            Some(format!("{}\n{}", cfg.unwrap(), version.unwrap()))
+        );
+        assert_eq!(join_conditions(None, None), None);
+    }
+
+    #[test]
+    fn version_condition_optionally_shows_doc_cfg() {
+        let version = Version::Full(3, 22, 0);
+        assert_eq!(
+            version_cfg_attrs(version, VersionGating::Dox, false, false, 1),
+            "\t#[cfg(any(feature = \"v3_22\", feature = \"dox\"))]"
+        );
+        assert_eq!(
+            version_cfg_attrs(version, VersionGating::Dox, true, false, 1),
+            "\t#[cfg(any(feature = \"v3_22\", feature = \"dox\"))]
+\t#[cfg_attr(feature = \"dox\", doc(cfg(feature = \"v3_22\")))]"
+        );
+    }
+
+    #[test]
+    fn version_condition_gated_by_feature_only() {
+        let version = Version::Full(3, 24, 0);
+        assert_eq!(
+            version_cfg_attrs(version, VersionGating::Features, false, false, 0),
+            "#[cfg(feature = \"v3_24\")]"
+        );
+        assert_eq!(
+            gated("not(feature = \"v3_24\")", VersionGating::Features),
+            "not(feature = \"v3_24\")"
+        );
+        assert_eq!(
+            gated("not(feature = \"v3_24\")", VersionGating::Dox),
+            "any(not(feature = \"v3_24\"), feature = \"dox\")"
+        );
+    }
+
+    #[test]
+    fn extra_derives_are_merged() {
+        let extra = vec!["Hash".to_owned(), "Copy".to_owned(), "my_crate::MyDerive".to_owned()];
+        assert_eq!(
+            merge_derives(&["Clone", "Copy"], &extra),
+            vec!["Clone", "Copy", "Hash", "MyDerive"]
+        );
+        assert_eq!(analysis::general::derive_uses(&extra), vec!["my_crate::MyDerive"]);
+
+        let mut buf = Vec::new();
+        write_derives(&mut buf, &merge_derives(&["Clone"], &extra[..1]), 0).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "#[derive(Clone, Hash)]\n");
+    }
+
+    #[test]
+    fn value_from_owned_type() {
+        assert_eq!(
+            value_from_impl("Align"),
+            "impl From<Align> for Value {
+    #[inline]
+    fn from(v: Align) -> Value {
+        Value::from(&v)
+    }
+}"
+        );
+    }
+
+    #[test]
+    fn external_parent_with_custom_ffi_crate() {
+        assert_eq!(
+            external_parent("gdk", "Window", "gdk_sys", "GdkWindow"),
+            "gdk::Window => gdk_sys::GdkWindow"
+        );
+    }
+
+    #[test]
+    fn external_interface_is_listed_with_ffi_type() {
+        let mut buf = Vec::new();
+        let parents = vec![
+            "Widget".to_owned(),
+            external_parent("gio", "ActionGroup", "gio_ffi", "GActionGroup"),
+        ];
+        write_object_type(
+            &mut buf,
+            "ApplicationWindow",
+            "GtkApplicationWindow",
+            &Some("GtkApplicationWindowClass"),
+            "gtk_application_window_get_type",
+            &parents,
+            true,
+        ).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "
+glib_wrapper! {
+\t#[doc(alias = \"GtkApplicationWindow\")]
+\tpub struct ApplicationWindow(Object<ffi::GtkApplicationWindow, \
+ffi::GtkApplicationWindowClass>): [
+\t\tWidget,
+\t\tgio::ActionGroup => gio_ffi::GActionGroup,
+\t];
+
+\tmatch fn {
+\t\tget_type => || ffi::gtk_application_window_get_type(),
+\t}
+}
+"
+        );
+    }
+
+    #[test]
+    fn pointer_boxed_type_clones_with_copy_fn() {
+        // Only inline boxed types derive `Clone` and `Copy`
+        let mut buf = Vec::new();
+        define_boxed_type(
+            &mut buf,
+            "Rgba",
+            "GdkRGBA",
+            "gdk_rgba_copy",
+            "gdk_rgba_free",
+            &None,
+        ).unwrap();
+        let code = String::from_utf8(buf).unwrap();
+        assert!(!code.contains("derive"));
+        assert!(code.contains("\t\tcopy => |ptr| ffi::gdk_rgba_copy(mut_override(ptr)),"));
+    }
+
+    #[test]
+    fn fundamental_type_wrapper() {
+        let mut buf = Vec::new();
+        define_fundamental_type(
+            &mut buf,
+            "ParamSpec",
+            "GParamSpec",
+            "g_param_spec_ref_sink",
+            "g_param_spec_unref",
+            "g_param_spec_get_type",
+        ).unwrap();
+        let code = String::from_utf8(buf).unwrap();
+
+        assert_eq!(
+            code,
+            "
+glib_wrapper! {
+\t#[doc(alias = "GParamSpec")]
+\tpub struct ParamSpec(Fundamental<ffi::GParamSpec>);
+
+\tmatch fn {
+\t\tref => |ptr| ffi::g_param_spec_ref_sink(ptr),
+\t\tunref => |ptr| ffi::g_param_spec_unref(ptr),
+\t\tget_type => || ffi::g_param_spec_get_type(),
+\t}
+}
+"
+        );
+    }
+
+    #[test]
+    fn into_glib_ptr_transfers_ownership() {
+        let mut buf = Vec::new();
+        define_into_glib_ptr(&mut buf, "Widget", "GtkWidget").unwrap();
+        let code = String::from_utf8(buf).unwrap();
+
+        assert!(code.contains("impl Widget {"));
+        assert!(code.contains("\tpub unsafe fn into_glib_ptr(self) -> *mut ffi::GtkWidget {"));
+        assert!(code.contains("\t\tlet ptr = mut_override(self.to_glib_none().0);"));
+        assert!(code.contains("\t\tmem::forget(self);"));
+        assert!(!code.contains("to_glib_full"));
+    }
+
+    #[test]
+    fn ptr_accessors_match_storage() {
+        let mut buf = Vec::new();
+        define_ptr_accessors(&mut buf, "Widget", "GtkWidget", "mut").unwrap();
+        let code = String::from_utf8(buf).unwrap();
+
+        assert!(code.contains("\tpub fn as_ptr(&self) -> *mut ffi::GtkWidget {"));
+        assert!(code.contains("\t\tlet ptr: *mut ffi::GtkWidget = self.to_glib_none().0;\n\t\tptr\n"));
+        assert!(code.contains("\tpub unsafe fn from_ptr(ptr: *mut ffi::GtkWidget) -> Self {"));
+        assert!(code.contains("\t\tfrom_glib_none(ptr)"));
+
+        let mut buf = Vec::new();
+        define_ptr_accessors(&mut buf, "Bytes", "GBytes", "const").unwrap();
+        let code = String::from_utf8(buf).unwrap();
+
+        assert!(code.contains("\t\tlet ptr: *const ffi::GBytes = self.to_glib_none().0;"));
+        assert!(code.contains("\t\tptr as *mut _"));
+    }
+
+    #[test]
+    fn drop_assert_only_in_debug_builds() {
+        let mut buf = Vec::new();
+        define_drop_assert(&mut buf, "Bytes", "GBytes", "const").unwrap();
+        let code = String::from_utf8(buf).unwrap();
+
+        assert!(code.starts_with(
+            "\nimpl Drop for Bytes {\n\tfn drop(&mut self) {\n\t\t#[cfg(debug_assertions)]\n\t\t{\n"
+        ));
+        assert!(code.contains("\t\t\tlet ptr: *const ffi::GBytes = self.to_glib_none().0;"));
+        assert!(code.contains(
+            "\t\t\tassert!(!ptr.is_null(), \"Dropping Bytes with a null pointer\");"
+        ));
+        assert!(code.contains(
+            "\t\t\tassert_eq!(ptr as usize % ::std::mem::align_of::<ffi::GBytes>(), 0, \
+             \"Dropping Bytes with a misaligned pointer\");"
+        ));
+    }
+}
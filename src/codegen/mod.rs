@@ -1,8 +1,10 @@
 use std::path::Path;
 
+use analysis::namespaces;
 use env::Env;
-use config::WorkMode;
+use config::{VersionGating, WorkMode};
 use file_saver::*;
+use self::passes::CodegenPass;
 
 mod child_properties;
 mod doc;
@@ -16,6 +18,7 @@ mod general;
 mod object;
 mod objects;
 mod parameter;
+pub mod passes;
 mod properties;
 mod property_body;
 mod record;
@@ -32,26 +35,60 @@ pub mod translate_from_glib;
 pub mod translate_to_glib;
 
 pub fn generate(env: &Env) {
+    generate_with_passes(env, &[]);
+}
+
+/// Generates like `generate`, running the custom `passes` after the code of each
+/// object and record in the normal work mode.
+pub fn generate_with_passes(env: &Env, passes: &[Box<CodegenPass>]) {
     match env.config.work_mode {
-        WorkMode::Normal => normal_generate(env),
+        WorkMode::Normal => normal_generate(env, passes),
         WorkMode::Sys => sys::generate(env),
         WorkMode::Doc => doc::generate(env),
     }
 }
 
-fn normal_generate(env: &Env) {
+fn normal_generate(env: &Env, passes: &[Box<CodegenPass>]) {
     let mut mod_rs: Vec<String> = Vec::new();
     let mut traits: Vec<String> = Vec::new();
     let root_path = env.config.target_path.join("src").join("auto");
 
-    objects::generate(env, &root_path, &mut mod_rs, &mut traits);
-    records::generate(env, &root_path, &mut mod_rs);
+    objects::generate(env, &root_path, None, passes, &mut mod_rs, &mut traits);
+    records::generate(env, &root_path, None, passes, &mut mod_rs);
     enums::generate(env, &root_path, &mut mod_rs);
     flags::generate(env, &root_path, &mut mod_rs);
     alias::generate(env, &root_path, &mut mod_rs);
     functions::generate(env, &root_path, &mut mod_rs);
 
     generate_mod_rs(env, &root_path, &mod_rs, &traits);
+
+    if env.config.version_gating == VersionGating::Features {
+        print_version_features(env);
+    }
+
+    for partition in env.config.partitions.iter() {
+        let mut mod_rs: Vec<String> = Vec::new();
+        let mut traits: Vec<String> = Vec::new();
+        let root_path = partition.target_path.join("src").join("auto");
+        let crate_name = Some(&partition.crate_name[..]);
+
+        objects::generate(env, &root_path, crate_name, passes, &mut mod_rs, &mut traits);
+        records::generate(env, &root_path, crate_name, passes, &mut mod_rs);
+
+        generate_mod_rs(env, &root_path, &mod_rs, &traits);
+    }
+}
+
+/// Lists the Cargo features the generated code is gated by, which the crate must declare.
+fn print_version_features(env: &Env) {
+    let features: Vec<String> = env.namespaces
+        .main()
+        .versions
+        .iter()
+        .filter(|&&v| v > env.config.min_cfg_version)
+        .map(|v| v.to_feature())
+        .collect();
+    println!("Version features of {}: {}", env.config.library_name, features.join(", "));
 }
 
 pub fn generate_mod_rs(env: &Env, root_path: &Path, mod_rs: &[String], traits: &[String]) {
@@ -63,6 +100,211 @@ pub fn generate_mod_rs(env: &Env, root_path: &Path, mod_rs: &[String], traits: &
         try!(writeln!(w, "#[doc(hidden)]"));
         try!(writeln!(w, "pub mod traits {{"));
         try!(general::write_vec(w, traits));
-        writeln!(w, "}}")
+        try!(writeln!(w, "}}"));
+        if env.config.generate_prelude {
+            try!(writeln!(w, ""));
+            try!(writeln!(w, "pub mod prelude {{"));
+            try!(general::write_vec(w, &prelude(env)));
+            try!(writeln!(w, "}}"));
+        }
+        Ok(())
     });
 }
+
+// Traits the generated public API requires, re-exported unless configured otherwise
+const DEFAULT_PRELUDE: &'static [&'static str] = &["glib::object::IsA", "glib::object::Downcast"];
+
+fn prelude(env: &Env) -> Vec<String> {
+    let glib_is_main = env.namespaces.glib_ns_id == namespaces::MAIN;
+    match env.config.prelude {
+        Some(ref configured) => prelude_lines(configured, glib_is_main),
+        None => {
+            let analysis = &env.analysis;
+            let used: Vec<String> = DEFAULT_PRELUDE
+                .iter()
+                .filter(|&&name| {
+                    analysis
+                        .objects
+                        .values()
+                        .map(|info| &info.imports)
+                        .chain(analysis.records.values().map(|info| &info.imports))
+                        .chain(analysis.global_functions.iter().map(|info| &info.imports))
+                        .any(|imports| {
+                            imports.iter().any(|(import, _)| {
+                                import == name || (glib_is_main && import == &name[6..])
+                            })
+                        })
+                })
+                .map(|&name| name.to_owned())
+                .collect();
+            prelude_lines(&used, glib_is_main)
+        }
+    }
+}
+
+fn prelude_lines(names: &[String], glib_is_main: bool) -> Vec<String> {
+    let mut lines = vec!["\tpub use super::traits::*;".to_owned()];
+    for name in names {
+        let name = if glib_is_main && name.starts_with("glib::") {
+            &name[6..]
+        } else {
+            &name[..]
+        };
+        lines.push(format!("\tpub use {};", name));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_env;
+
+    const SINKS: &'static str = r#"
+    <class name="Widget" c:type="TestWidget" parent="GObject.Object"
+           glib:type-name="TestWidget" glib:get-type="test_widget_get_type">
+      <method name="get_audio_sink" c:identifier="test_widget_get_audio_sink">
+        <return-value transfer-ownership="none">
+          <type name="AudioSink" c:type="TestAudioSink*"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="widget" transfer-ownership="none">
+            <type name="Widget" c:type="TestWidget*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+    </class>
+    <class name="VideoSink" c:type="TestVideoSink" parent="Widget"
+           glib:type-name="TestVideoSink" glib:get-type="test_video_sink_get_type">
+    </class>
+    <class name="AudioSink" c:type="TestAudioSink" parent="Widget"
+           glib:type-name="TestAudioSink" glib:get-type="test_audio_sink_get_type">
+      <method name="set_video_sink" c:identifier="test_audio_sink_set_video_sink">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="sink" transfer-ownership="none">
+            <type name="AudioSink" c:type="TestAudioSink*"/>
+          </instance-parameter>
+          <parameter name="video_sink" transfer-ownership="none">
+            <type name="VideoSink" c:type="TestVideoSink*"/>
+          </parameter>
+        </parameters>
+      </method>
+    </class>"#;
+
+    fn sinks_config(widget: &str) -> String {
+        format!(
+            r#"
+[[object]]
+name = "Test.Widget"
+status = "generate"
+{}
+
+[[object]]
+name = "Test.VideoSink"
+status = "generate"
+
+[[object]]
+name = "Test.AudioSink"
+status = "generate"
+
+[[partition]]
+crate_name = "test_video"
+target_path = "video"
+prefixes = ["Video"]
+
+[[partition]]
+crate_name = "test_audio"
+target_path = "audio"
+prefixes = ["Audio"]
+"#,
+            widget
+        )
+    }
+
+    fn read(path: &Path) -> String {
+        use std::io::Read;
+
+        let mut content = String::new();
+        ::std::fs::File::open(path)
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        content
+    }
+
+    #[test]
+    fn partitions_are_generated_with_cross_references() {
+        let objects = sinks_config(
+            r#"
+[[object.function]]
+name = "get_audio_sink"
+ignore = true"#,
+        );
+        let env = test_env::env(SINKS, "", &objects);
+        normal_generate(&env, &[]);
+
+        let root = env.config.target_path.clone();
+        let auto = |krate: &str| root.join(krate).join("src").join("auto");
+        let main_mod = read(&auto("").join("mod.rs"));
+        let video_mod = read(&auto("video").join("mod.rs"));
+        let audio_mod = read(&auto("audio").join("mod.rs"));
+        let audio_sink = read(&auto("audio").join("audio_sink.rs"));
+        ::std::fs::remove_dir_all(&root).unwrap();
+
+        assert!(main_mod.contains("mod widget;"));
+        assert!(!main_mod.contains("sink;"));
+        assert!(video_mod.contains("mod video_sink;"));
+        assert!(!video_mod.contains("mod audio_sink;"));
+        assert!(audio_mod.contains("mod audio_sink;"));
+        assert!(!audio_mod.contains("mod video_sink;"));
+
+        assert!(audio_sink.contains("use test::Widget;"));
+        assert!(audio_sink.contains("use test_video::VideoSink;"));
+    }
+
+    #[test]
+    fn partitions_depending_on_each_other_are_rejected() {
+        let result = test_env::try_env(SINKS, "", &sinks_config(""));
+        assert!(result.is_err());
+    }
+
+    const WIDGETS: &'static str = r#"
+[[object]]
+name = "Test.Widget"
+status = "generate"
+
+[[object]]
+name = "Test.VideoSink"
+status = "generate"
+"#;
+
+    #[test]
+    fn prelude_reexports_used_traits() {
+        let options = "generate_prelude = true";
+        let mod_rs = test_env::generated_file(SINKS, options, WIDGETS, "mod.rs");
+        assert!(mod_rs.contains(
+            "pub mod prelude {
+    pub use super::traits::*;
+    pub use glib::object::IsA;
+}"
+        ));
+        assert!(mod_rs.contains("    pub use super::WidgetExt;"));
+        assert!(!mod_rs.contains("Downcast"));
+    }
+
+    #[test]
+    fn prelude_reexports_configured_traits() {
+        let options = "generate_prelude = true\nprelude = [\"glib::object::Cast\"]";
+        let mod_rs = test_env::generated_file(SINKS, options, WIDGETS, "mod.rs");
+        assert!(mod_rs.contains(
+            "pub mod prelude {
+    pub use super::traits::*;
+    pub use glib::object::Cast;
+}"
+        ));
+        assert!(!mod_rs.contains("IsA"));
+    }
+}
@@ -1,8 +1,11 @@
 use std::io::{Result, Write};
 
 use analysis;
+use analysis::properties::Property;
+use config::VersionGating;
 use library;
 use env::Env;
+use version::Version;
 use super::child_properties;
 use super::function;
 use super::general;
@@ -15,15 +18,51 @@ pub fn generate(w: &mut Write, env: &Env, analysis: &analysis::object::Info) ->
     try!(general::start_comments(w, &env.config));
     try!(general::uses(w, env, &analysis.imports));
 
-    try!(general::define_object_type(
-        w,
-        env,
-        &analysis.name,
-        &analysis.c_type,
-        &analysis.c_class_type.as_ref().map(|s| &s[..]),
-        &analysis.get_type,
-        &analysis.supertypes,
-    ));
+    if let Some(ref fundamental) = analysis.fundamental {
+        try!(general::define_fundamental_type(
+            w,
+            &analysis.name,
+            &analysis.c_type,
+            &fundamental.ref_fn,
+            &fundamental.unref_fn,
+            &analysis.get_type,
+        ));
+    } else {
+        try!(general::define_object_type(
+            w,
+            env,
+            &analysis.name,
+            &analysis.c_type,
+            &analysis.c_class_type.as_ref().map(|s| &s[..]),
+            &analysis.get_type,
+            &analysis.supertypes,
+        ));
+    }
+
+    if analysis.fundamental.is_none() {
+        try!(general::define_into_glib_ptr(
+            w,
+            &analysis.name,
+            &analysis.c_type
+        ));
+    }
+
+    if env.config.expose_ptr {
+        try!(general::define_ptr_accessors(
+            w,
+            &analysis.name,
+            &analysis.c_type,
+            "mut"
+        ));
+    }
+
+    if env.config.generate_downgrade && analysis.fundamental.is_none() {
+        try!(general::define_downgrade(w, &analysis.name));
+    }
+
+    if env.config.generate_property_names && !analysis.properties.is_empty() {
+        try!(generate_property_names(w, env, &analysis.name, &analysis.properties));
+    }
 
     if need_generate_inherent(analysis) {
         try!(writeln!(w, ""));
@@ -95,8 +134,66 @@ pub fn generate(w: &mut Write, env: &Env, analysis: &analysis::object::Info) ->
         } else {
             None
         },
+        analysis.generate_eq,
     ));
 
+    if let Some(ref as_ref_str) = analysis.as_ref_str {
+        try!(trait_impls::generate_as_ref_str(
+            w,
+            env,
+            &analysis.name,
+            as_ref_str
+        ));
+    }
+
+    if let Some(ref eq_str) = analysis.eq_str {
+        try!(trait_impls::generate_eq_str(w, env, &analysis.name, eq_str));
+    }
+
+    if let Some(ref iterator_next) = analysis.iterator_next {
+        try!(trait_impls::generate_iterator(
+            w,
+            env,
+            &analysis.name,
+            iterator_next
+        ));
+    }
+
+    if let Some(ref index_getter) = analysis.index_getter {
+        try!(trait_impls::generate_index_getter(
+            w,
+            env,
+            &analysis.name,
+            index_getter
+        ));
+    }
+
+    if let Some(ref from_str) = analysis.from_str {
+        try!(trait_impls::generate_from_str(
+            w,
+            env,
+            &analysis.name,
+            from_str
+        ));
+    }
+
+    if let Some(ref borrowed_type) = analysis.borrowed_type {
+        try!(trait_impls::generate_borrow(w, &analysis.name, borrowed_type));
+    }
+
+    if env.config.generate_value_from && analysis.fundamental.is_none() {
+        try!(writeln!(w, "\n{}", general::value_from_impl(&analysis.name)));
+    }
+
+    for ancestor in &analysis.ancestors {
+        try!(trait_impls::generate_as_ref_ancestor(
+            w,
+            env,
+            &analysis.name,
+            ancestor
+        ));
+    }
+
     if analysis.concurrency != library::Concurrency::None {
         try!(writeln!(w, ""));
     }
@@ -222,6 +319,86 @@ fn generate_trait(w: &mut Write, env: &Env, analysis: &analysis::object::Info) -
     Ok(())
 }
 
+fn generate_property_names(
+    w: &mut Write,
+    env: &Env,
+    type_name: &str,
+    properties: &[Property],
+) -> Result<()> {
+    let mut names: Vec<(&str, Option<Version>)> = Vec::new();
+    for property in properties {
+        if !names.iter().any(|&(name, _)| name == property.name) {
+            let version = match property.version {
+                Some(v) if v > env.config.min_cfg_version => Some(v),
+                _ => None,
+            };
+            names.push((&property.name[..], version));
+        }
+    }
+
+    try!(writeln!(w, ""));
+    try!(writeln!(w, "impl {} {{", type_name));
+    for (pos, &(ref cfg, ref names)) in property_name_levels(&names, env.config.version_gating)
+        .iter()
+        .enumerate()
+    {
+        if pos > 0 {
+            try!(writeln!(w, ""));
+        }
+        if let Some(ref cfg) = *cfg {
+            try!(writeln!(w, "\t#[cfg({})]", cfg));
+        }
+        try!(writeln!(
+            w,
+            "\t/// Names of the properties declared by `{}` itself.",
+            type_name
+        ));
+        try!(writeln!(
+            w,
+            "\tpub fn property_names() -> &'static [&'static str] {{"
+        ));
+        let names: Vec<String> = names.iter().map(|name| format!("\"{}\"", name)).collect();
+        try!(writeln!(w, "\t\t&[{}]", names.join(", ")));
+        try!(writeln!(w, "\t}}"));
+    }
+    writeln!(w, "}}")
+}
+
+/// Splits the names by the versions adding them, as a static slice can't have
+/// conditional elements. Each level gets a condition excluding the newer levels,
+/// the newest one also holds for the docs.
+fn property_name_levels<'a>(
+    names: &[(&'a str, Option<Version>)],
+    gating: VersionGating,
+) -> Vec<(Option<String>, Vec<&'a str>)> {
+    let mut versions: Vec<Option<Version>> = names.iter().map(|&(_, version)| version).collect();
+    versions.sort();
+    versions.dedup();
+
+    let gated = |version: Version| general::gated(&version.to_cfg(), gating);
+
+    let mut levels = Vec::with_capacity(versions.len());
+    for (pos, &version) in versions.iter().enumerate() {
+        let level_names = names
+            .iter()
+            .filter(|&&(_, v)| v <= version)
+            .map(|&(name, _)| name)
+            .collect();
+        let cfg = match (version, versions.get(pos + 1)) {
+            (None, None) => None,
+            (Some(version), None) => Some(gated(version)),
+            (None, Some(&next)) => Some(format!("not({})", gated(next.unwrap()))),
+            (Some(version), Some(&next)) => Some(format!(
+                "all({}, not({}))",
+                version.to_cfg(),
+                gated(next.unwrap())
+            )),
+        };
+        levels.push((cfg, level_names));
+    }
+    levels
+}
+
 fn need_generate_inherent(analysis: &analysis::object::Info) -> bool {
     analysis.has_constructors || analysis.has_functions || !need_generate_trait(analysis)
 }
@@ -262,3 +439,454 @@ pub fn generate_reexports(
         traits.push(format!("\tpub use super::{};", analysis.trait_name));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_env;
+
+    #[test]
+    fn property_names_of_one_level() {
+        let names = [("label", None), ("use-underline", None)];
+        assert_eq!(
+            property_name_levels(&names, VersionGating::Dox),
+            vec![(None, vec!["label", "use-underline"])]
+        );
+    }
+
+    #[test]
+    fn property_names_split_by_version() {
+        let v3_16 = Some(Version::Full(3, 16, 0));
+        let names = [("label", None), ("xalign", v3_16)];
+        assert_eq!(
+            property_name_levels(&names, VersionGating::Dox),
+            vec![
+                (
+                    Some("not(any(feature = \"v3_16\", feature = \"dox\"))".to_owned()),
+                    vec!["label"],
+                ),
+                (
+                    Some("any(feature = \"v3_16\", feature = \"dox\")".to_owned()),
+                    vec!["label", "xalign"],
+                ),
+            ]
+        );
+    }
+
+    const WIDGET: &'static str = r#"
+    <class name="Widget" c:type="TestWidget" parent="GObject.Object"
+           glib:type-name="TestWidget" glib:get-type="test_widget_get_type">
+      <method name="adopt" c:identifier="test_widget_adopt">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="widget" transfer-ownership="none">
+            <type name="Widget" c:type="TestWidget*"/>
+          </instance-parameter>
+          <parameter name="child" transfer-ownership="full">
+            <type name="Widget" c:type="TestWidget*"/>
+          </parameter>
+        </parameters>
+      </method>
+      <method name="release" c:identifier="test_widget_release">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="widget" transfer-ownership="full">
+            <type name="Widget" c:type="TestWidget*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+    </class>"#;
+
+    #[test]
+    fn transfer_full_is_borrowed_by_default() {
+        let code = test_env::generated_object(WIDGET, "", "Widget", "");
+        assert!(code.contains("fn release(&self)"));
+        assert!(code.contains("child: &"));
+        assert!(code.contains("self.to_glib_full()"));
+    }
+
+    #[test]
+    fn transfer_full_is_consumed_on_request() {
+        let code =
+            test_env::generated_object(WIDGET, "consume_transfer_full = true", "Widget", "");
+        assert!(code.contains("fn release(self)"));
+        assert!(code.contains("child: "));
+        assert!(!code.contains("child: &"));
+        assert!(code.contains("self.to_glib_full()"));
+        let call = code.lines()
+            .find(|line| line.contains("ffi::test_widget_adopt("))
+            .unwrap();
+        assert!(call.contains("child") && call.contains(".to_glib_full()"));
+    }
+
+    #[test]
+    fn into_glib_ptr_forgets_the_wrapper() {
+        let code = test_env::generated_object(WIDGET, "", "Widget", "");
+        assert!(code.contains("pub unsafe fn into_glib_ptr(self) -> *mut ffi::TestWidget {"));
+        assert!(code.contains("mem::forget(self);"));
+    }
+
+    const CONTAINER: &'static str = r#"
+    <class name="Widget" c:type="TestWidget" parent="GObject.Object"
+           glib:type-name="TestWidget" glib:get-type="test_widget_get_type">
+    </class>
+    <class name="Label" c:type="TestLabel" parent="GObject.Object" final="1"
+           glib:type-name="TestLabel" glib:get-type="test_label_get_type">
+    </class>
+    <class name="Container" c:type="TestContainer" parent="GObject.Object"
+           glib:type-name="TestContainer" glib:get-type="test_container_get_type">
+      <method name="add" c:identifier="test_container_add">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="container" transfer-ownership="none">
+            <type name="Container" c:type="TestContainer*"/>
+          </instance-parameter>
+          <parameter name="widget" transfer-ownership="none">
+            <type name="Widget" c:type="TestWidget*"/>
+          </parameter>
+          <parameter name="label" transfer-ownership="none">
+            <type name="Label" c:type="TestLabel*"/>
+          </parameter>
+        </parameters>
+      </method>
+      <method name="set_focus_child" c:identifier="test_container_set_focus_child">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="container" transfer-ownership="none">
+            <type name="Container" c:type="TestContainer*"/>
+          </instance-parameter>
+          <parameter name="child" transfer-ownership="none" nullable="1">
+            <type name="Widget" c:type="TestWidget*"/>
+          </parameter>
+        </parameters>
+      </method>
+    </class>"#;
+
+    const CONTAINER_OBJECTS: &'static str = r#"
+[[object]]
+name = "Test.Widget"
+status = "generate"
+
+[[object]]
+name = "Test.Label"
+status = "generate"
+"#;
+
+    #[test]
+    fn class_without_subtypes_is_taken_by_reference() {
+        let code = test_env::generated_object(CONTAINER, "", "Container", CONTAINER_OBJECTS);
+        assert!(code.contains("widget: &Widget"));
+        assert!(code.contains("label: &Label"));
+        assert!(!code.contains("IsA<Widget>"));
+    }
+
+    #[test]
+    fn non_final_class_is_taken_by_is_a_on_request() {
+        let options = "non_final_is_a = true";
+        let code = test_env::generated_object(CONTAINER, options, "Container", CONTAINER_OBJECTS);
+        assert!(code.contains("<P: IsA<Widget>>"));
+        assert!(code.contains("widget: &P"));
+        assert!(code.contains("label: &Label"));
+        let call = code.lines()
+            .find(|line| line.contains("ffi::test_container_add("))
+            .unwrap();
+        assert!(call.contains("widget.to_glib_none().0"));
+        assert!(call.contains("label.to_glib_none().0"));
+    }
+
+    #[test]
+    fn nullable_is_a_parameter_is_taken_by_into_option() {
+        let options = "non_final_is_a = true";
+        let code = test_env::generated_object(CONTAINER, options, "Container", CONTAINER_OBJECTS);
+        assert!(code.contains("<'a, P: IsA<Widget> + 'a, Q: Into<Option<&'a P>>>"));
+        assert!(code.contains("child: Q"));
+        assert!(code.contains("let child = child.into();"));
+    }
+
+    #[test]
+    fn nullable_is_a_parameter_is_kept_without_into_option() {
+        let options = "non_final_is_a = true\ninto_option_params = false";
+        let code = test_env::generated_object(CONTAINER, options, "Container", CONTAINER_OBJECTS);
+        assert!(code.contains("<P: IsA<Widget>>"));
+        assert!(code.contains("child: Option<&P>"));
+        assert!(!code.contains("child: Option<&Widget>"));
+        let call = code.lines()
+            .find(|line| line.contains("ffi::test_container_set_focus_child("))
+            .unwrap();
+        assert!(call.contains("child.to_glib_none().0"));
+    }
+
+    #[test]
+    fn constructors_get_short_names() {
+        let gir = r#"
+    <class name="Button" c:type="TestButton" parent="GObject.Object"
+           glib:type-name="TestButton" glib:get-type="test_button_get_type">
+      <constructor name="new" c:identifier="test_button_new">
+        <return-value transfer-ownership="none">
+          <type name="Button" c:type="TestButton*"/>
+        </return-value>
+      </constructor>
+      <constructor name="new_with_label" c:identifier="test_button_new_with_label">
+        <return-value transfer-ownership="none">
+          <type name="Button" c:type="TestButton*"/>
+        </return-value>
+        <parameters>
+          <parameter name="label" transfer-ownership="none">
+            <type name="utf8" c:type="const gchar*"/>
+          </parameter>
+        </parameters>
+      </constructor>
+      <constructor name="new_from_file" c:identifier="test_button_new_from_file">
+        <return-value transfer-ownership="none">
+          <type name="Button" c:type="TestButton*"/>
+        </return-value>
+        <parameters>
+          <parameter name="filename" transfer-ownership="none">
+            <type name="filename" c:type="const gchar*"/>
+          </parameter>
+        </parameters>
+      </constructor>
+      <constructor name="new_for_path" c:identifier="test_button_new_for_path">
+        <return-value transfer-ownership="none">
+          <type name="Button" c:type="TestButton*"/>
+        </return-value>
+        <parameters>
+          <parameter name="path" transfer-ownership="none">
+            <type name="utf8" c:type="const gchar*"/>
+          </parameter>
+        </parameters>
+      </constructor>
+      <function name="lookup" c:identifier="test_button_lookup">
+        <return-value transfer-ownership="none">
+          <type name="Button" c:type="TestButton*"/>
+        </return-value>
+        <parameters>
+          <parameter name="path" transfer-ownership="none">
+            <type name="utf8" c:type="const gchar*"/>
+          </parameter>
+        </parameters>
+      </function>
+    </class>"#;
+        let rename = r#"
+    [[object.function]]
+    name = "lookup"
+    rename = "for_path"
+"#;
+        let options = "short_constructor_names = true";
+        let code = test_env::generated_object(gir, options, "Button", rename);
+
+        assert!(code.contains("pub fn new() -> Button {"));
+        assert!(code.contains("pub fn with_label(label: &str) -> Button {"));
+        assert!(code.contains("pub fn from_file<"));
+        // `for_path` is taken by a renamed function
+        assert!(code.contains("pub fn new_for_path(path: &str) -> Button {"));
+        assert!(code.contains("pub fn for_path(path: &str) -> Option<Button> {"));
+    }
+
+    #[test]
+    fn floating_constructor_return_is_sunk() {
+        let gir = r#"
+    <class name="Button" c:type="TestButton" parent="GObject.InitiallyUnowned"
+           glib:type-name="TestButton" glib:get-type="test_button_get_type">
+      <constructor name="new" c:identifier="test_button_new">
+        <return-value transfer-ownership="full">
+          <type name="Button" c:type="TestButton*"/>
+        </return-value>
+      </constructor>
+    </class>
+    <class name="Group" c:type="TestGroup" parent="GObject.Object"
+           glib:type-name="TestGroup" glib:get-type="test_group_get_type">
+      <constructor name="new" c:identifier="test_group_new">
+        <return-value transfer-ownership="full">
+          <type name="Group" c:type="TestGroup*"/>
+        </return-value>
+      </constructor>
+    </class>"#;
+        let code = test_env::generated_object(gir, "", "Button", "");
+        assert!(code.contains("from_glib_none(ffi::test_button_new())"));
+        assert!(!code.contains("from_glib_full(ffi::test_button_new())"));
+
+        let code = test_env::generated_object(gir, "", "Group", "");
+        assert!(code.contains("from_glib_full(ffi::test_group_new())"));
+    }
+
+    #[test]
+    fn fundamental_type_is_wrapped_without_into_glib_ptr() {
+        let gir = r#"
+    <class name="MiniObject" c:type="TestMiniObject" glib:fundamental="1"
+           glib:ref-func="test_mini_object_ref" glib:unref-func="test_mini_object_unref"
+           glib:type-name="TestMiniObject" glib:get-type="test_mini_object_get_type">
+    </class>"#;
+        let code = test_env::generated_object(gir, "", "MiniObject", "");
+
+        assert!(code.contains("    pub struct MiniObject(Fundamental<ffi::TestMiniObject>);"));
+        assert!(code.contains("        ref => |ptr| ffi::test_mini_object_ref(ptr),"));
+        assert!(code.contains("        unref => |ptr| ffi::test_mini_object_unref(ptr),"));
+        assert!(code.contains("        get_type => || ffi::test_mini_object_get_type(),"));
+        assert!(!code.contains("into_glib_ptr"));
+    }
+
+    const BUFFER: &'static str = r#"
+    <class name="Buffer" c:type="TestBuffer" parent="GObject.Object"
+           glib:type-name="TestBuffer" glib:get-type="test_buffer_get_type">
+      <glib:signal name="reserve" when="last">
+        <return-value transfer-ownership="none">
+          <type name="gsize" c:type="gsize"/>
+        </return-value>
+        <parameters>
+          <parameter name="hint" transfer-ownership="none">
+            <type name="gsize" c:type="gsize"/>
+          </parameter>
+        </parameters>
+      </glib:signal>
+    </class>"#;
+
+    #[test]
+    fn narrowing_trampoline_return_is_checked() {
+        let options = "size_type = \"u64\"";
+        let code = test_env::generated_object(BUFFER, options, "Buffer", "");
+        assert!(code.contains("f(&from_glib_borrow(this), hint as u64) as usize"));
+        assert!(!code.contains("TryFrom"));
+
+        let options = "size_type = \"u64\"\nchecked_casts = true";
+        let code = test_env::generated_object(BUFFER, options, "Buffer", "");
+        assert!(code.contains("use std::convert::TryFrom;\n"));
+        // Widening the parameter can't fail
+        assert!(code.contains("usize::try_from(f(&from_glib_borrow(this), hint as u64))"));
+    }
+
+    const UNSTABLE: &'static str = r#"
+    <class name="Gesture" c:type="TestGesture" parent="GObject.Object" stability="Unstable"
+           glib:type-name="TestGesture" glib:get-type="test_gesture_get_type">
+    </class>
+    <class name="Canvas" c:type="TestCanvas" parent="GObject.Object"
+           glib:type-name="TestCanvas" glib:get-type="test_canvas_get_type">
+      <property name="opacity" writable="1" transfer-ownership="none" stability="Unstable">
+        <type name="gdouble" c:type="gdouble"/>
+      </property>
+      <glib:signal name="pinched" when="last" stability="Unstable">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+      </glib:signal>
+    </class>"#;
+
+    const UNSTABLE_OBJECTS: &'static str = r#"
+[[object]]
+name = "Test.Gesture"
+status = "generate"
+
+[[object]]
+name = "Test.Canvas"
+status = "generate"
+"#;
+
+    fn assert_gated(code: &str, item: &str) {
+        let lines: Vec<&str> = code.lines().collect();
+        let mut found = false;
+        for (i, line) in lines.iter().enumerate() {
+            if line.contains(item) {
+                assert_eq!(lines[i - 1].trim(), "#[cfg(feature = \"unstable\")]", "{}", item);
+                found = true;
+            }
+        }
+        assert!(found, "{} not generated", item);
+    }
+
+    #[test]
+    fn unstable_properties_signals_and_types_are_feature_gated() {
+        let options = "unstable_feature = \"unstable\"";
+        let env = test_env::env(UNSTABLE, options, UNSTABLE_OBJECTS);
+        let gated = Some("feature = \"unstable\"".to_owned());
+        assert_eq!(env.analysis.objects["Test.Gesture"].cfg_condition, gated);
+        assert_eq!(env.analysis.objects["Test.Canvas"].cfg_condition, None);
+
+        let code = test_env::generated_object(UNSTABLE, options, "Canvas", "");
+        for item in &[
+            "fn get_property_opacity(",
+            "fn set_property_opacity(",
+            "fn connect_property_opacity_notify<",
+            "fn notify_opacity_trampoline",
+            "fn connect_pinched<",
+            "fn pinched_trampoline",
+            "use glib::signal::connect;",
+        ] {
+            assert_gated(&code, item);
+        }
+
+        let env = test_env::env(UNSTABLE, "", UNSTABLE_OBJECTS);
+        assert_eq!(env.analysis.objects["Test.Gesture"].cfg_condition, None);
+        let code = test_env::generated_object(UNSTABLE, "", "Canvas", "");
+        assert!(code.contains("fn connect_pinched<"));
+        assert!(!code.contains("unstable"));
+    }
+
+    #[test]
+    fn object_types_are_downgraded_to_weak_refs() {
+        let code = test_env::generated_object(WIDGET, "", "Widget", "");
+        assert!(code.contains("use glib::WeakRef;"));
+        assert!(code.contains(
+            "    pub fn downgrade(&self) -> WeakRef<Self> {\n        ObjectExt::downgrade(self)\n    }"
+        ));
+
+        let code =
+            test_env::generated_object(WIDGET, "generate_downgrade = false", "Widget", "");
+        assert!(!code.contains("WeakRef"));
+        assert!(!code.contains("fn downgrade("));
+    }
+
+    #[test]
+    fn doc_aliases_name_c_symbols() {
+        let code = test_env::generated_object(WIDGET, "", "Widget", "");
+        assert!(code.contains("glib_wrapper! {\n    #[doc(alias = \"TestWidget\")]\n"));
+        assert!(code.contains(
+            "    #[doc(alias = \"test_widget_release\")]\n    fn release(&self)"
+        ));
+    }
+
+    const ACTIONS: &'static str = r#"
+    <class name="Entry" c:type="TestEntry" parent="GObject.Object"
+           glib:type-name="TestEntry" glib:get-type="test_entry_get_type">
+      <glib:signal name="activate" when="last" action="1">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+      </glib:signal>
+      <glib:signal name="changed" when="last">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+      </glib:signal>
+    </class>"#;
+
+    #[test]
+    fn emit_methods_are_generated_for_action_signals() {
+        let code = test_env::generated_object(ACTIONS, "", "Entry", "");
+        assert!(code.contains("fn emit_activate("));
+        assert!(code.contains("let _ = self.emit(\"activate\", &[]).unwrap();"));
+        assert!(!code.contains("fn emit_changed("));
+
+        let code = test_env::generated_object(ACTIONS, "emit_signals = true", "Entry", "");
+        assert!(code.contains("fn emit_activate("));
+        assert!(code.contains("fn emit_changed("));
+
+        let signals = r#"
+    [[object.signal]]
+    name = "activate"
+    emit = false
+    [[object.signal]]
+    name = "changed"
+    emit = true"#;
+        let code = test_env::generated_object(ACTIONS, "", "Entry", signals);
+        assert!(!code.contains("fn emit_activate("));
+        assert!(code.contains("fn emit_changed("));
+    }
+}
@@ -3,14 +3,25 @@ use std::path::Path;
 use env::Env;
 use file_saver::*;
 use nameutil::*;
+use super::passes::{self, CodegenPass};
 
-pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>, traits: &mut Vec<String>) {
+pub fn generate(
+    env: &Env,
+    root_path: &Path,
+    crate_name: Option<&str>,
+    passes: &[Box<CodegenPass>],
+    mod_rs: &mut Vec<String>,
+    traits: &mut Vec<String>,
+) {
     info!("Generate objects");
     for class_analysis in env.analysis.objects.values() {
         let obj = &env.config.objects[&class_analysis.full_name];
         if !obj.status.need_generate() {
             continue;
         }
+        if env.config.partitions.crate_of(&class_analysis.full_name) != crate_name {
+            continue;
+        }
 
         let mod_name = obj.module_name.clone().unwrap_or_else(|| {
             module_name(split_namespace_name(&class_analysis.full_name).1)
@@ -20,8 +31,9 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>, traits: &
         path.set_extension("rs");
         info!("Generating file {:?}", path);
 
-        save_to_file(path, env.config.make_backup, |ref mut w| {
-            super::object::generate(w, env, class_analysis)
+        save_to_file(path, env.config.make_backup, |w| {
+            try!(super::object::generate(w, env, class_analysis));
+            passes::run(w, passes, env, &class_analysis.base)
         });
 
         super::object::generate_reexports(env, class_analysis, &mod_name, mod_rs, traits);
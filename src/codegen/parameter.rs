@@ -1,9 +1,10 @@
 use env::Env;
 use analysis::bounds::{BoundType, Bounds};
 use analysis::conversion_type::ConversionType;
-use analysis::function_parameters::CParameter;
+use analysis::function_parameters::{is_bytes, CParameter};
 use analysis::ref_mode::RefMode;
 use analysis::rust_type::parameter_rust_type;
+use library::ParameterDirection;
 use traits::*;
 
 pub trait ToParameter {
@@ -18,7 +19,11 @@ impl ToParameter for CParameter {
             ""
         };
         if self.instance_parameter {
-            format!("&{}self", mut_str)
+            if self.ref_mode == RefMode::None {
+                "self".into()
+            } else {
+                format!("&{}self", mut_str)
+            }
         } else {
             let type_str: String;
             match bounds.get_parameter_alias_info(&self.name) {
@@ -26,8 +31,9 @@ impl ToParameter for CParameter {
                     match bound_type {
                         BoundType::IsA(_) => {
                             if *self.nullable {
-                                // should not happen!
-                                type_str = String::new()
+                                type_str = format!("Option<&{}{}>", mut_str, t)
+                            } else if self.ref_mode == RefMode::None {
+                                type_str = t.to_string()
                             } else {
                                 type_str = format!("&{}{}", mut_str, t)
                             }
@@ -36,6 +42,25 @@ impl ToParameter for CParameter {
                         BoundType::AsRef(_) | BoundType::Into(_, None) => type_str = t.to_string(),
                     }
                 }
+                None if self.duration_us => type_str = "::std::time::Duration".into(),
+                None if self.date_time.is_some() => {
+                    let time_type = self.date_time.unwrap().rust_type().unwrap();
+                    type_str = if *self.nullable {
+                        format!("Option<{}>", time_type)
+                    } else {
+                        time_type.into()
+                    }
+                }
+                None if self.time_t.is_some() => {
+                    type_str = self.time_t.unwrap().rust_type().unwrap().into()
+                }
+                None if self.direction == ParameterDirection::In && is_bytes(env, self.typ) => {
+                    type_str = if *self.nullable {
+                        "Option<&[u8]>".into()
+                    } else {
+                        "&[u8]".into()
+                    }
+                }
                 None => {
                     let rust_type = parameter_rust_type(
                         env,
@@ -0,0 +1,79 @@
+use std::io::{Result, Write};
+
+use analysis::info_base::InfoBase;
+use env::Env;
+
+/// A custom generation step for needs of a binding not covered by the config.
+/// Passes are given to `codegen::generate_with_passes` and append their code to
+/// the file of each generated object and record.
+pub trait CodegenPass {
+    fn generate_type(&self, w: &mut Write, env: &Env, info: &InfoBase) -> Result<()>;
+}
+
+pub fn run(w: &mut Write, passes: &[Box<CodegenPass>], env: &Env, info: &InfoBase) -> Result<()> {
+    for pass in passes {
+        try!(pass.generate_type(w, env, info));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+    use std::io::Read;
+
+    use super::*;
+    use codegen;
+    use test_env;
+
+    struct NamePass;
+
+    impl CodegenPass for NamePass {
+        fn generate_type(&self, w: &mut Write, _: &Env, info: &InfoBase) -> Result<()> {
+            writeln!(w, "// custom {}", info.full_name)
+        }
+    }
+
+    const TYPES: &'static str = r#"
+    <class name="Widget" c:type="TestWidget" parent="GObject.Object"
+           glib:type-name="TestWidget" glib:get-type="test_widget_get_type">
+    </class>
+    <record name="Border" c:type="TestBorder" glib:type-name="TestBorder"
+            glib:get-type="test_border_get_type">
+    </record>"#;
+
+    const TYPES_OBJECTS: &'static str = r#"
+[[object]]
+name = "Test.Widget"
+status = "generate"
+
+[[object]]
+name = "Test.Border"
+status = "generate"
+"#;
+
+    #[test]
+    fn registered_pass_runs_for_each_type() {
+        let env = test_env::env(TYPES, "", TYPES_OBJECTS);
+        let passes: Vec<Box<CodegenPass>> = vec![Box::new(NamePass)];
+        codegen::generate_with_passes(&env, &passes);
+
+        let auto = env.config.target_path.join("src").join("auto");
+        let read = |file: &str| {
+            let mut code = String::new();
+            File::open(auto.join(file))
+                .unwrap()
+                .read_to_string(&mut code)
+                .unwrap();
+            code
+        };
+        let widget = read("widget.rs");
+        let border = read("border.rs");
+        fs::remove_dir_all(&env.config.target_path).unwrap();
+
+        assert!(widget.contains("glib_wrapper! {"));
+        assert!(widget.ends_with("\n// custom Test.Widget\n"));
+        assert!(border.contains("glib_wrapper! {"));
+        assert!(border.ends_with("\n// custom Test.Border\n"));
+    }
+}
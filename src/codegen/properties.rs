@@ -5,7 +5,7 @@ use analysis::properties::Property;
 use analysis::rust_type::{parameter_rust_type, rust_type};
 use chunk::Chunk;
 use env::Env;
-use super::general::version_condition;
+use super::general::{cfg_condition, version_condition};
 use library;
 use writer::primitives::tabs;
 use super::property_body;
@@ -50,6 +50,7 @@ fn generate_prop_func(
     try!(writeln!(w, ""));
 
     let decl = declaration(env, prop);
+    try!(cfg_condition(w, &prop.cfg_condition, commented, indent));
     try!(version_condition(w, env, prop.version, commented, indent));
     try!(writeln!(
         w,
@@ -136,6 +137,9 @@ fn body(prop: &Property) -> Chunk {
         .is_ref(prop.set_in_ref_mode.is_ref())
         .is_nullable(*prop.nullable)
         .conversion(prop.conversion);
+    if let Some(ref ffi_type) = prop.object_ffi_type {
+        builder.object_ffi_type(ffi_type);
+    }
     if let Some(ref default_value) = prop.default_value {
         builder.default_value(default_value);
     } else {
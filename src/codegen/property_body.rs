@@ -13,6 +13,7 @@ pub struct Builder {
     is_nullable: bool,
     is_into: bool,
     conversion: PropertyConversion,
+    object_ffi_type: Option<String>,
 }
 
 #[cfg_attr(feature = "cargo-clippy", allow(wrong_self_convention))]
@@ -68,6 +69,11 @@ impl Builder {
         self
     }
 
+    pub fn object_ffi_type(&mut self, value: &str) -> &mut Builder {
+        self.object_ffi_type = Some(value.into());
+        self
+    }
+
     pub fn generate(&self) -> Chunk {
         let chunks = if self.is_get {
             self.chunks_for_get()
@@ -95,6 +101,11 @@ impl Builder {
             base_tid: None,
             commented: false,
             bool_return_is_error: None,
+            date_time: None,
+            time_t: None,
+            bytes_to_vec: false,
+            invalid_char: None,
+            string_type: None,
         };
         let ffi_call = Chunk::FfiCall {
             name: self.get_ffi_func(),
@@ -115,6 +126,9 @@ impl Builder {
             )),
             _ => (),
         }
+        if let Some(ref ffi_type) = self.object_ffi_type {
+            body.push(Chunk::Custom(object_get_conversion(ffi_type)));
+        }
 
         let unsafe_ = Chunk::Unsafe(body);
 
@@ -129,7 +143,7 @@ impl Builder {
         });
         chunks.push(unsafe_);
 
-        if self.conversion == Direct {
+        if self.conversion == Direct && self.object_ffi_type.is_none() {
             let unwrap = if self.is_nullable { "" } else { ".unwrap()" };
             chunks.push(Chunk::Custom(format!("value.get(){}", unwrap)));
         }
@@ -164,6 +178,11 @@ impl Builder {
             base_tid: None,
             commented: false,
             bool_return_is_error: None,
+            date_time: None,
+            time_t: None,
+            bytes_to_vec: false,
+            invalid_char: None,
+            string_type: None,
         };
         body.push(Chunk::FfiCallConversion {
             ret: return_info,
@@ -228,3 +247,52 @@ impl Builder {
         }
     }
 }
+
+/// The `GValue` keeps its reference, so the returned wrapper takes
+/// a new one with `from_glib_none`.
+fn object_get_conversion(ffi_type: &str) -> String {
+    format!(
+        "from_glib_none(gobject_ffi::g_value_get_object(value.to_glib_none().0) as {})",
+        ffi_type
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn customs(chunk: &Chunk, out: &mut Vec<String>) {
+        match *chunk {
+            Chunk::BlockHalf(ref chs) | Chunk::Unsafe(ref chs) => for ch in chs {
+                customs(ch, out);
+            },
+            Chunk::Custom(ref s) => out.push(s.clone()),
+            _ => (),
+        }
+    }
+
+    #[test]
+    fn object_property_getter_uses_from_glib_none() {
+        let mut builder = Builder::new();
+        builder
+            .name("child")
+            .is_get(true)
+            .default_value("None::<&Widget>")
+            .object_ffi_type("*mut ffi::GtkWidget");
+        let mut out = Vec::new();
+        customs(&builder.generate(), &mut out);
+        assert!(out.contains(&"from_glib_none(gobject_ffi::g_value_get_object(\
+                                value.to_glib_none().0) as *mut ffi::GtkWidget)"
+            .to_owned()));
+        assert!(!out.iter().any(|s| s.starts_with("value.get()")));
+    }
+
+    #[test]
+    fn plain_property_getter_uses_value_get() {
+        let mut builder = Builder::new();
+        builder.name("label").is_get(true).default_value("None::<&str>").is_nullable(true);
+        let mut out = Vec::new();
+        customs(&builder.generate(), &mut out);
+        assert!(out.contains(&"value.get()".to_owned()));
+    }
+}
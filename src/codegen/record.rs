@@ -12,7 +12,15 @@ pub fn generate(w: &mut Write, env: &Env, analysis: &analysis::record::Info) ->
     try!(general::start_comments(w, &env.config));
     try!(general::uses(w, env, &analysis.imports));
 
-    if let (Some(ref_fn), Some(unref_fn)) = (
+    if analysis.boxed_inline {
+        try!(general::define_boxed_inline_type(
+            w,
+            &analysis.name,
+            &type_.c_type,
+            analysis.glib_get_type.as_ref().unwrap(),
+            &analysis.extra_derives,
+        ));
+    } else if let (Some(ref_fn), Some(unref_fn)) = (
         analysis.specials.get(&Type::Ref),
         analysis.specials.get(&Type::Unref),
     ) {
@@ -24,6 +32,9 @@ pub fn generate(w: &mut Write, env: &Env, analysis: &analysis::record::Info) ->
             unref_fn,
             &analysis.glib_get_type,
         ));
+        if env.config.generate_drop_asserts {
+            try!(general::define_drop_assert(w, &analysis.name, &type_.c_type, "mut"));
+        }
     } else if let (Some(copy_fn), Some(free_fn)) = (
         analysis.specials.get(&Type::Copy),
         analysis.specials.get(&Type::Free),
@@ -36,6 +47,19 @@ pub fn generate(w: &mut Write, env: &Env, analysis: &analysis::record::Info) ->
             free_fn,
             &analysis.glib_get_type,
         ));
+        if env.config.generate_drop_asserts {
+            try!(general::define_drop_assert(w, &analysis.name, &type_.c_type, "const"));
+        }
+    } else if let Some(ref get_type_fn) = analysis.glib_get_type {
+        try!(general::define_auto_boxed_type(
+            w,
+            &analysis.name,
+            &type_.c_type,
+            get_type_fn,
+        ));
+        if env.config.generate_drop_asserts {
+            try!(general::define_drop_assert(w, &analysis.name, &type_.c_type, "const"));
+        }
     } else {
         panic!(
             "Missing memory management functions for {}",
@@ -43,6 +67,29 @@ pub fn generate(w: &mut Write, env: &Env, analysis: &analysis::record::Info) ->
         );
     }
 
+    if !analysis.boxed_inline {
+        try!(general::define_into_glib_ptr(
+            w,
+            &analysis.name,
+            &type_.c_type
+        ));
+    }
+
+    if env.config.expose_ptr && !analysis.boxed_inline {
+        let is_shared = analysis.specials.get(&Type::Ref).is_some() &&
+            analysis.specials.get(&Type::Unref).is_some();
+        try!(general::define_ptr_accessors(
+            w,
+            &analysis.name,
+            &type_.c_type,
+            if is_shared { "mut" } else { "const" }
+        ));
+    }
+
+    if !analysis.array_setters.is_empty() {
+        try!(generate_array_setters(w, &analysis.name, &analysis.array_setters));
+    }
+
     if analysis.functions.iter().any(|f| !f.visibility.hidden()) {
         try!(writeln!(w, ""));
         try!(write!(w, "impl {} {{", analysis.name));
@@ -61,14 +108,82 @@ pub fn generate(w: &mut Write, env: &Env, analysis: &analysis::record::Info) ->
         &analysis.functions
     ));
 
+    if !analysis.default_fields.is_empty() {
+        try!(general::define_field_wise_default(
+            w,
+            &analysis.name,
+            &type_.c_type,
+            &analysis.default_fields
+        ));
+    }
+
     try!(trait_impls::generate(
         w,
         &analysis.name,
         &analysis.functions,
         &analysis.specials,
         None,
+        analysis.generate_eq,
     ));
 
+    if let Some(ref as_ref_str) = analysis.as_ref_str {
+        try!(trait_impls::generate_as_ref_str(
+            w,
+            env,
+            &analysis.name,
+            as_ref_str
+        ));
+    }
+
+    if env.config.generate_value_from && analysis.glib_get_type.is_some() {
+        try!(writeln!(w, "\n{}", general::value_from_impl(&analysis.name)));
+    }
+
+    if let Some(ref eq_str) = analysis.eq_str {
+        try!(trait_impls::generate_eq_str(w, env, &analysis.name, eq_str));
+    }
+
+    if let Some(ref iterator_next) = analysis.iterator_next {
+        try!(trait_impls::generate_iterator(
+            w,
+            env,
+            &analysis.name,
+            iterator_next
+        ));
+    }
+
+    if let Some(ref index_getter) = analysis.index_getter {
+        try!(trait_impls::generate_index_getter(
+            w,
+            env,
+            &analysis.name,
+            index_getter
+        ));
+    }
+
+    if let Some(ref from_str) = analysis.from_str {
+        try!(trait_impls::generate_from_str(
+            w,
+            env,
+            &analysis.name,
+            from_str
+        ));
+    }
+
+    if let Some(ref borrowed_type) = analysis.borrowed_type {
+        try!(trait_impls::generate_borrow(w, &analysis.name, borrowed_type));
+    }
+
+    if analysis.identity_eq {
+        let is_shared = analysis.specials.get(&Type::Ref).is_some();
+        try!(trait_impls::generate_identity_eq(
+            w,
+            &analysis.name,
+            &type_.c_type,
+            if is_shared { "mut" } else { "const" },
+        ));
+    }
+
     if analysis.concurrency != library::Concurrency::None {
         try!(writeln!(w, ""));
     }
@@ -113,3 +228,146 @@ pub fn generate_reexports(
         analysis.name
     ));
 }
+
+fn generate_array_setters(
+    w: &mut Write,
+    type_name: &str,
+    setters: &[analysis::record::ArraySetter],
+) -> Result<()> {
+    try!(writeln!(w, ""));
+    try!(writeln!(w, "impl {} {{", type_name));
+    for (pos, setter) in setters.iter().enumerate() {
+        if pos > 0 {
+            try!(writeln!(w, ""));
+        }
+        try!(writeln!(
+            w,
+            "\tpub fn {}(&mut self, {}: &[{}]) {{",
+            setter.name,
+            setter.field_name,
+            setter.element_type
+        ));
+        try!(writeln!(
+            w,
+            "\t\tassert_eq!({}.len(), {}, \"{} expects {} elements\");",
+            setter.field_name,
+            setter.size,
+            setter.name,
+            setter.size
+        ));
+        try!(writeln!(
+            w,
+            "\t\tself.0.{0}.copy_from_slice({0});",
+            setter.field_name
+        ));
+        try!(writeln!(w, "\t}}"));
+    }
+    writeln!(w, "}}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use analysis::record::ArraySetter;
+    use test_env;
+
+    #[test]
+    fn fixed_array_field_setter_checks_length() {
+        let setters = vec![
+            ArraySetter {
+                name: "set_data".into(),
+                field_name: "data".into(),
+                element_type: "u8",
+                size: 16,
+            },
+        ];
+        let mut buf = Vec::new();
+        generate_array_setters(&mut buf, "Uuid", &setters).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "
+impl Uuid {
+\tpub fn set_data(&mut self, data: &[u8]) {
+\t\tassert_eq!(data.len(), 16, \"set_data expects 16 elements\");
+\t\tself.0.data.copy_from_slice(data);
+\t}
+}
+"
+        );
+    }
+
+    const BYTES: &'static str = r#"
+    <record name="Bytes" c:type="TestBytes" glib:type-name="TestBytes"
+            glib:get-type="test_bytes_get_type">
+      <method name="ref" c:identifier="test_bytes_ref">
+        <return-value transfer-ownership="full">
+          <type name="Bytes" c:type="TestBytes*"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="bytes" transfer-ownership="none">
+            <type name="Bytes" c:type="TestBytes*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+      <method name="unref" c:identifier="test_bytes_unref">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="bytes" transfer-ownership="none">
+            <type name="Bytes" c:type="TestBytes*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+    </record>"#;
+
+    #[test]
+    fn drop_asserts_are_generated_on_request() {
+        let code = test_env::generated_object(BYTES, "", "Bytes", "");
+        assert!(!code.contains("impl Drop for Bytes"));
+
+        let options = "generate_drop_asserts = true";
+        let code = test_env::generated_object(BYTES, options, "Bytes", "");
+        assert!(code.contains(
+            "impl Drop for Bytes {\n    fn drop(&mut self) {\n        #[cfg(debug_assertions)]"
+        ));
+        assert!(code.contains("let ptr: *mut ffi::TestBytes = self.to_glib_none().0;"));
+    }
+
+    const RGBA: &'static str = r#"
+    <record name="RGBA" c:type="TestRGBA" glib:type-name="TestRGBA"
+            glib:get-type="test_rgba_get_type">
+      <field name="red" writable="1">
+        <type name="gdouble" c:type="gdouble"/>
+      </field>
+      <field name="alpha" writable="1">
+        <type name="gdouble" c:type="gdouble"/>
+      </field>
+    </record>"#;
+
+    #[test]
+    fn boxed_inline_type_is_stored_by_value() {
+        let code = test_env::generated_object(RGBA, "", "RGBA", "boxed_inline = true");
+        assert!(code.contains(
+            "
+#[derive(Clone, Copy)]
+#[repr(C)]
+#[doc(alias = \"TestRGBA\")]
+pub struct RGBA(ffi::TestRGBA);
+"
+        ));
+        assert!(code.contains(
+            "impl StaticType for RGBA {
+    fn static_type() -> Type {
+        unsafe { from_glib(ffi::test_rgba_get_type()) }
+    }
+}"
+        ));
+        assert!(code.contains("RGBA(*ptr)"));
+        assert!(!code.contains("glib_wrapper!"));
+        assert!(!code.contains("into_glib_ptr"));
+
+        let code = test_env::generated_object(RGBA, "", "RGBA", "");
+        assert!(code.contains("pub struct RGBA(Boxed<ffi::TestRGBA>);"));
+    }
+}
@@ -3,14 +3,24 @@ use std::path::Path;
 use env::Env;
 use file_saver::*;
 use nameutil::*;
+use super::passes::{self, CodegenPass};
 
-pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
+pub fn generate(
+    env: &Env,
+    root_path: &Path,
+    crate_name: Option<&str>,
+    passes: &[Box<CodegenPass>],
+    mod_rs: &mut Vec<String>,
+) {
     info!("Generate records");
     for record_analysis in env.analysis.records.values() {
         let obj = &env.config.objects[&record_analysis.full_name];
         if !obj.status.need_generate() {
             continue;
         }
+        if env.config.partitions.crate_of(&record_analysis.full_name) != crate_name {
+            continue;
+        }
 
         let mod_name = obj.module_name.clone().unwrap_or_else(|| {
             module_name(split_namespace_name(&record_analysis.full_name).1)
@@ -21,7 +31,8 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
         info!("Generating file {:?}", path);
 
         save_to_file(path, env.config.make_backup, |w| {
-            super::record::generate(w, env, record_analysis)
+            try!(super::record::generate(w, env, record_analysis));
+            passes::run(w, passes, env, &record_analysis.base)
         });
 
         super::record::generate_reexports(env, record_analysis, &mod_name, mod_rs);
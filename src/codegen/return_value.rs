@@ -4,6 +4,7 @@ use env::Env;
 use library::{self, ParameterDirection};
 use analysis::conversion_type::ConversionType;
 use analysis::rust_type::parameter_rust_type;
+use config::StringType;
 use traits::*;
 use nameutil;
 
@@ -27,90 +28,124 @@ impl ToReturnValue for library::Parameter {
 
 impl ToReturnValue for analysis::return_value::Info {
     fn to_return_value(&self, env: &Env) -> String {
-        match self.parameter {
-            Some(ref par) => par.to_return_value(env),
-            None => String::new(),
+        let time_type = self.date_time.or(self.time_t).and_then(|t| t.rust_type());
+        match (&self.parameter, time_type) {
+            (&Some(ref par), Some(type_)) if *par.nullable => format!(" -> Option<{}>", type_),
+            (&Some(_), Some(type_)) => format!(" -> {}", type_),
+            (&Some(ref par), None) if self.bytes_to_vec => if *par.nullable {
+                " -> Option<Vec<u8>>".into()
+            } else {
+                " -> Vec<u8>".into()
+            },
+            (&Some(_), None) if self.invalid_char.is_some() => {
+                format!(" -> {}", self.invalid_char.unwrap().rust_type())
+            }
+            (&Some(ref par), None) if self.string_type == Some(StringType::GString) => {
+                string_return_value(StringType::GString, *par.nullable)
+            }
+            (&Some(ref par), None) => par.to_return_value(env),
+            (&None, _) => String::new(),
         }
     }
 }
 
+/// `from_glib_none`/`from_glib_full` convert into the returned type by inference.
+fn string_return_value(string_type: StringType, nullable: bool) -> String {
+    if nullable {
+        format!(" -> Option<{}>", string_type.rust_type())
+    } else {
+        format!(" -> {}", string_type.rust_type())
+    }
+}
+
 pub fn out_parameter_as_return_parts(
     analysis: &analysis::functions::Info,
-) -> (&'static str, &'static str) {
+) -> (&'static str, String) {
     use analysis::out_parameters::Mode::*;
-    let num_outs = analysis
-        .outs
-        .iter()
-        .filter(|p| p.array_length.is_none())
-        .count();
+    let num_outs = analysis.outs.params.len() - array_length_outs(analysis).len();
     match analysis.outs.mode {
         Normal | Combined => if num_outs > 1 {
-            ("(", ")")
+            ("(", ")".into())
         } else {
-            ("", "")
+            ("", "".into())
         },
         Optional => if num_outs > 1 {
-            ("Option<(", ")>")
+            ("Option<(", ")>".into())
         } else {
-            ("Option<", ">")
+            ("Option<", ">".into())
         },
-        Throws(..) => {
-            if num_outs == 1 + 1 {
-                //if only one parameter except "glib::Error"
-                ("Result<", ", Error>")
-            } else {
-                ("Result<(", "), Error>")
-            }
-        }
+        Throws(..) => throws_return_parts(num_outs, analysis.error_type.as_ref()),
         None => unreachable!(),
     }
 }
 
+/// A configured error domain enum is returned instead of `glib::Error`.
+fn throws_return_parts(num_outs: usize, error_type: Option<&String>) -> (&'static str, String) {
+    let error_type = error_type.map_or("Error", |s| &s[..]);
+    if num_outs == 1 + 1 {
+        //if only one parameter except "glib::Error"
+        ("Result<", format!(", {}>", error_type))
+    } else {
+        ("Result<(", format!("), {}>", error_type))
+    }
+}
+
 pub fn out_parameters_as_return(env: &Env, analysis: &analysis::functions::Info) -> String {
     let (prefix, suffix) = out_parameter_as_return_parts(analysis);
     let mut return_str = String::with_capacity(100);
     return_str.push_str(" -> ");
     return_str.push_str(prefix);
 
-    let array_lengths: Vec<_> = analysis
-        .outs
-        .iter()
-        .filter_map(|p| p.array_length)
-        .collect();
+    let length_outs = array_length_outs(analysis);
 
-    let mut skip = 0;
-    for (pos, par) in analysis.outs.iter().filter(|par| !par.is_error).enumerate() {
-        // The actual return value is inserted with an empty name at position 0
-        if !par.name.is_empty() {
-            let mangled_par_name = nameutil::mangle_keywords(par.name.as_str());
-            let param_pos = analysis
-                .parameters
-                .c_parameters
-                .iter()
-                .enumerate()
-                .filter_map(|(pos, orig_par)| if orig_par.name == mangled_par_name {
-                    Some(pos)
-                } else {
-                    None
-                })
-                .next()
-                .unwrap();
-            if array_lengths.contains(&(param_pos as u32)) {
-                skip += 1;
-                continue;
-            }
+    let mut first = true;
+    for (pos, par) in analysis.outs.iter().enumerate() {
+        if par.is_error || length_outs.contains(&pos) {
+            continue;
         }
 
-        if pos > skip {
+        if !first {
             return_str.push_str(", ")
         }
+        first = false;
         let s = out_parameter_as_return(par, env);
         return_str.push_str(&s);
     }
-    return_str.push_str(suffix);
+    return_str.push_str(&suffix);
     return_str
 }
 
+fn array_length_outs(analysis: &analysis::functions::Info) -> Vec<usize> {
+    let c_parameter_names: Vec<&str> = analysis
+        .parameters
+        .c_parameters
+        .iter()
+        .map(|par| &par.name[..])
+        .collect();
+    length_out_positions(&analysis.outs.params, &c_parameter_names)
+}
+
+/// Positions of the outs holding the length of an out array,
+/// they aren't returned on their own but folded into the returned `Vec`.
+fn length_out_positions(outs: &[library::Parameter], c_parameter_names: &[&str]) -> Vec<usize> {
+    let array_lengths: Vec<u32> = outs.iter().filter_map(|par| par.array_length).collect();
+    outs.iter()
+        .enumerate()
+        .filter(|&(_, par)| {
+            // The actual return value is inserted with an empty name at position 0
+            if par.name.is_empty() {
+                return false;
+            }
+            let mangled_par_name = nameutil::mangle_keywords(par.name.as_str());
+            c_parameter_names
+                .iter()
+                .position(|name| *name == mangled_par_name)
+                .map_or(false, |pos| array_lengths.contains(&(pos as u32)))
+        })
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
 fn out_parameter_as_return(par: &library::Parameter, env: &Env) -> String {
     //TODO: upcasts?
     let rust_type = parameter_rust_type(
@@ -126,3 +161,28 @@ fn out_parameter_as_return(par: &library::Parameter, env: &Env) -> String {
         _ => name,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throws_with_error_type() {
+        let error_type = "FileError".to_owned();
+        assert_eq!(
+            throws_return_parts(2, Some(&error_type)),
+            ("Result<", ", FileError>".to_owned())
+        );
+        assert_eq!(
+            throws_return_parts(3, Some(&error_type)),
+            ("Result<(", "), FileError>".to_owned())
+        );
+        assert_eq!(throws_return_parts(2, None), ("Result<", ", Error>".to_owned()));
+    }
+
+    #[test]
+    fn gstring_return_value() {
+        assert_eq!(string_return_value(StringType::GString, false), " -> GString");
+        assert_eq!(string_return_value(StringType::GString, true), " -> Option<GString>");
+    }
+}
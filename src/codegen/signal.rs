@@ -5,7 +5,7 @@ use library;
 use chunk::Chunk;
 use consts::TYPE_PARAMETERS_START;
 use env::Env;
-use super::general::{doc_hidden, version_condition};
+use super::general::{cfg_condition, doc_hidden, version_condition};
 use super::signal_body;
 use super::trampoline::func_string;
 use writer::primitives::tabs;
@@ -29,6 +29,7 @@ pub fn generate(
     let suffix = if only_declaration { ";" } else { " {" };
 
     try!(writeln!(w, ""));
+    try!(cfg_condition(w, &analysis.cfg_condition, commented, indent));
     try!(version_condition(
         w,
         env,
@@ -37,6 +38,10 @@ pub fn generate(
         indent,
     ));
     try!(doc_hidden(w, analysis.doc_hidden, comment_prefix, indent));
+    // Trait impls get it from the declaration in the trait
+    if env.config.must_use_connect && (!in_trait || only_declaration) {
+        try!(must_use(w, comment_prefix, indent));
+    }
     try!(writeln!(
         w,
         "{}{}{}{}{}",
@@ -73,6 +78,7 @@ pub fn generate(
 
     if let Some(ref emit_name) = analysis.action_emit_name {
         try!(writeln!(w, ""));
+        try!(cfg_condition(w, &analysis.cfg_condition, commented, indent));
         try!(version_condition(
             w,
             env,
@@ -194,6 +200,11 @@ fn declaration(analysis: &analysis::signals::Info, function_type: &Option<String
     )
 }
 
+/// Keeps the `SignalHandlerId` needed for disconnecting from being dropped by accident.
+fn must_use(w: &mut Write, comment_prefix: &str, indent: usize) -> Result<()> {
+    writeln!(w, "{}{}#[must_use]", tabs(indent), comment_prefix)
+}
+
 fn bounds(function_type: &Option<String>) -> String {
     match *function_type {
         Some(ref type_) => format!("F: {}", type_),
@@ -212,3 +223,16 @@ fn body(analysis: &analysis::signals::Info, function_type: &str, in_trait: bool)
 
     builder.generate()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connect_must_use() {
+        let mut buf = Vec::new();
+        must_use(&mut buf, "", 1).unwrap();
+        must_use(&mut buf, "//", 0).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "\t#[must_use]\n//#[must_use]\n");
+    }
+}
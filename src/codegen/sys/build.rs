@@ -32,17 +32,10 @@ use std::env;
 use std::io::prelude::*;
 use std::io;
 use std::process;
-
-fn main() {
-    if let Err(s) = find() {
-        let _ = writeln!(io::stderr(), "{}", s);
-        process::exit(1);
-    }
-}
-
-fn find() -> Result<(), Error> {
 "##
     ));
+    try!(write!(w, "{}", main_fn(env.config.generate_dox_stubs)));
+    try!(writeln!(w, "fn find() -> Result<(), Error> {{"));
 
     let ns = env.namespaces.main();
     let regex = Regex::new(r"^lib(.+)\.(so.*|dylib)$").expect("Regex failed");
@@ -136,3 +129,47 @@ fn find() -> Result<(), Error> {
 "##
     )
 }
+
+/// Declaring the FFI functions doesn't need the library, only linking does.
+/// With `dox` stubs the library isn't searched, so the docs build without it.
+fn main_fn(dox_stubs: bool) -> &'static str {
+    if dox_stubs {
+        r##"
+#[cfg(feature = "dox")]
+fn main() {} // The docs are built without linking the library
+
+#[cfg(not(feature = "dox"))]
+fn main() {
+    if let Err(s) = find() {
+        let _ = writeln!(io::stderr(), "{}", s);
+        process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "dox"))]
+"##
+    } else {
+        r##"
+fn main() {
+    if let Err(s) = find() {
+        let _ = writeln!(io::stderr(), "{}", s);
+        process::exit(1);
+    }
+}
+
+"##
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dox_stub_skips_library_search() {
+        let main = main_fn(true);
+        assert!(main.contains("#[cfg(feature = \"dox\")]\nfn main() {}"));
+        assert!(main.ends_with("#[cfg(not(feature = \"dox\"))]\n"));
+        assert!(!main_fn(false).contains("dox"));
+    }
+}
@@ -258,6 +258,10 @@ fn escape_string(s: &str) -> String {
     es
 }
 
+/// C enums are declared as `c_int` aliases with a constant per member instead of
+/// Rust enums: that's the storage of a C enum, and values unknown to the bindings
+/// passed by C stay valid. The API enums convert from these by matching, with
+/// unknown values kept in `__Unknown(i32)`, so they need no `#[repr]`.
 fn generate_enums(w: &mut Write, env: &Env, items: &[&Enumeration]) -> Result<()> {
     if !items.is_empty() {
         try!(writeln!(w, "// Enums"));
@@ -317,7 +321,7 @@ fn generate_unions(w: &mut Write, env: &Env, items: &[&Union]) -> Result<()> {
 
     for item in items {
         if let Some(ref c_type) = item.c_type {
-            let (lines, commented) = generate_fields(env, &item.name, &item.fields);
+            let (lines, commented) = generate_fields(env, &item.name, &item.fields, true);
 
             let comment = if commented { "//" } else { "" };
             if lines.is_empty() {
@@ -328,12 +332,12 @@ fn generate_unions(w: &mut Write, env: &Env, items: &[&Union]) -> Result<()> {
                     c_type
                 ));
             } else {
-                try!(writeln!(
-                    w,
-                    "{0}#[repr(C)]\n{0}#[derive(Copy,Clone)]\n{0}pub union {1} {{",
-                    comment,
-                    c_type
-                ));
+                let derive = fields_derive(contains_atomic(env, &item.name, &item.fields), false);
+                try!(writeln!(w, "{}#[repr(C)]", comment));
+                if !derive.is_empty() {
+                    try!(write!(w, "{}{}", comment, derive));
+                }
+                try!(writeln!(w, "{}pub union {} {{", comment, c_type));
 
                 for line in &lines {
                     try!(writeln!(w, "{}{}", comment, line));
@@ -405,7 +409,7 @@ fn generate_classes_structs(w: &mut Write, env: &Env, classes: &[&Class]) -> Res
         try!(writeln!(w, "// Classes"));
     }
     for klass in classes {
-        let (lines, commented) = generate_fields(env, &klass.name, &klass.fields);
+        let (lines, commented) = generate_fields(env, &klass.name, &klass.fields, false);
 
         let comment = if commented { "//" } else { "" };
         if lines.is_empty() {
@@ -427,8 +431,10 @@ fn generate_classes_structs(w: &mut Write, env: &Env, classes: &[&Class]) -> Res
                 w,
                 "{comment}#[repr(C)]\n{debug}{comment}pub struct {name} {{",
                 comment = comment,
-                debug = if can_generate_fields_debug { "#[derive(Copy,Clone,Debug)]\n" }
-                        else { "#[derive(Copy,Clone)]\n" },
+                debug = fields_derive(
+                    contains_atomic(env, &klass.name, &klass.fields),
+                    can_generate_fields_debug,
+                ),
                 name = klass.c_type,
             ));
 
@@ -484,7 +490,7 @@ fn generate_records(w: &mut Write, env: &Env, records: &[&Record]) -> Result<()>
         try!(writeln!(w, "// Records"));
     }
     for record in records {
-        let (lines, commented) = generate_fields(env, &record.name, &record.fields);
+        let (lines, commented) = generate_fields(env, &record.name, &record.fields, false);
 
         let comment = if commented { "//" } else { "" };
         if lines.is_empty() {
@@ -510,8 +516,10 @@ fn generate_records(w: &mut Write, env: &Env, records: &[&Record]) -> Result<()>
                 w,
                 "{}#[repr(C)]\n{}{0}pub struct {} {{",
                 comment,
-                if can_generate_fields_debug(&record.fields) { "#[derive(Copy,Clone,Debug)]\n" }
-                else { "#[derive(Copy,Clone)]\n" },
+                fields_derive(
+                    contains_atomic(env, &record.name, &record.fields),
+                    can_generate_fields_debug(&record.fields),
+                ),
                 record.c_type
             ));
             for line in &lines {
@@ -540,10 +548,82 @@ fn is_union_special_case(c_type: &Option<String>) -> bool {
     }
 }
 
-fn generate_fields(env: &Env, struct_name: &str, fields: &[Field]) -> (Vec<String>, bool) {
+/// Fields configured as `atomic_fields` of the object named `struct_name`
+fn atomic_fields<'a>(env: &'a Env, struct_name: &str) -> &'a [String] {
+    let full_name = format!("{}.{}", env.namespaces.main().name, struct_name);
+    env.config
+        .objects
+        .get(&full_name)
+        .map(|obj| &obj.atomic_fields[..])
+        .unwrap_or(&[])
+}
+
+/// Whether the struct or union named `struct_name` has atomic fields,
+/// also counting those of structs and unions embedded by value
+fn contains_atomic(env: &Env, struct_name: &str, fields: &[Field]) -> bool {
+    !atomic_fields(env, struct_name).is_empty() || fields.iter().any(|f| embeds_atomic(env, f))
+}
+
+fn embeds_atomic(env: &Env, field: &Field) -> bool {
+    // Atomic fields are only known for the namespace being generated
+    if field.typ.ns_id != MAIN_NAMESPACE {
+        return false;
+    }
+    if let Some(ref c_type) = field.c_type {
+        if !rustify_pointers(c_type).0.is_empty() {
+            return false;
+        }
+    }
+    match *env.library.type_(field.typ) {
+        Type::Record(ref record) => contains_atomic(env, &record.name, &record.fields),
+        Type::Union(ref item) => contains_atomic(env, &item.name, &item.fields),
+        _ => false,
+    }
+}
+
+/// Atomics are neither `Copy` nor `Clone`, structs containing them can only derive `Debug`
+fn fields_derive(atomic: bool, debug: bool) -> &'static str {
+    match (atomic, debug) {
+        (false, true) => "#[derive(Copy,Clone,Debug)]\n",
+        (false, false) => "#[derive(Copy,Clone)]\n",
+        (true, true) => "#[derive(Debug)]\n",
+        (true, false) => "",
+    }
+}
+
+/// Atomic type with the same layout as the FFI type of a field
+fn atomic_type(ffi_type: &str) -> Option<String> {
+    let atomic = match ffi_type {
+        "i32" | "c_int" | "gboolean" => "AtomicI32",
+        "u32" | "c_uint" => "AtomicU32",
+        "i64" => "AtomicI64",
+        "u64" => "AtomicU64",
+        "ssize_t" | "intptr_t" => "AtomicIsize",
+        "size_t" | "uintptr_t" => "AtomicUsize",
+        "gpointer" => return Some("::std::sync::atomic::AtomicPtr<c_void>".into()),
+        _ => {
+            if ffi_type.starts_with("*mut ") {
+                return Some(format!(
+                    "::std::sync::atomic::AtomicPtr<{}>",
+                    &ffi_type["*mut ".len()..]
+                ));
+            }
+            return None;
+        }
+    };
+    Some(format!("::std::sync::atomic::{}", atomic))
+}
+
+fn generate_fields(
+    env: &Env,
+    struct_name: &str,
+    fields: &[Field],
+    in_union: bool,
+) -> (Vec<String>, bool) {
     let mut lines = Vec::new();
     let mut commented = false;
     let mut truncated = false;
+    let atomic_fields = atomic_fields(env, struct_name);
 
     //TODO: remove after GObject-2.0.gir fixed
     // Fix for wrong GValue size on i686-pc-windows-gnu due `c:type="gpointer"` in data field
@@ -626,7 +706,27 @@ fn generate_fields(env: &Env, struct_name: &str, fields: &[Field]) -> (Vec<Strin
             if c_type.is_err() {
                 commented = true;
             }
-            lines.push(format!("\tpub {}: {},", name, c_type.into_string()));
+            let mut c_type = c_type.into_string();
+            let mut atomic = false;
+            if atomic_fields.contains(&field.name) {
+                match atomic_type(&c_type) {
+                    Some(atomic_c_type) => {
+                        c_type = atomic_c_type;
+                        atomic = true;
+                    }
+                    None => warn!(
+                        "Field `{}::{}` of type `{}` can't be atomic",
+                        struct_name,
+                        field.name,
+                        c_type
+                    ),
+                }
+            }
+            if in_union && (atomic || embeds_atomic(env, field)) {
+                // Union fields must be `Copy` unless wrapped in `ManuallyDrop`
+                c_type = format!("::std::mem::ManuallyDrop<{}>", c_type);
+            }
+            lines.push(format!("\tpub {}: {},", name, c_type));
         } else {
             let name = mangle_keywords(&*field.name);
             if let Some(func) = env.library.type_(field.typ).maybe_ref_as::<Function>() {
@@ -696,4 +796,138 @@ mod tests {
             "extern crate some_crate_sys as gdk_pixbuf;\n".to_owned()
         );
     }
+
+    #[test]
+    fn atomic_field_types() {
+        assert_eq!(
+            atomic_type("c_uint"),
+            Some("::std::sync::atomic::AtomicU32".to_owned())
+        );
+        assert_eq!(
+            atomic_type("c_int"),
+            Some("::std::sync::atomic::AtomicI32".to_owned())
+        );
+        assert_eq!(
+            atomic_type("gpointer"),
+            Some("::std::sync::atomic::AtomicPtr<c_void>".to_owned())
+        );
+        assert_eq!(
+            atomic_type("*mut GList"),
+            Some("::std::sync::atomic::AtomicPtr<GList>".to_owned())
+        );
+        assert_eq!(atomic_type("c_double"), None);
+        assert_eq!(atomic_type("*const c_char"), None);
+    }
+
+    #[test]
+    fn atomic_fields_derive_no_copy() {
+        assert_eq!(fields_derive(false, true), "#[derive(Copy,Clone,Debug)]\n");
+        assert_eq!(fields_derive(true, true), "#[derive(Debug)]\n");
+        assert_eq!(fields_derive(true, false), "");
+    }
+
+    const COUNTERS: &'static str = r#"
+    <record name="Counter" c:type="TestCounter">
+      <field name="ref_count" writable="1">
+        <type name="gint" c:type="gint"/>
+      </field>
+    </record>
+    <record name="Holder" c:type="TestHolder">
+      <field name="counter" writable="1">
+        <type name="Counter" c:type="TestCounter"/>
+      </field>
+      <field name="next" writable="1">
+        <type name="Holder" c:type="TestHolder*"/>
+      </field>
+    </record>
+    <union name="Either" c:type="TestEither">
+      <field name="holder" writable="1">
+        <type name="Holder" c:type="TestHolder"/>
+      </field>
+      <field name="value" writable="1">
+        <type name="gint" c:type="gint"/>
+      </field>
+    </union>"#;
+
+    #[test]
+    fn embedded_atomics_prevent_copy() {
+        use test_env;
+
+        let objects = r#"
+[[object]]
+name = "Test.Counter"
+status = "generate"
+atomic_fields = ["ref_count"]
+"#;
+        let env = test_env::env(COUNTERS, "", objects);
+        let lookup = |name: &str| env.library.find_type(0, name).map(|tid| env.type_(tid)).unwrap();
+        let records: Vec<&Record> = ["Test.Counter", "Test.Holder"]
+            .iter()
+            .map(|name| lookup(name).maybe_ref_as::<Record>().unwrap())
+            .collect();
+        let unions = vec![lookup("Test.Either").maybe_ref_as::<Union>().unwrap()];
+
+        let code = test_env::generated(|w| generate_records(w, &env, &records));
+        assert!(code.contains("#[repr(C)]\npub struct TestCounter {"));
+        assert!(code.contains("\tpub ref_count: ::std::sync::atomic::AtomicI32,"));
+        assert!(code.contains("#[repr(C)]\npub struct TestHolder {"));
+        assert!(code.contains("\tpub next: *mut TestHolder,"));
+        assert!(!code.contains("Copy"));
+
+        let code = test_env::generated(|w| generate_unions(w, &env, &unions));
+        assert!(code.contains("#[repr(C)]\npub union TestEither {"));
+        assert!(code.contains("\tpub holder: ::std::mem::ManuallyDrop<TestHolder>,"));
+        assert!(code.contains("\tpub value: c_int,"));
+        assert!(!code.contains("Copy"));
+    }
+
+    const POINTS: &'static str = r#"
+    <record name="Point" c:type="TestPoint">
+      <field name="x" writable="1">
+        <type name="gint" c:type="gint"/>
+      </field>
+      <field name="y" writable="1">
+        <type name="gint" c:type="gint"/>
+      </field>
+    </record>
+    <function name="add" c:identifier="test_add">
+      <return-value transfer-ownership="none">
+        <type name="gint" c:type="gint"/>
+      </return-value>
+      <parameters>
+        <parameter name="a" transfer-ownership="none">
+          <type name="gint" c:type="gint"/>
+        </parameter>
+        <parameter name="b" transfer-ownership="none">
+          <type name="gint" c:type="gint"/>
+        </parameter>
+      </parameters>
+    </function>"#;
+
+    #[test]
+    fn functions_are_declared_in_extern_block() {
+        use test_env;
+
+        let env = test_env::env(POINTS, "work_mode = \"sys\"", "");
+        let code = test_env::generated(|w| generate_lib(w, &env));
+        assert!(code.contains(
+            "extern \"C\" {\n\n    \
+             //=========================================================================\n    \
+             // Other functions\n    \
+             //=========================================================================\n    \
+             pub fn test_add(a: c_int, b: c_int) -> c_int;\n\n}"
+        ));
+    }
+
+    #[test]
+    fn records_are_repr_c_structs() {
+        use test_env;
+
+        let env = test_env::env(POINTS, "work_mode = \"sys\"", "");
+        let code = test_env::generated(|w| generate_lib(w, &env));
+        assert!(code.contains(
+            "#[repr(C)]\n#[derive(Copy,Clone)]\npub struct TestPoint {\n\
+             \tpub x: c_int,\n\tpub y: c_int,\n}"
+        ));
+    }
 }
@@ -1,6 +1,13 @@
 use std::io::{Result, Write};
+use analysis::{as_ref_str, eq_str, from_str, index_getter, iterator_next, supertypes};
+use analysis::conversion_type::ConversionType;
+use analysis::namespaces;
 use analysis::functions::Info;
 use analysis::special_functions::{Infos, Type};
+use codegen::general::version_condition;
+use config::gobjects::IteratorEnd;
+use env::Env;
+use library::Transfer;
 
 pub fn generate(
     w: &mut Write,
@@ -8,6 +15,7 @@ pub fn generate(
     functions: &[Info],
     specials: &Infos,
     trait_name: Option<&str>,
+    generate_eq: bool,
 ) -> Result<()> {
     for (type_, name) in specials.iter() {
         match *type_ {
@@ -19,6 +27,9 @@ pub fn generate(
                         lookup(functions, name),
                         trait_name,
                     ));
+                } else {
+                    // `Ord` requires `Eq`, whichever function implements `PartialEq`
+                    try!(generate_eq_marker(w, type_name));
                 }
                 try!(generate_ord(
                     w,
@@ -28,12 +39,15 @@ pub fn generate(
                 ));
             }
             Type::Equal => {
-                try!(generate_eq(
+                try!(generate_partial_eq(
                     w,
                     type_name,
                     lookup(functions, name),
                     trait_name
                 ));
+                if generate_eq && specials.get(&Type::Compare).is_none() {
+                    try!(generate_eq_marker(w, type_name));
+                }
             }
             Type::ToString => try!(generate_display(
                 w,
@@ -118,7 +132,7 @@ impl fmt::Display for {type_name} {{
     )
 }
 
-fn generate_eq(
+fn generate_partial_eq(
     w: &mut Write,
     type_name: &str,
     func: &Info,
@@ -134,14 +148,16 @@ impl PartialEq for {type_name} {{
     fn eq(&self, other: &Self) -> bool {{
         {call}
     }}
-}}
-
-impl Eq for {type_name} {{}}",
+}}",
         type_name = type_name,
         call = call
     )
 }
 
+fn generate_eq_marker(w: &mut Write, type_name: &str) -> Result<()> {
+    writeln!(w, "\nimpl Eq for {} {{}}", type_name)
+}
+
 fn generate_eq_compare(
     w: &mut Write,
     type_name: &str,
@@ -194,3 +210,868 @@ impl Ord for {type_name} {{
         call = call
     )
 }
+
+pub fn generate_as_ref_str(
+    w: &mut Write,
+    env: &Env,
+    type_name: &str,
+    info: &as_ref_str::Info,
+) -> Result<()> {
+    try!(writeln!(w, ""));
+    try!(version_condition(w, env, info.version, false, 0));
+    writeln!(w, "{}", as_ref_str_impl(type_name, &info.glib_name))
+}
+
+/// A string that isn't valid UTF-8 can't be borrowed as a whole, so it's cut off
+/// before the first invalid byte instead of panicking.
+fn as_ref_str_impl(type_name: &str, glib_name: &str) -> String {
+    format!(
+        "impl AsRef<str> for {type_name} {{
+    #[inline]
+    fn as_ref(&self) -> &str {{
+        unsafe {{
+            let ptr = ffi::{glib_name}(mut_override(self.to_glib_none().0));
+            let bytes = CStr::from_ptr(ptr).to_bytes();
+            match ::std::str::from_utf8(bytes) {{
+                Ok(s) => s,
+                Err(e) => ::std::str::from_utf8_unchecked(&bytes[..e.valid_up_to()]),
+            }}
+        }}
+    }}
+}}",
+        type_name = type_name,
+        glib_name = glib_name
+    )
+}
+
+pub fn generate_eq_str(
+    w: &mut Write,
+    env: &Env,
+    type_name: &str,
+    info: &eq_str::Info,
+) -> Result<()> {
+    let (eq_str, eq_str_ref) = eq_str_impls(type_name, info);
+    try!(writeln!(w, ""));
+    try!(version_condition(w, env, info.version, false, 0));
+    try!(writeln!(w, "{}", eq_str));
+    try!(writeln!(w, ""));
+    try!(version_condition(w, env, info.version, false, 0));
+    writeln!(w, "{}", eq_str_ref)
+}
+
+/// A NULL string only equals the empty string.
+fn eq_str_impls(type_name: &str, info: &eq_str::Info) -> (String, String) {
+    let compare = if info.nullable {
+        "if ptr.is_null() {
+                other.is_empty()
+            } else {
+                CStr::from_ptr(ptr).to_bytes() == other.as_bytes()
+            }"
+    } else {
+        "CStr::from_ptr(ptr).to_bytes() == other.as_bytes()"
+    };
+    let eq_str = format!(
+        "impl PartialEq<str> for {type_name} {{
+    fn eq(&self, other: &str) -> bool {{
+        unsafe {{
+            let ptr = ffi::{glib_name}(mut_override(self.to_glib_none().0));
+            {compare}
+        }}
+    }}
+}}",
+        type_name = type_name,
+        glib_name = info.glib_name,
+        compare = compare
+    );
+    let eq_str_ref = format!(
+        "impl<'a> PartialEq<&'a str> for {type_name} {{
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {{
+        PartialEq::<str>::eq(self, other)
+    }}
+}}",
+        type_name = type_name
+    );
+    (eq_str, eq_str_ref)
+}
+
+pub fn generate_iterator(
+    w: &mut Write,
+    env: &Env,
+    type_name: &str,
+    info: &iterator_next::Info,
+) -> Result<()> {
+    try!(writeln!(w, ""));
+    try!(version_condition(w, env, info.version, false, 0));
+    writeln!(w, "{}", iterator_impl(type_name, info))
+}
+
+/// Borrowed `transfer none` items are yielded with a new reference or as a copy,
+/// so they stay valid after the iterator advances or is dropped.
+fn iterator_impl(type_name: &str, info: &iterator_next::Info) -> String {
+    let from_glib = if info.transfer == Transfer::None {
+        "from_glib_none"
+    } else {
+        "from_glib_full"
+    };
+    let self_ptr = if info.mut_self {
+        "self.to_glib_none_mut().0"
+    } else {
+        "self.to_glib_none().0"
+    };
+    let body = match info.end {
+        IteratorEnd::Null => format!(
+            "unsafe {{ {}(ffi::{}({})) }}",
+            from_glib,
+            info.glib_name,
+            self_ptr
+        ),
+        IteratorEnd::False => {
+            let (init, item) = match info.conversion_type {
+                ConversionType::Pointer | ConversionType::Borrow => {
+                    ("ptr::null_mut()", format!("{}(item)", from_glib))
+                }
+                ConversionType::Scalar => ("mem::uninitialized()", "from_glib(item)".into()),
+                _ => ("mem::uninitialized()", "item".into()),
+            };
+            format!(
+                "unsafe {{
+            let mut item = {};
+            if from_glib(ffi::{}({}, &mut item)) {{
+                Some({})
+            }} else {{
+                None
+            }}
+        }}",
+                init,
+                info.glib_name,
+                self_ptr,
+                item
+            )
+        }
+    };
+    format!(
+        "impl Iterator for {type_name} {{
+    type Item = {item};
+
+    fn next(&mut self) -> Option<{item}> {{
+        {body}
+    }}
+}}",
+        type_name = type_name,
+        item = info.item,
+        body = body
+    )
+}
+
+pub fn generate_index_getter(
+    w: &mut Write,
+    env: &Env,
+    type_name: &str,
+    info: &index_getter::Info,
+) -> Result<()> {
+    try!(writeln!(w, ""));
+    try!(version_condition(w, env, info.version, false, 0));
+    writeln!(w, "{}", index_getter_impl(type_name, info))
+}
+
+/// `Index` can't return the items by value, so they're returned by a `get` which
+/// is `None` past the length, or where the getter returns `NULL` without a length.
+fn index_getter_impl(type_name: &str, info: &index_getter::Info) -> String {
+    let from_glib = if info.transfer == Transfer::None {
+        "from_glib_none"
+    } else {
+        "from_glib_full"
+    };
+    let check = match info.len_glib_name {
+        Some(ref len) => format!(
+            "let len = unsafe {{ ffi::{}(mut_override(self.to_glib_none().0)) }};
+        if {}index >= len as usize {{",
+            len,
+            if info.len_signed { "len < 0 || " } else { "" }
+        ),
+        None => format!("if index >= {}::max_value() as usize {{", info.index_type),
+    };
+    format!(
+        "impl {type_name} {{
+    pub fn get(&self, index: usize) -> Option<{item}> {{
+        {check}
+            return None;
+        }}
+        unsafe {{
+            {from_glib}(ffi::{glib_name}(mut_override(self.to_glib_none().0), index as {index_type}))
+        }}
+    }}
+}}",
+        type_name = type_name,
+        item = info.item,
+        check = check,
+        from_glib = from_glib,
+        glib_name = info.glib_name,
+        index_type = info.index_type
+    )
+}
+
+pub fn generate_from_str(
+    w: &mut Write,
+    env: &Env,
+    type_name: &str,
+    info: &from_str::Info,
+) -> Result<()> {
+    let error = if env.namespaces.glib_ns_id == namespaces::MAIN {
+        "::Error"
+    } else {
+        "::glib::Error"
+    };
+    try!(writeln!(w, ""));
+    try!(version_condition(w, env, info.version, false, 0));
+    writeln!(w, "{}", from_str_impl(type_name, info, error))
+}
+
+fn from_str_impl(type_name: &str, info: &from_str::Info, glib_error: &str) -> String {
+    if info.throws {
+        return format!(
+            "impl FromStr for {type_name} {{
+    type Err = {error};
+
+    fn from_str(s: &str) -> Result<Self, {error}> {{
+        {type_name}::{func_name}(s)
+    }}
+}}",
+            type_name = type_name,
+            func_name = info.func_name,
+            error = glib_error
+        );
+    }
+    format!(
+        "#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct {type_name}ParseError;
+
+impl fmt::Display for {type_name}ParseError {{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {{
+        f.write_str(\"failed to parse {type_name}\")
+    }}
+}}
+
+impl error::Error for {type_name}ParseError {{}}
+
+impl FromStr for {type_name} {{
+    type Err = {type_name}ParseError;
+
+    fn from_str(s: &str) -> Result<Self, {type_name}ParseError> {{
+        {type_name}::{func_name}(s).ok_or({type_name}ParseError)
+    }}
+}}",
+        type_name = type_name,
+        func_name = info.func_name
+    )
+}
+
+pub fn generate_borrow(w: &mut Write, type_name: &str, borrowed_type: &str) -> Result<()> {
+    writeln!(w, "\n{}", borrow_impls(type_name, borrowed_type))
+}
+
+/// The owned type derefs to the borrowed one, which is copied with `From`,
+/// so both keep their own construction and free semantics.
+fn borrow_impls(type_name: &str, borrowed_type: &str) -> String {
+    format!(
+        "impl ::std::borrow::Borrow<{borrowed_type}> for {type_name} {{
+    #[inline]
+    fn borrow(&self) -> &{borrowed_type} {{
+        self
+    }}
+}}
+
+impl ToOwned for {borrowed_type} {{
+    type Owned = {type_name};
+
+    #[inline]
+    fn to_owned(&self) -> {type_name} {{
+        {type_name}::from(self)
+    }}
+}}",
+        type_name = type_name,
+        borrowed_type = borrowed_type
+    )
+}
+
+pub fn generate_identity_eq(
+    w: &mut Write,
+    type_name: &str,
+    c_type: &str,
+    ptr_mut: &str,
+) -> Result<()> {
+    writeln!(w, "\n{}", identity_eq_impl(type_name, c_type, ptr_mut))
+}
+
+/// Two wrappers are equal if they refer to the same instance.
+fn identity_eq_impl(type_name: &str, c_type: &str, ptr_mut: &str) -> String {
+    format!(
+        "impl PartialEq for {type_name} {{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {{
+        let ptr: *{ptr_mut} ffi::{c_type} = self.to_glib_none().0;
+        let other_ptr: *{ptr_mut} ffi::{c_type} = other.to_glib_none().0;
+        ptr == other_ptr
+    }}
+}}
+
+impl Eq for {type_name} {{}}
+
+impl hash::Hash for {type_name} {{
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {{
+        let ptr: *{ptr_mut} ffi::{c_type} = self.to_glib_none().0;
+        ptr.hash(state)
+    }}
+}}",
+        type_name = type_name,
+        c_type = c_type,
+        ptr_mut = ptr_mut
+    )
+}
+
+pub fn generate_as_ref_ancestor(
+    w: &mut Write,
+    env: &Env,
+    type_name: &str,
+    ancestor: &supertypes::Ancestor,
+) -> Result<()> {
+    try!(writeln!(w, ""));
+    try!(version_condition(w, env, ancestor.version, false, 0));
+    writeln!(w, "{}", as_ref_ancestor_impl(type_name, &ancestor.rust_type))
+}
+
+fn as_ref_ancestor_impl(type_name: &str, ancestor: &str) -> String {
+    format!(
+        "impl AsRef<{ancestor}> for {type_name} {{
+    #[inline]
+    fn as_ref(&self) -> &{ancestor} {{
+        self.upcast_ref()
+    }}
+}}",
+        type_name = type_name,
+        ancestor = ancestor
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_env;
+
+    #[test]
+    fn eq_marker_impl() {
+        let mut buf = Vec::new();
+        generate_eq_marker(&mut buf, "RGBA").unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "\nimpl Eq for RGBA {}\n");
+    }
+
+    #[test]
+    fn as_ref_str_uses_accessor() {
+        let expected = "impl AsRef<str> for Quark {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        unsafe {
+            let ptr = ffi::g_quark_get_string(mut_override(self.to_glib_none().0));
+            let bytes = CStr::from_ptr(ptr).to_bytes();
+            match ::std::str::from_utf8(bytes) {
+                Ok(s) => s,
+                Err(e) => ::std::str::from_utf8_unchecked(&bytes[..e.valid_up_to()]),
+            }
+        }
+    }
+}";
+        assert_eq!(as_ref_str_impl("Quark", "g_quark_get_string"), expected);
+    }
+
+    #[test]
+    fn borrowed_string_pair() {
+        let expected = "impl ::std::borrow::Borrow<GStr> for GString {
+    #[inline]
+    fn borrow(&self) -> &GStr {
+        self
+    }
+}
+
+impl ToOwned for GStr {
+    type Owned = GString;
+
+    #[inline]
+    fn to_owned(&self) -> GString {
+        GString::from(self)
+    }
+}";
+        assert_eq!(borrow_impls("GString", "GStr"), expected);
+    }
+
+    #[test]
+    fn index_getter_is_bounds_checked() {
+        let info = index_getter::Info {
+            glib_name: "gtk_notebook_get_nth_page".into(),
+            len_glib_name: Some("gtk_notebook_get_n_pages".into()),
+            len_signed: true,
+            index_type: "i32",
+            item: "Widget".into(),
+            transfer: Transfer::None,
+            version: None,
+        };
+        let expected = "impl Notebook {
+    pub fn get(&self, index: usize) -> Option<Widget> {
+        let len = unsafe { ffi::gtk_notebook_get_n_pages(mut_override(self.to_glib_none().0)) };
+        if len < 0 || index >= len as usize {
+            return None;
+        }
+        unsafe {
+            from_glib_none(ffi::gtk_notebook_get_nth_page(mut_override(self.to_glib_none().0), \
+             index as i32))
+        }
+    }
+}";
+        assert_eq!(index_getter_impl("Notebook", &info), expected);
+
+        let info = index_getter::Info {
+            len_signed: false,
+            ..info
+        };
+        assert!(index_getter_impl("Notebook", &info).contains("        if index >= len as usize {"));
+
+        let info = index_getter::Info {
+            len_glib_name: None,
+            index_type: "u32",
+            transfer: Transfer::Full,
+            ..info
+        };
+        let code = index_getter_impl("Notebook", &info);
+        assert!(code.contains("        if index >= u32::max_value() as usize {"));
+        assert!(code.contains("from_glib_full(ffi::gtk_notebook_get_nth_page("));
+        assert!(code.contains("index as u32))"));
+    }
+
+    fn iterator_info(end: IteratorEnd, transfer: Transfer) -> iterator_next::Info {
+        iterator_next::Info {
+            glib_name: "element_iterator_next".into(),
+            item: "Element".into(),
+            end: end,
+            transfer: transfer,
+            conversion_type: ConversionType::Pointer,
+            mut_self: true,
+            version: None,
+        }
+    }
+
+    #[test]
+    fn iterator_ends_on_null() {
+        let info = iterator_info(IteratorEnd::Null, Transfer::Full);
+        let expected = "impl Iterator for ElementIterator {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Element> {
+        unsafe { from_glib_full(ffi::element_iterator_next(self.to_glib_none_mut().0)) }
+    }
+}";
+        assert_eq!(iterator_impl("ElementIterator", &info), expected);
+    }
+
+    #[test]
+    fn iterator_refs_borrowed_items() {
+        let info = iterator_info(IteratorEnd::Null, Transfer::None);
+        assert!(iterator_impl("ElementIterator", &info).contains(
+            "unsafe { from_glib_none(ffi::element_iterator_next(self.to_glib_none_mut().0)) }"
+        ));
+    }
+
+    #[test]
+    fn iterator_ends_on_false() {
+        let info = iterator_info(IteratorEnd::False, Transfer::None);
+        let expected = "impl Iterator for ElementIterator {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Element> {
+        unsafe {
+            let mut item = ptr::null_mut();
+            if from_glib(ffi::element_iterator_next(self.to_glib_none_mut().0, &mut item)) {
+                Some(from_glib_none(item))
+            } else {
+                None
+            }
+        }
+    }
+}";
+        assert_eq!(iterator_impl("ElementIterator", &info), expected);
+    }
+
+    #[test]
+    fn iterator_of_numbers_ends_on_false() {
+        let info = iterator_next::Info {
+            item: "i32".into(),
+            conversion_type: ConversionType::Direct,
+            ..iterator_info(IteratorEnd::False, Transfer::None)
+        };
+        let code = iterator_impl("ElementIterator", &info);
+        assert!(code.contains("let mut item = mem::uninitialized();"));
+        assert!(code.contains("                Some(item)\n"));
+        assert!(!code.contains("ptr::"));
+    }
+
+    fn from_str_info(throws: bool) -> from_str::Info {
+        from_str::Info {
+            func_name: "from_string".into(),
+            throws: throws,
+            version: None,
+        }
+    }
+
+    #[test]
+    fn from_str_forwards_glib_error() {
+        let expected = "impl FromStr for TreePath {
+    type Err = ::glib::Error;
+
+    fn from_str(s: &str) -> Result<Self, ::glib::Error> {
+        TreePath::from_string(s)
+    }
+}";
+        assert_eq!(
+            from_str_impl("TreePath", &from_str_info(true), "::glib::Error"),
+            expected
+        );
+        assert!(
+            from_str_impl("VariantType", &from_str_info(true), "::Error")
+                .contains("    type Err = ::Error;\n")
+        );
+    }
+
+    #[test]
+    fn from_str_maps_none_to_unit_error() {
+        let code = from_str_impl("TreePath", &from_str_info(false), "::glib::Error");
+        assert!(code.starts_with("#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreePathParseError;
+"));
+        assert!(code.contains("        f.write_str(\"failed to parse TreePath\")\n"));
+        assert!(code.contains("impl error::Error for TreePathParseError {}\n"));
+        assert!(!code.contains("description"));
+        assert!(code.ends_with("impl FromStr for TreePath {
+    type Err = TreePathParseError;
+
+    fn from_str(s: &str) -> Result<Self, TreePathParseError> {
+        TreePath::from_string(s).ok_or(TreePathParseError)
+    }
+}"));
+    }
+
+    #[test]
+    fn as_ref_ancestor_upcasts() {
+        let expected = "impl AsRef<gtk::Widget> for SourceView {
+    #[inline]
+    fn as_ref(&self) -> &gtk::Widget {
+        self.upcast_ref()
+    }
+}";
+        assert_eq!(as_ref_ancestor_impl("SourceView", "gtk::Widget"), expected);
+    }
+
+    #[test]
+    fn identity_eq_compares_pointers() {
+        let code = identity_eq_impl("TreePath", "GtkTreePath", "const");
+        assert!(code.starts_with("impl PartialEq for TreePath {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        let ptr: *const ffi::GtkTreePath = self.to_glib_none().0;
+        let other_ptr: *const ffi::GtkTreePath = other.to_glib_none().0;
+        ptr == other_ptr
+    }
+}"));
+        assert!(code.contains("impl Eq for TreePath {}"));
+        assert!(code.ends_with("impl hash::Hash for TreePath {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        let ptr: *const ffi::GtkTreePath = self.to_glib_none().0;
+        ptr.hash(state)
+    }
+}"));
+    }
+
+    #[test]
+    fn eq_str_compares_accessor() {
+        let info = eq_str::Info {
+            glib_name: "pango_language_to_string".into(),
+            nullable: false,
+            version: None,
+        };
+        let (eq_str, eq_str_ref) = eq_str_impls("Language", &info);
+        assert_eq!(
+            eq_str,
+            "impl PartialEq<str> for Language {
+    fn eq(&self, other: &str) -> bool {
+        unsafe {
+            let ptr = ffi::pango_language_to_string(mut_override(self.to_glib_none().0));
+            CStr::from_ptr(ptr).to_bytes() == other.as_bytes()
+        }
+    }
+}"
+        );
+        assert!(eq_str_ref.starts_with("impl<'a> PartialEq<&'a str> for Language {"));
+        assert!(eq_str_ref.contains("PartialEq::<str>::eq(self, other)"));
+
+        let info = eq_str::Info {
+            nullable: true,
+            ..info
+        };
+        let (eq_str, _) = eq_str_impls("Language", &info);
+        assert!(eq_str.contains("if ptr.is_null() {\n                other.is_empty()"));
+    }
+
+    const DATE: &'static str = r#"
+    <record name="Date" c:type="TestDate" glib:type-name="TestDate"
+            glib:get-type="test_date_get_type">
+      <method name="copy" c:identifier="test_date_copy">
+        <return-value transfer-ownership="full">
+          <type name="Date" c:type="TestDate*"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="date" transfer-ownership="none">
+            <type name="Date" c:type="const TestDate*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+      <method name="free" c:identifier="test_date_free">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="date" transfer-ownership="none">
+            <type name="Date" c:type="TestDate*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+      <method name="equal" c:identifier="test_date_equal">
+        <return-value transfer-ownership="none">
+          <type name="gboolean" c:type="gboolean"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="date" transfer-ownership="none">
+            <type name="Date" c:type="const TestDate*"/>
+          </instance-parameter>
+          <parameter name="other" transfer-ownership="none">
+            <type name="Date" c:type="const TestDate*"/>
+          </parameter>
+        </parameters>
+      </method>
+      <method name="compare" c:identifier="test_date_compare">
+        <return-value transfer-ownership="none">
+          <type name="gint" c:type="gint"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="date" transfer-ownership="none">
+            <type name="Date" c:type="const TestDate*"/>
+          </instance-parameter>
+          <parameter name="other" transfer-ownership="none">
+            <type name="Date" c:type="const TestDate*"/>
+          </parameter>
+        </parameters>
+      </method>
+    </record>"#;
+
+    #[test]
+    fn equal_and_compare_implement_eq_once() {
+        for &generate_eq in &[false, true] {
+            let config = format!("generate_eq = {}", generate_eq);
+            let code = test_env::generated_object(DATE, "", "Date", &config);
+            assert_eq!(code.matches("impl PartialEq for Date {").count(), 1);
+            assert!(code.contains("self.equal(other)"));
+            assert_eq!(code.matches("impl Eq for Date {}").count(), 1);
+            assert!(code.contains("impl Ord for Date {"));
+        }
+    }
+
+    const ELEMENTS: &'static str = r#"
+    <class name="Element" c:type="TestElement" parent="GObject.Object"
+           glib:type-name="TestElement" glib:get-type="test_element_get_type">
+    </class>
+    <record name="ElementIterator" c:type="TestElementIterator"
+            glib:type-name="TestElementIterator" glib:get-type="test_element_iterator_get_type">
+      <method name="next" c:identifier="test_element_iterator_next">
+        <return-value transfer-ownership="full" nullable="1">
+          <type name="Element" c:type="TestElement*"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="iter" transfer-ownership="none">
+            <type name="ElementIterator" c:type="TestElementIterator*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+    </record>
+    <record name="ElementList" c:type="TestElementList"
+            glib:type-name="TestElementList" glib:get-type="test_element_list_get_type">
+      <method name="get_nth" c:identifier="test_element_list_get_nth">
+        <return-value transfer-ownership="none" nullable="1">
+          <type name="Element" c:type="TestElement*"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="list" transfer-ownership="none">
+            <type name="ElementList" c:type="const TestElementList*"/>
+          </instance-parameter>
+          <parameter name="index" transfer-ownership="none">
+            <type name="gint" c:type="gint"/>
+          </parameter>
+        </parameters>
+      </method>
+      <method name="get_length" c:identifier="test_element_list_get_length">
+        <return-value transfer-ownership="none">
+          <type name="gint" c:type="gint"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="list" transfer-ownership="none">
+            <type name="ElementList" c:type="const TestElementList*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+    </record>"#;
+
+    const ELEMENT_OBJECT: &'static str = r#"
+[[object]]
+name = "Test.Element"
+status = "generate"
+"#;
+
+    #[test]
+    fn iterator_imports_item_type() {
+        // The import of the `next` function alone would be `unix` only
+        let config = r#"iterator_next = { func = "next", item = "Element", end = "null" }
+    [[object.function]]
+    name = "next"
+    cfg_condition = "unix""#;
+        let code = test_env::generated_object(
+            ELEMENTS,
+            "",
+            "ElementIterator",
+            &format!("{}\n{}", config, ELEMENT_OBJECT),
+        );
+        assert!(code.contains("use Element;"));
+        assert!(!code.contains("#[cfg(unix)]\nuse Element;"));
+        assert!(code.contains("impl Iterator for ElementIterator {"));
+        assert!(code.contains("from_glib_full(ffi::test_element_iterator_next("));
+    }
+
+    #[test]
+    fn index_getter_imports_item_type() {
+        // The import of the getter alone would be `unix` only
+        let config = r#"index = { get = "get_nth", len = "get_length", item = "Element" }
+    [[object.function]]
+    name = "get_nth"
+    cfg_condition = "unix""#;
+        let code = test_env::generated_object(
+            ELEMENTS,
+            "",
+            "ElementList",
+            &format!("{}\n{}", config, ELEMENT_OBJECT),
+        );
+        assert!(code.contains("use Element;"));
+        assert!(!code.contains("#[cfg(unix)]\nuse Element;"));
+        assert!(code.contains("pub fn get(&self, index: usize) -> Option<Element> {"));
+        assert!(code.contains("if len < 0 || index >= len as usize {"));
+    }
+
+    const LANGUAGE: &'static str = r#"
+    <record name="Language" c:type="TestLanguage" glib:type-name="TestLanguage"
+            glib:get-type="test_language_get_type">
+      <method name="copy" c:identifier="test_language_copy">
+        <return-value transfer-ownership="full">
+          <type name="Language" c:type="TestLanguage*"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="language" transfer-ownership="none">
+            <type name="Language" c:type="const TestLanguage*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+      <method name="free" c:identifier="test_language_free">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="language" transfer-ownership="none">
+            <type name="Language" c:type="TestLanguage*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+      <function name="from_string" c:identifier="test_language_from_string">
+        <return-value transfer-ownership="full" nullable="1">
+          <type name="Language" c:type="TestLanguage*"/>
+        </return-value>
+        <parameters>
+          <parameter name="language" transfer-ownership="none">
+            <type name="utf8" c:type="const char*"/>
+          </parameter>
+        </parameters>
+      </function>
+      <method name="to_string" c:identifier="test_language_to_string">
+        <return-value transfer-ownership="none">
+          <type name="utf8" c:type="const char*"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="language" transfer-ownership="none">
+            <type name="Language" c:type="const TestLanguage*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+    </record>"#;
+
+    #[test]
+    fn as_ref_str_of_string_wrapper() {
+        let config = "as_ref_str = \"to_string\"";
+        let code = test_env::generated_object(LANGUAGE, "", "Language", config);
+        assert!(code.contains("use std::ffi::CStr;"));
+        assert!(code.contains("impl AsRef<str> for Language {"));
+        assert!(code.contains(
+            "let ptr = ffi::test_language_to_string(mut_override(self.to_glib_none().0));"
+        ));
+        assert!(!code.contains("unwrap()"));
+    }
+
+    #[test]
+    fn identity_eq_of_boxed_record() {
+        let code = test_env::generated_object(LANGUAGE, "", "Language", "identity_eq = true");
+        assert!(code.contains("use std::hash;"));
+        assert_eq!(code.matches("impl PartialEq for Language {").count(), 1);
+        assert!(code.contains("let other_ptr: *const ffi::TestLanguage = other.to_glib_none().0;"));
+        assert!(code.contains("impl hash::Hash for Language {"));
+    }
+
+    #[test]
+    fn identity_eq_is_not_generated_for_objects() {
+        let code = test_env::generated_object(ELEMENTS, "", "Element", "identity_eq = true");
+        // `glib_wrapper!` already implements them
+        assert!(!code.contains("impl PartialEq for Element"));
+        assert!(!code.contains("impl hash::Hash for Element"));
+    }
+
+    #[test]
+    fn eq_str_of_string_wrapper() {
+        let code = test_env::generated_object(LANGUAGE, "", "Language", "eq_str = \"to_string\"");
+        assert!(code.contains("impl PartialEq<str> for Language {"));
+        assert!(code.contains(
+            "let ptr = ffi::test_language_to_string(mut_override(self.to_glib_none().0));"
+        ));
+        assert!(code.contains("impl<'a> PartialEq<&'a str> for Language {"));
+    }
+
+    #[test]
+    fn borrowed_type_is_imported() {
+        let config = "borrowed_type = \"LanguageRef\"";
+        let code = test_env::generated_object(LANGUAGE, "", "Language", config);
+        assert!(code.contains("use LanguageRef;"));
+        assert!(code.contains("impl ::std::borrow::Borrow<LanguageRef> for Language {"));
+        assert!(code.contains("impl ToOwned for LanguageRef {"));
+    }
+
+    #[test]
+    fn from_str_of_nullable_parser() {
+        let config = "from_str = \"from_string\"";
+        let code = test_env::generated_object(LANGUAGE, "", "Language", config);
+        assert!(code.contains("use std::error;"));
+        assert!(!code.contains("use glib;"));
+        assert!(code.contains("impl error::Error for LanguageParseError {}"));
+        assert!(code.contains("Language::from_string(s).ok_or(LanguageParseError)"));
+    }
+}
@@ -8,7 +8,7 @@ use analysis::ref_mode::RefMode;
 use analysis::rust_type::parameter_rust_type;
 use analysis::trampoline_parameters::*;
 use analysis::trampolines::Trampoline;
-use super::general::version_condition;
+use super::general::{cfg_condition, version_condition};
 use super::return_value::ToReturnValue;
 use super::trampoline_from_glib::TrampolineFromGlib;
 use super::trampoline_to_glib::TrampolineToGlib;
@@ -33,6 +33,7 @@ pub fn generate(
     let func_str = func_string(env, analysis, None, true);
     let ret_str = trampoline_returns(env, analysis);
 
+    try!(cfg_condition(w, &analysis.cfg_condition, false, 0));
     try!(version_condition(w, env, analysis.version, false, 0));
     try!(writeln!(
         w,
@@ -222,13 +223,12 @@ fn transformation_vars(w: &mut Write, analysis: &Trampoline) -> Result<()> {
 }
 
 fn trampoline_call_func(env: &Env, analysis: &Trampoline, in_trait: bool) -> String {
-    let params = trampoline_call_parameters(env, analysis, in_trait);
-    let ret = if analysis.ret.typ == Default::default() {
-        String::new()
+    let call = format!("f({})", trampoline_call_parameters(env, analysis, in_trait));
+    if analysis.ret.typ == Default::default() {
+        call
     } else {
-        analysis.ret.trampoline_to_glib(env)
-    };
-    format!("f({}){}", params, ret)
+        analysis.ret.trampoline_to_glib(env, &call)
+    }
 }
 
 fn trampoline_call_parameters(env: &Env, analysis: &Trampoline, in_trait: bool) -> String {
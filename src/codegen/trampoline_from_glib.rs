@@ -1,5 +1,6 @@
-use analysis::rust_type::rust_type;
+use analysis::rust_type::{is_checked_size_cast, rust_type, size_cast};
 use analysis::trampoline_parameters::Transformation;
+use codegen::translate_to_glib::int_cast;
 use env::Env;
 use library;
 use traits::*;
@@ -13,7 +14,14 @@ impl TrampolineFromGlib for Transformation {
         use analysis::conversion_type::ConversionType::*;
         let need_type_name = need_downcast || is_need_type_name(env, self.typ);
         match self.conversion_type {
-            Direct => self.name.clone(),
+            Direct => match size_cast(env, self.typ) {
+                Some(_) => int_cast(
+                    &self.name,
+                    &rust_type(env, self.typ).into_string(),
+                    is_checked_size_cast(env, self.typ, false),
+                ),
+                None => self.name.clone(),
+            },
             Scalar => format!("from_glib({})", self.name),
             Borrow | Pointer => {
                 let is_borrow = self.conversion_type == Borrow;
@@ -1,20 +1,25 @@
 use analysis::conversion_type::ConversionType;
+use analysis::rust_type::{is_checked_size_cast, size_cast};
+use codegen::translate_to_glib::int_cast;
 use library;
 use env;
 
 pub trait TrampolineToGlib {
-    fn trampoline_to_glib(&self, env: &env::Env) -> String;
+    fn trampoline_to_glib(&self, env: &env::Env, value: &str) -> String;
 }
 
 impl TrampolineToGlib for library::Parameter {
-    fn trampoline_to_glib(&self, env: &env::Env) -> String {
+    fn trampoline_to_glib(&self, env: &env::Env, value: &str) -> String {
         use analysis::conversion_type::ConversionType::*;
         match ConversionType::of(env, self.typ) {
-            Direct => String::new(),
-            Scalar => ".to_glib()".to_owned(),
-            Pointer => to_glib_xxx(self.transfer).to_owned(),
-            Borrow => "/*Not applicable conversion Borrow*/".to_owned(),
-            Unknown => "/*Unknown conversion*/".to_owned(),
+            Direct => match size_cast(env, self.typ) {
+                Some(type_) => int_cast(value, type_, is_checked_size_cast(env, self.typ, true)),
+                None => value.to_owned(),
+            },
+            Scalar => format!("{}.to_glib()", value),
+            Pointer => format!("{}{}", value, to_glib_xxx(self.transfer)),
+            Borrow => format!("{}/*Not applicable conversion Borrow*/", value),
+            Unknown => format!("{}/*Unknown conversion*/", value),
         }
     }
 }
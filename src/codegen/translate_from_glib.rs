@@ -1,8 +1,9 @@
 use analysis;
 use analysis::namespaces;
-use analysis::rust_type::rust_type;
+use analysis::rust_type::{rust_type, size_cast};
 use analysis::conversion_type::ConversionType;
 use chunk::conversion_from_glib::Mode;
+use config::TimeIntegration;
 use env::Env;
 use library;
 use traits::*;
@@ -23,21 +24,20 @@ impl TranslateFromGlib for Mode {
     ) -> (String, String) {
         use analysis::conversion_type::ConversionType::*;
         match ConversionType::of(env, self.typ) {
-            Direct => (String::new(), String::new()),
+            Direct => match size_cast(env, self.typ) {
+                Some(_) => (
+                    "(".into(),
+                    format!(" as {})", rust_type(env, self.typ).into_string()),
+                ),
+                None => (String::new(), String::new()),
+            },
             Scalar => ("from_glib(".into(), ")".into()),
-            Pointer => {
-                let trans = from_glib_xxx(self.transfer, array_length);
-                match *env.type_(self.typ) {
-                    library::Type::List(..) |
-                    library::Type::SList(..) |
-                    library::Type::CArray(..) => if array_length.is_some() {
-                        (format!("FromGlibContainer::{}", trans.0), trans.1)
-                    } else {
-                        (format!("FromGlibPtrContainer::{}", trans.0), trans.1)
-                    },
-                    _ => trans,
-                }
-            }
+            Pointer => match *env.type_(self.typ) {
+                library::Type::List(..) |
+                library::Type::SList(..) |
+                library::Type::CArray(..) => container_from_glib_xxx(self.transfer, array_length),
+                _ => from_glib_xxx(self.transfer, array_length),
+            },
             Borrow => ("/*TODO: conversion Borrow*/".into(), String::new()),
             Unknown => ("/*Unknown conversion*/".into(), String::new()),
         }
@@ -69,17 +69,26 @@ impl TranslateFromGlib for analysis::return_value::Info {
                     (prefix, format!("{}.{}", from_glib_xxx.1, suffix_function))
                 }
                 None if self.bool_return_is_error.is_some() => {
-                    if env.namespaces.glib_ns_id == namespaces::MAIN {
-                        (
-                            "error::BoolError::from_glib(".into(),
-                            format!(", \"{}\")", self.bool_return_is_error.as_ref().unwrap()),
-                        )
+                    let bool_error = if env.namespaces.glib_ns_id == namespaces::MAIN {
+                        "error::BoolError"
                     } else {
-                        (
-                            "glib::error::BoolError::from_glib(".into(),
-                            format!(", \"{}\")", self.bool_return_is_error.as_ref().unwrap()),
-                        )
-                    }
+                        "glib::error::BoolError"
+                    };
+                    bool_error_from_glib(bool_error, self.bool_return_is_error.as_ref().unwrap())
+                }
+                None if self.date_time.is_some() => date_time_from_glib(
+                    self.date_time.unwrap(),
+                    &rust_type(env, par.typ).into_string(),
+                    par.transfer,
+                    *par.nullable,
+                ),
+                None if self.time_t.is_some() => time_t_from_glib(self.time_t.unwrap()),
+                None if self.bytes_to_vec => {
+                    bytes_from_glib(&rust_type(env, par.typ).into_string(), *par.nullable)
+                }
+                None if self.invalid_char.is_some() => {
+                    let (prefix, suffix) = self.invalid_char.unwrap().from_u32().unwrap();
+                    (prefix.into(), suffix.into())
                 }
                 None => Mode::from(par).translate_from_glib_as_function(env, array_length),
             },
@@ -108,3 +117,116 @@ fn from_glib_xxx(transfer: library::Transfer, array_length: Option<&String>) ->
         ),
     }
 }
+
+/// `FALSE` returns are turned into an `Err` with the configured message.
+fn bool_error_from_glib(bool_error: &str, message: &str) -> (String, String) {
+    (
+        format!("{}::from_glib(", bool_error),
+        format!(", \"{}\")", message),
+    )
+}
+
+/// Converts the returned `DateTime` to the configured Rust time type.
+fn date_time_from_glib(
+    time: TimeIntegration,
+    date_time_type: &str,
+    transfer: library::Transfer,
+    nullable: bool,
+) -> (String, String) {
+    let from_glib_xxx = from_glib_xxx(transfer, None);
+    let conversion = time.from_date_time().unwrap();
+    if nullable {
+        (
+            format!("Option::<{}>::{}", date_time_type, from_glib_xxx.0),
+            format!("{}.map(|dt| {{ {} }})", from_glib_xxx.1, conversion),
+        )
+    } else {
+        (
+            format!("{{ let dt: {} = {}", date_time_type, from_glib_xxx.0),
+            format!("{}; {} }}", from_glib_xxx.1, conversion),
+        )
+    }
+}
+
+/// Converts the returned `time_t`, a `c_long`, to the configured Rust time type.
+fn time_t_from_glib(time: TimeIntegration) -> (String, String) {
+    (
+        "{ let secs = i64::from(".into(),
+        format!("); {} }}", time.from_unix().unwrap()),
+    )
+}
+
+/// Copies the data of the returned borrowed `Bytes`.
+fn bytes_from_glib(bytes_type: &str, nullable: bool) -> (String, String) {
+    if nullable {
+        (
+            format!("Option::<{}>::from_glib_none(", bytes_type),
+            ").map(|bytes| bytes.to_vec())".into(),
+        )
+    } else {
+        (
+            format!("{{ let bytes: {} = from_glib_none(", bytes_type),
+            "); bytes.to_vec() }".into(),
+        )
+    }
+}
+
+/// Arrays without a length are zero-terminated, e.g. `gchar**` string arrays.
+/// With `transfer full` both the array and its elements are freed.
+fn container_from_glib_xxx(
+    transfer: library::Transfer,
+    array_length: Option<&String>,
+) -> (String, String) {
+    let trans = from_glib_xxx(transfer, array_length);
+    if array_length.is_some() {
+        (format!("FromGlibContainer::{}", trans.0), trans.1)
+    } else {
+        (format!("FromGlibPtrContainer::{}", trans.0), trans.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use library::Transfer;
+
+    #[test]
+    fn string_out_conversion_depends_on_transfer() {
+        // `(out) (transfer none)` strings are borrowed and must be copied, not freed
+        let (borrowed, _) = from_glib_xxx(Transfer::None, None);
+        let (owned, _) = from_glib_xxx(Transfer::Full, None);
+        assert_eq!(borrowed, "from_glib_none(");
+        assert_eq!(owned, "from_glib_full(");
+    }
+
+    #[test]
+    fn date_time_return_converted_to_system_time() {
+        let (prefix, suffix) =
+            date_time_from_glib(TimeIntegration::Std, "glib::DateTime", Transfer::Full, false);
+        assert_eq!(prefix, "{ let dt: glib::DateTime = from_glib_full(");
+        assert!(suffix.starts_with("); let secs = dt.to_unix(); "));
+        assert!(suffix.contains("::std::time::UNIX_EPOCH + "));
+        assert!(suffix.ends_with(" }"));
+
+        let (prefix, suffix) =
+            date_time_from_glib(TimeIntegration::Chrono, "DateTime", Transfer::Full, true);
+        assert_eq!(prefix, "Option::<DateTime>::from_glib_full(");
+        assert_eq!(
+            suffix,
+            ").map(|dt| { ::chrono::TimeZone::timestamp(&::chrono::Utc, dt.to_unix(), \
+             dt.get_microsecond() as u32 * 1000) })"
+        );
+    }
+
+    #[test]
+    fn byte_buffer_return_with_length() {
+        // guint8 *foo_serialize(Foo *self, gsize *length)
+        let length = "length".to_owned();
+        let (prefix, suffix) = container_from_glib_xxx(Transfer::Full, Some(&length));
+        assert_eq!(prefix, "FromGlibContainer::from_glib_full_num(");
+        assert_eq!(suffix, ", length as usize)");
+        // Borrowed buffers are only copied, not freed
+        let (prefix, _) = container_from_glib_xxx(Transfer::None, Some(&length));
+        assert_eq!(prefix, "FromGlibContainer::from_glib_none_num(");
+    }
+}
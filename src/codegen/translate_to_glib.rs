@@ -11,6 +11,42 @@ impl TranslateToGlib for TransformationType {
         use self::TransformationType::*;
         match *self {
             ToGlibDirect { ref name } => name.clone(),
+            ToGlibCast {
+                ref name,
+                ref type_,
+                checked,
+            } => int_cast(name, type_, checked),
+            ToGlibFixedArray { ref name } => format!("{}.as_ptr() as *mut _", name),
+            ToGlibDuration { ref name } => duration_to_us(name),
+            ToGlibDateTime {
+                ref name,
+                time,
+                ref date_time_type,
+                nullable,
+                transfer,
+            } => {
+                let (_, right) = to_glib_xxx(transfer, RefMode::ByRef);
+                if *nullable {
+                    let date_time = time.to_date_time("t", date_time_type).unwrap();
+                    format!("{}.map(|t| {}){}", name, date_time, right)
+                } else {
+                    format!("{}{}", time.to_date_time(name, date_time_type).unwrap(), right)
+                }
+            }
+            ToGlibTimeT { ref name, time } => format!("({}) as _", time.to_unix(name).unwrap()),
+            ToGlibBytes {
+                ref name,
+                ref bytes_type,
+                nullable,
+                transfer,
+            } => {
+                let (_, right) = to_glib_xxx(transfer, RefMode::ByRef);
+                if *nullable {
+                    format!("{}.map({}::from){}", name, bytes_type, right)
+                } else {
+                    format!("{}::from({}){}", bytes_type, name, right)
+                }
+            }
             ToGlibScalar { ref name, nullable } => format!(
                 "{}{}{}",
                 name,
@@ -33,12 +69,35 @@ impl TranslateToGlib for TransformationType {
             }
             ToGlibStash { ref name } => format!("{}.0", name),
             ToGlibBorrow => "/*Not applicable conversion Borrow*/".to_owned(),
+            ToGlibNull => "ptr::null_mut()".to_owned(),
             ToGlibUnknown { ref name } => format!("/*Unknown conversion*/{}", name),
             _ => unreachable!("Unexpected transformation type {:?}", self),
         }
     }
 }
 
+/// Casts `value` with `as` or, if `checked`, panics when it doesn't fit into `type_`.
+pub fn int_cast(value: &str, type_: &str, checked: bool) -> String {
+    if checked {
+        format!(
+            "{type_}::try_from({value}).expect(\"`{value}` doesn't fit into {type_}\")",
+            type_ = type_,
+            value = value
+        )
+    } else {
+        format!("{} as {}", value, type_)
+    }
+}
+
+/// Microseconds of a `Duration`, clamped to `i64::MAX` instead of overflowing.
+fn duration_to_us(name: &str) -> String {
+    format!(
+        "::std::cmp::min({name}.as_secs().saturating_mul(1_000_000)\
+         .saturating_add(u64::from({name}.subsec_nanos() / 1_000)), i64::max_value() as u64) as i64",
+        name = name
+    )
+}
+
 fn to_glib_xxx(transfer: Transfer, ref_mode: RefMode) -> (&'static str, &'static str) {
     use self::Transfer::*;
     match transfer {
@@ -55,3 +114,85 @@ fn to_glib_xxx(transfer: Transfer, ref_mode: RefMode) -> (&'static str, &'static
         Container => ("", ".to_glib_container().0"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use library::Nullable;
+
+    #[test]
+    fn duration_passed_in_microseconds() {
+        let transformation = TransformationType::ToGlibDuration {
+            name: "timeout".to_owned(),
+        };
+        assert_eq!(
+            transformation.translate_to_glib(),
+            "::std::cmp::min(timeout.as_secs().saturating_mul(1_000_000)\
+             .saturating_add(u64::from(timeout.subsec_nanos() / 1_000)), \
+             i64::max_value() as u64) as i64"
+        );
+    }
+
+    #[test]
+    fn bytes_built_from_slice() {
+        let transformation = TransformationType::ToGlibBytes {
+            name: "data".to_owned(),
+            bytes_type: "Bytes".to_owned(),
+            nullable: Nullable(false),
+            transfer: Transfer::None,
+        };
+        assert_eq!(
+            transformation.translate_to_glib(),
+            "Bytes::from(data).to_glib_none().0"
+        );
+
+        let transformation = TransformationType::ToGlibBytes {
+            name: "data".to_owned(),
+            bytes_type: "glib::Bytes".to_owned(),
+            nullable: Nullable(true),
+            transfer: Transfer::Full,
+        };
+        assert_eq!(
+            transformation.translate_to_glib(),
+            "data.map(glib::Bytes::from).to_glib_full()"
+        );
+    }
+
+    #[test]
+    fn narrowing_cast_is_checked() {
+        let transformation = TransformationType::ToGlibCast {
+            name: "size".to_owned(),
+            type_: "usize".to_owned(),
+            checked: true,
+        };
+        assert_eq!(
+            transformation.translate_to_glib(),
+            "usize::try_from(size).expect(\"`size` doesn't fit into usize\")"
+        );
+        assert_eq!(
+            int_cast("items.len()", "i32", true),
+            "i32::try_from(items.len()).expect(\"`items.len()` doesn't fit into i32\")"
+        );
+    }
+
+    #[test]
+    fn transfer_full_parameter_is_consumed() {
+        let transformation = TransformationType::ToGlibPointer {
+            name: "widget".to_owned(),
+            instance_parameter: false,
+            transfer: Transfer::Full,
+            ref_mode: RefMode::None,
+            to_glib_extra: String::new(),
+        };
+        assert_eq!(transformation.translate_to_glib(), "widget.to_glib_full()");
+
+        let transformation = TransformationType::ToGlibPointer {
+            name: "self".to_owned(),
+            instance_parameter: true,
+            transfer: Transfer::Full,
+            ref_mode: RefMode::None,
+            to_glib_extra: String::new(),
+        };
+        assert_eq!(transformation.translate_to_glib(), "self.to_glib_full()");
+    }
+}
@@ -1,4 +1,5 @@
 use docopt::{self, Docopt};
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
@@ -8,8 +9,10 @@ use toml;
 use git::repo_hash;
 use library;
 use library::Library;
-use super::external_libraries::{read_external_libraries, ExternalLibrary};
-use super::WorkMode;
+use super::external_libraries::{read_external_libraries, read_ffi_crate_names, ExternalLibrary};
+use super::partitions::{read_partitions, Partitions};
+use super::platform_cfg::{read_platform_cfg, PlatformCfg};
+use super::{InvalidChar, SizeType, StringType, TimeIntegration, VersionGating, WorkMode};
 use super::gobjects;
 use super::error::*;
 use version::Version;
@@ -37,11 +40,47 @@ pub struct Config {
     pub library_version: String,
     pub target_path: PathBuf,
     pub external_libraries: Vec<ExternalLibrary>,
+    pub ffi_crate_names: BTreeMap<String, String>,
     pub objects: gobjects::GObjects,
     pub min_cfg_version: Version,
+    pub max_cfg_version: Option<Version>,
     pub make_backup: bool,
     pub generate_safety_asserts: bool,
     pub deprecate_by_min_version: bool,
+    pub generate_without_cancellable: bool,
+    pub platform_cfg: PlatformCfg,
+    pub generate_doc_links: bool,
+    pub generate_prelude: bool,
+    pub prelude: Option<Vec<String>>,
+    pub generate_int_conversions: bool,
+    pub generate_nick_conversions: bool,
+    pub serde: bool,
+    pub unstable_feature: Option<String>,
+    /// Cargo features gating functions, by C identifier
+    pub function_features: BTreeMap<String, String>,
+    pub partitions: Partitions,
+    pub generate_downgrade: bool,
+    pub generate_property_names: bool,
+    pub generate_value_from: bool,
+    pub generate_drop_asserts: bool,
+    pub expose_ptr: bool,
+    pub generate_dox_stubs: bool,
+    pub emit_signals: bool,
+    pub must_use_connect: bool,
+    pub size_type: SizeType,
+    pub checked_casts: bool,
+    pub emit_doc_cfg: bool,
+    pub version_gating: VersionGating,
+    pub into_option_params: bool,
+    pub consume_transfer_full: bool,
+    pub non_final_is_a: bool,
+    pub trace_macro: Option<String>,
+    pub short_constructor_names: bool,
+    pub time_integration: TimeIntegration,
+    pub invalid_char: InvalidChar,
+    pub string_type: StringType,
+    pub init_function: Option<String>,
+    pub module_path: Option<String>,
     pub show_statistics: bool,
     pub concurrency: library::Concurrency,
 }
@@ -67,6 +106,18 @@ impl Config {
             .chain_err(|| ErrorKind::Options(config_file))
     }
 
+    /// Reads the config from a TOML string, with the GIRs taken from `girs_dir`
+    /// and relative paths resolved against it.
+    #[cfg(test)]
+    pub fn from_toml_str(input: &str, girs_dir: &Path) -> Result<Config> {
+        let girs_dir = girs_dir.to_string_lossy().into_owned();
+        let args = try!(
+            Docopt::new(USAGE).and_then(|dopt| dopt.argv(vec!["gir", "-d", &girs_dir[..]]).parse())
+        );
+        let toml = try!(toml::from_str(input));
+        Config::process_options(args, toml, Path::new(&girs_dir))
+    }
+
     fn process_options(
         args: docopt::ArgvMap,
         toml: toml::Value,
@@ -119,14 +170,30 @@ impl Config {
             .map(|t| gobjects::parse_toml(t, concurrency))
             .unwrap_or_default();
         gobjects::parse_status_shorthands(&mut objects, &toml, concurrency);
+        try!(gobjects::read_body_overrides(&mut objects, config_dir));
 
         let external_libraries = try!(read_external_libraries(&toml));
+        let ffi_crate_names = try!(read_ffi_crate_names(&toml));
 
-        let min_cfg_version = match toml.lookup("options.min_cfg_version") {
+        let min_cfg_version: Version = match toml.lookup("options.min_cfg_version") {
             Some(v) => try!(try!(v.as_result_str("options.min_cfg_version")).parse()),
             None => Default::default(),
         };
 
+        let max_cfg_version: Option<Version> = match toml.lookup("options.max_cfg_version") {
+            Some(v) => Some(try!(try!(v.as_result_str("options.max_cfg_version")).parse())),
+            None => None,
+        };
+        if let Some(max_cfg_version) = max_cfg_version {
+            if max_cfg_version < min_cfg_version {
+                bail!(
+                    "options.max_cfg_version {} is less than options.min_cfg_version {}",
+                    max_cfg_version,
+                    min_cfg_version
+                );
+            }
+        }
+
         let make_backup = args.get_bool("-b");
 
         let generate_safety_asserts = match toml.lookup("options.generate_safety_asserts") {
@@ -139,6 +206,168 @@ impl Config {
             None => false,
         };
 
+        let generate_without_cancellable =
+            match toml.lookup("options.generate_without_cancellable") {
+                Some(v) => try!(v.as_result_bool("options.generate_without_cancellable")),
+                None => false,
+            };
+
+        let platform_cfg = try!(read_platform_cfg(&toml));
+
+        let generate_doc_links = match toml.lookup("options.generate_doc_links") {
+            Some(v) => try!(v.as_result_bool("options.generate_doc_links")),
+            None => false,
+        };
+
+        let generate_prelude = match toml.lookup("options.generate_prelude") {
+            Some(v) => try!(v.as_result_bool("options.generate_prelude")),
+            None => false,
+        };
+
+        let prelude = match toml.lookup("options.prelude") {
+            Some(v) => Some(
+                try!(v.as_result_vec("options.prelude"))
+                    .iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect(),
+            ),
+            None => None,
+        };
+
+        let generate_int_conversions = match toml.lookup("options.generate_int_conversions") {
+            Some(v) => try!(v.as_result_bool("options.generate_int_conversions")),
+            None => false,
+        };
+
+        let generate_nick_conversions = match toml.lookup("options.generate_nick_conversions") {
+            Some(v) => try!(v.as_result_bool("options.generate_nick_conversions")),
+            None => false,
+        };
+
+        let serde = match toml.lookup("options.serde") {
+            Some(v) => try!(v.as_result_bool("options.serde")),
+            None => false,
+        };
+
+        let unstable_feature = match toml.lookup("options.unstable_feature") {
+            Some(v) => Some(try!(v.as_result_str("options.unstable_feature")).to_owned()),
+            None => None,
+        };
+
+        let function_features = try!(read_function_features(&toml));
+
+        let partitions = try!(read_partitions(&toml, config_dir));
+
+        let generate_downgrade = match toml.lookup("options.generate_downgrade") {
+            Some(v) => try!(v.as_result_bool("options.generate_downgrade")),
+            None => true,
+        };
+
+        let generate_property_names = match toml.lookup("options.generate_property_names") {
+            Some(v) => try!(v.as_result_bool("options.generate_property_names")),
+            None => false,
+        };
+
+        let generate_value_from = match toml.lookup("options.generate_value_from") {
+            Some(v) => try!(v.as_result_bool("options.generate_value_from")),
+            None => false,
+        };
+
+        let generate_drop_asserts = match toml.lookup("options.generate_drop_asserts") {
+            Some(v) => try!(v.as_result_bool("options.generate_drop_asserts")),
+            None => false,
+        };
+
+        let expose_ptr = match toml.lookup("options.expose_ptr") {
+            Some(v) => try!(v.as_result_bool("options.expose_ptr")),
+            None => false,
+        };
+
+        let generate_dox_stubs = match toml.lookup("options.generate_dox_stubs") {
+            Some(v) => try!(v.as_result_bool("options.generate_dox_stubs")),
+            None => false,
+        };
+
+        let emit_signals = match toml.lookup("options.emit_signals") {
+            Some(v) => try!(v.as_result_bool("options.emit_signals")),
+            None => false,
+        };
+
+        let must_use_connect = match toml.lookup("options.must_use_connect") {
+            Some(v) => try!(v.as_result_bool("options.must_use_connect")),
+            None => false,
+        };
+
+        let size_type = match toml.lookup("options.size_type") {
+            Some(v) => try!(try!(v.as_result_str("options.size_type")).parse()),
+            None => Default::default(),
+        };
+
+        let checked_casts = match toml.lookup("options.checked_casts") {
+            Some(v) => try!(v.as_result_bool("options.checked_casts")),
+            None => false,
+        };
+
+        let short_constructor_names = match toml.lookup("options.short_constructor_names") {
+            Some(v) => try!(v.as_result_bool("options.short_constructor_names")),
+            None => false,
+        };
+
+        let emit_doc_cfg = match toml.lookup("options.emit_doc_cfg") {
+            Some(v) => try!(v.as_result_bool("options.emit_doc_cfg")),
+            None => false,
+        };
+
+        let version_gating = match toml.lookup("options.version_gating") {
+            Some(v) => try!(try!(v.as_result_str("options.version_gating")).parse()),
+            None => Default::default(),
+        };
+
+        let trace_macro = match toml.lookup("options.trace_macro") {
+            Some(v) => Some(try!(v.as_result_str("options.trace_macro")).to_owned()),
+            None => None,
+        };
+
+        let into_option_params = match toml.lookup("options.into_option_params") {
+            Some(v) => try!(v.as_result_bool("options.into_option_params")),
+            None => true,
+        };
+
+        let consume_transfer_full = match toml.lookup("options.consume_transfer_full") {
+            Some(v) => try!(v.as_result_bool("options.consume_transfer_full")),
+            None => false,
+        };
+
+        let non_final_is_a = match toml.lookup("options.non_final_is_a") {
+            Some(v) => try!(v.as_result_bool("options.non_final_is_a")),
+            None => false,
+        };
+
+        let time_integration = match toml.lookup("options.time_integration") {
+            Some(v) => try!(try!(v.as_result_str("options.time_integration")).parse()),
+            None => Default::default(),
+        };
+
+        let invalid_char = match toml.lookup("options.invalid_char") {
+            Some(v) => try!(try!(v.as_result_str("options.invalid_char")).parse()),
+            None => Default::default(),
+        };
+
+        let string_type = match toml.lookup("options.string_type") {
+            Some(v) => try!(try!(v.as_result_str("options.string_type")).parse()),
+            None => Default::default(),
+        };
+
+        let init_function = match toml.lookup("options.init_function") {
+            Some(v) => Some(try!(v.as_result_str("options.init_function")).to_owned()),
+            None => None,
+        };
+
+        let module_path = match toml.lookup("options.module_path") {
+            Some(v) => Some(try!(v.as_result_str("options.module_path")).to_owned()),
+            None => None,
+        };
+
         let show_statistics = args.get_bool("-s");
 
         Ok(Config {
@@ -149,11 +378,46 @@ impl Config {
             library_version: library_version.into(),
             target_path: target_path,
             external_libraries: external_libraries,
+            ffi_crate_names: ffi_crate_names,
             objects: objects,
             min_cfg_version: min_cfg_version,
+            max_cfg_version: max_cfg_version,
             make_backup: make_backup,
             generate_safety_asserts: generate_safety_asserts,
             deprecate_by_min_version: deprecate_by_min_version,
+            generate_without_cancellable: generate_without_cancellable,
+            platform_cfg: platform_cfg,
+            generate_doc_links: generate_doc_links,
+            generate_prelude: generate_prelude,
+            prelude: prelude,
+            generate_int_conversions: generate_int_conversions,
+            generate_nick_conversions: generate_nick_conversions,
+            serde: serde,
+            unstable_feature: unstable_feature,
+            function_features: function_features,
+            partitions: partitions,
+            generate_downgrade: generate_downgrade,
+            generate_property_names: generate_property_names,
+            generate_value_from: generate_value_from,
+            generate_drop_asserts: generate_drop_asserts,
+            expose_ptr: expose_ptr,
+            generate_dox_stubs: generate_dox_stubs,
+            emit_signals: emit_signals,
+            must_use_connect: must_use_connect,
+            size_type: size_type,
+            checked_casts: checked_casts,
+            emit_doc_cfg: emit_doc_cfg,
+            version_gating: version_gating,
+            into_option_params: into_option_params,
+            consume_transfer_full: consume_transfer_full,
+            non_final_is_a: non_final_is_a,
+            trace_macro: trace_macro,
+            short_constructor_names: short_constructor_names,
+            time_integration: time_integration,
+            invalid_char: invalid_char,
+            string_type: string_type,
+            init_function: init_function,
+            module_path: module_path,
             show_statistics: show_statistics,
             concurrency: concurrency,
         })
@@ -172,8 +436,27 @@ impl Config {
     }
 
     pub fn resolve_type_ids(&mut self, library: &Library) {
-        gobjects::resolve_type_ids(&mut self.objects, library)
+        gobjects::resolve_type_ids(
+            &mut self.objects,
+            library,
+            self.unstable_feature.as_ref().map(|s| &s[..]),
+        )
+    }
+}
+
+fn read_function_features(toml: &toml::Value) -> Result<BTreeMap<String, String>> {
+    let mut function_features = BTreeMap::new();
+    if let Some(table) = toml.lookup("options.function_features").and_then(|v| v.as_table()) {
+        for (function, feature) in table {
+            match feature.as_str() {
+                Some(s) => {
+                    function_features.insert(function.clone(), s.to_owned());
+                }
+                None => bail!("For function \"{}\" the feature must be string", function),
+            }
+        }
     }
+    Ok(function_features)
 }
 
 fn read_toml<P: AsRef<Path>>(filename: P) -> Result<toml::Value> {
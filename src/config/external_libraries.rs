@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use toml;
 
 use super::error::*;
@@ -45,6 +46,25 @@ pub fn read_external_libraries(toml: &toml::Value) -> Result<Vec<ExternalLibrary
     Ok(external_libraries)
 }
 
+/// FFI crates of dependency namespaces not named `{crate}_ffi`, by namespace.
+pub fn read_ffi_crate_names(toml: &toml::Value) -> Result<BTreeMap<String, String>> {
+    let mut ffi_crate_names = BTreeMap::new();
+    if let Some(table) = toml.lookup("ffi_crate_name").and_then(|v| v.as_table()) {
+        for (namespace, ffi_crate_name) in table {
+            match ffi_crate_name.as_str() {
+                Some(s) => {
+                    ffi_crate_names.insert(namespace.clone(), s.to_owned());
+                }
+                None => bail!(
+                    "For namespace \"{}\" ffi_crate_name must be string",
+                    namespace
+                ),
+            }
+        }
+    }
+    Ok(ffi_crate_names)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,4 +132,16 @@ other-lib="OtherLib"
         );
     }
 
+    #[test]
+    fn test_read_ffi_crate_names() {
+        let toml = toml(
+            r#"
+[ffi_crate_name]
+Gdk = "gdk_sys"
+"#,
+        );
+        let names = read_ffi_crate_names(&toml).unwrap();
+        assert_eq!(names.get("Gdk").map(|s| &s[..]), Some("gdk_sys"));
+        assert_eq!(names.get("GLib"), None);
+    }
 }
@@ -3,6 +3,7 @@ use super::error::TomlHelper;
 use super::ident::Ident;
 use super::parameter_matchable::Functionlike;
 use super::parsable::{Parsable, Parse};
+use super::string_type::StringType;
 use toml::Value;
 use version::Version;
 
@@ -14,6 +15,8 @@ pub struct Parameter {
     pub constant: bool,
     pub nullable: Option<Nullable>,
     pub length_of: Option<String>,
+    /// `gint64` microseconds accepted as a `Duration`
+    pub duration_us: bool,
 }
 
 impl Parse for Parameter {
@@ -29,7 +32,7 @@ impl Parse for Parameter {
             }
         };
         toml.check_unwanted(
-            &["const", "nullable", "length_of", "name", "pattern"],
+            &["const", "nullable", "length_of", "type", "name", "pattern"],
             &format!("function parameter {}", object_name),
         );
 
@@ -43,12 +46,25 @@ impl Parse for Parameter {
             .and_then(|val| val.as_str())
             .map(|s| if s == "return" { "" } else { s })
             .map(ToOwned::to_owned);
+        let duration_us = match toml.lookup("type").and_then(|val| val.as_str()) {
+            Some("duration_us") => true,
+            Some(type_) => {
+                error!(
+                    "Unknown type \"{}\" for parameter of object {}",
+                    type_,
+                    object_name
+                );
+                false
+            }
+            None => false,
+        };
 
         Some(Parameter {
             ident: ident,
             constant: constant,
             nullable: nullable,
             length_of: length_of,
+            duration_us: duration_us,
         })
     }
 }
@@ -102,6 +118,15 @@ pub struct Function {
     pub doc_hidden: bool,
     pub is_windows_utf8: bool,
     pub disable_length_detect: bool,
+    pub body_override: Option<String>,
+    pub body_override_file: Option<String>,
+    pub main_thread_only: Option<bool>,
+    pub rename: Option<String>,
+    pub doc_ignore_parameters: Vec<String>,
+    pub removed_version: Option<Version>,
+    pub string_type: Option<StringType>,
+    pub error_type: Option<String>,
+    pub chain: bool,
 }
 
 impl Parse for Function {
@@ -127,6 +152,15 @@ impl Parse for Function {
                 "doc_hidden",
                 "is_windows_utf8",
                 "disable_length_detect",
+                "body_override",
+                "body_override_file",
+                "main_thread_only",
+                "rename",
+                "doc_ignore_parameters",
+                "removed_version",
+                "string_type",
+                "error_type",
+                "chain",
                 "pattern",
             ],
             &format!("function {}", object_name),
@@ -152,6 +186,51 @@ impl Parse for Function {
         let disable_length_detect = toml.lookup("disable_length_detect")
             .and_then(|val| val.as_bool())
             .unwrap_or(false);
+        let body_override = toml.lookup("body_override")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_owned());
+        let body_override_file = toml.lookup("body_override_file")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_owned());
+        let main_thread_only = toml.lookup("main_thread_only").and_then(|val| val.as_bool());
+        let rename = toml.lookup("rename")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_owned());
+        let doc_ignore_parameters = toml.lookup("doc_ignore_parameters")
+            .and_then(|v| v.as_array())
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_owned()))
+                    .collect()
+            })
+            .unwrap_or_else(Vec::new);
+        let removed_version = toml.lookup("removed_version")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse().ok());
+        let string_type = match toml.lookup("string_type").and_then(|v| v.as_str()) {
+            Some(s) => match s.parse() {
+                Ok(string_type) => Some(string_type),
+                Err(e) => {
+                    error!("{} for function {:?} of {}", e, ident, object_name);
+                    None
+                }
+            },
+            None => None,
+        };
+        let error_type = toml.lookup("error_type")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_owned());
+        let chain = toml.lookup("chain")
+            .and_then(|val| val.as_bool())
+            .unwrap_or(false);
+        if body_override.is_some() && body_override_file.is_some() {
+            error!(
+                "Both body_override and body_override_file given for function {:?} of {}",
+                ident,
+                object_name
+            );
+        }
 
         Some(Function {
             ident: ident,
@@ -163,6 +242,15 @@ impl Parse for Function {
             doc_hidden: doc_hidden,
             is_windows_utf8: is_windows_utf8,
             disable_length_detect: disable_length_detect,
+            body_override: body_override,
+            body_override_file: body_override_file,
+            main_thread_only: main_thread_only,
+            rename: rename,
+            doc_ignore_parameters: doc_ignore_parameters,
+            removed_version: removed_version,
+            string_type: string_type,
+            error_type: error_type,
+            chain: chain,
         })
     }
 }
@@ -218,6 +306,40 @@ ignore = true
         assert_eq!(f.ignore, true);
     }
 
+    #[test]
+    fn function_parse_body_override() {
+        let toml = toml(
+            r#"
+name = "func1"
+body_override = """
+unsafe {
+    ffi::func1()
+}
+"""
+"#,
+        );
+        let f = Function::parse(&toml, "a").unwrap();
+        assert_eq!(
+            f.body_override,
+            Some("unsafe {\n    ffi::func1()\n}\n".to_owned())
+        );
+        assert_eq!(f.body_override_file, None);
+    }
+
+    #[test]
+    fn function_parse_main_thread_only() {
+        let toml = toml(
+            r#"
+name = "func1"
+main_thread_only = false
+"#,
+        );
+        let f = Function::parse(&toml, "a").unwrap();
+        assert_eq!(f.main_thread_only, Some(false));
+        let f = Function::parse(&toml("name = \"func2\""), "a").unwrap();
+        assert_eq!(f.main_thread_only, None);
+    }
+
     #[test]
     fn function_parse_version_default() {
         let toml = toml(
@@ -1,11 +1,14 @@
 use std::ascii::AsciiExt;
 use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
 use std::str::FromStr;
 use toml::Value;
 
 use library;
 use library::{Library, TypeId, MAIN_NAMESPACE};
-use config::error::TomlHelper;
+use config::error::{Result as ConfigResult, ResultExt, TomlHelper};
 use config::parsable::{Parsable, Parse};
 use super::child_properties::ChildProperties;
 use super::functions::Functions;
@@ -14,6 +17,7 @@ use super::properties::Properties;
 use super::signals::{Signal, Signals};
 use version::Version;
 use analysis::{ref_mode, conversion_type};
+use analysis::functions::stability_cfg_condition;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum GStatus {
@@ -54,6 +58,41 @@ impl FromStr for GStatus {
     }
 }
 
+/// How the C `next` function of an iterator signals the end
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IteratorEnd {
+    /// Returns the next item or NULL
+    Null,
+    /// Returns `FALSE` and the next item in an out parameter otherwise
+    False,
+}
+
+impl FromStr for IteratorEnd {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "null" => Ok(IteratorEnd::Null),
+            "false" => Ok(IteratorEnd::False),
+            e => Err(format!("Wrong iterator end: \"{}\"", e)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IteratorNext {
+    pub func: String,
+    pub item: String,
+    pub end: IteratorEnd,
+}
+
+/// Functions of a container type used to generate a bounds-checked `get(index)`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexGetter {
+    pub get: String,
+    pub len: Option<String>,
+    pub item: String,
+}
+
 /// Info about `GObject` descendant
 #[derive(Clone, Debug)]
 pub struct GObject {
@@ -74,6 +113,28 @@ pub struct GObject {
     pub ref_mode: Option<ref_mode::RefMode>,
     pub must_use: bool,
     pub conversion_type: Option<conversion_type::ConversionType>,
+    pub as_ref_str: Option<String>,
+    pub eq_str: Option<String>,
+    pub newtype: bool,
+    pub non_exhaustive: bool,
+    pub boxed_inline: bool,
+    pub fundamental: Option<bool>,
+    pub final_type: Option<bool>,
+    pub serde: Option<bool>,
+    pub extra_derives: Vec<String>,
+    pub manual_traits: Vec<String>,
+    pub generate_eq: bool,
+    pub iterator_next: Option<IteratorNext>,
+    pub index: Option<IndexGetter>,
+    pub from_str: Option<String>,
+    pub as_ref_ancestors: bool,
+    pub error_domain: Option<String>,
+    pub identity_eq: bool,
+    pub requires_symbol: Option<String>,
+    pub atomic_fields: Vec<String>,
+    pub truncate_bits: bool,
+    pub borrowed_type: Option<String>,
+    pub into_glib_error: bool,
 }
 
 impl Default for GObject {
@@ -96,6 +157,28 @@ impl Default for GObject {
             ref_mode: None,
             must_use: false,
             conversion_type: None,
+            as_ref_str: None,
+            eq_str: None,
+            newtype: false,
+            non_exhaustive: false,
+            boxed_inline: false,
+            fundamental: None,
+            final_type: None,
+            serde: None,
+            extra_derives: Vec::new(),
+            manual_traits: Vec::new(),
+            generate_eq: false,
+            iterator_next: None,
+            index: None,
+            from_str: None,
+            as_ref_ancestors: false,
+            error_domain: None,
+            identity_eq: false,
+            requires_symbol: None,
+            atomic_fields: Vec::new(),
+            truncate_bits: false,
+            borrowed_type: None,
+            into_glib_error: false,
         }
     }
 }
@@ -166,6 +249,28 @@ fn parse_object(toml_object: &Value, concurrency: library::Concurrency) -> GObje
             "trait_name",
             "cfg_condition",
             "must_use",
+            "as_ref_str",
+            "eq_str",
+            "newtype",
+            "non_exhaustive",
+            "boxed_inline",
+            "fundamental",
+            "final",
+            "serde",
+            "extra_derives",
+            "manual_traits",
+            "generate_eq",
+            "iterator_next",
+            "index",
+            "from_str",
+            "as_ref_ancestors",
+            "error_domain",
+            "identity_eq",
+            "requires_symbol",
+            "atomic_fields",
+            "truncate_bits",
+            "borrowed_type",
+            "into_glib_error",
         ],
         &format!("object {}", name),
     );
@@ -230,6 +335,103 @@ fn parse_object(toml_object: &Value, concurrency: library::Concurrency) -> GObje
         .lookup("must_use")
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
+    let as_ref_str = toml_object
+        .lookup("as_ref_str")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_owned());
+    let eq_str = toml_object
+        .lookup("eq_str")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_owned());
+    let newtype = toml_object
+        .lookup("newtype")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let non_exhaustive = toml_object
+        .lookup("non_exhaustive")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let boxed_inline = toml_object
+        .lookup("boxed_inline")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let fundamental = toml_object
+        .lookup("fundamental")
+        .and_then(|v| v.as_bool());
+    let final_type = toml_object.lookup("final").and_then(|v| v.as_bool());
+    let serde = toml_object.lookup("serde").and_then(|v| v.as_bool());
+    let extra_derives = toml_object
+        .lookup("extra_derives")
+        .and_then(|v| v.as_array())
+        .map(|derives| {
+            derives
+                .iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_owned()))
+                .collect()
+        })
+        .unwrap_or_else(Vec::new);
+    let manual_traits = toml_object
+        .lookup("manual_traits")
+        .and_then(|v| v.as_array())
+        .map(|traits| {
+            traits
+                .iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_owned()))
+                .collect()
+        })
+        .unwrap_or_else(Vec::new);
+    let generate_eq = toml_object
+        .lookup("generate_eq")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let iterator_next = toml_object
+        .lookup("iterator_next")
+        .and_then(|v| parse_iterator_next(v, &name));
+    let index = toml_object
+        .lookup("index")
+        .and_then(|v| parse_index(v, &name));
+    let from_str = toml_object
+        .lookup("from_str")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_owned());
+    let as_ref_ancestors = toml_object
+        .lookup("as_ref_ancestors")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let error_domain = toml_object
+        .lookup("error_domain")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_owned());
+    let identity_eq = toml_object
+        .lookup("identity_eq")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let requires_symbol = toml_object
+        .lookup("requires_symbol")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_owned());
+    let borrowed_type = toml_object
+        .lookup("borrowed_type")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_owned());
+    let atomic_fields = toml_object
+        .lookup("atomic_fields")
+        .and_then(|v| v.as_array())
+        .map(|fields| {
+            fields
+                .iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_owned()))
+                .collect()
+        })
+        .unwrap_or_else(Vec::new);
+    let truncate_bits = toml_object
+        .lookup("truncate_bits")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let into_glib_error = toml_object
+        .lookup("into_glib_error")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
 
     if status != GStatus::Manual && ref_mode.is_some() {
         warn!("ref_mode configuration used for non-manual object {}", name);
@@ -257,6 +459,70 @@ fn parse_object(toml_object: &Value, concurrency: library::Concurrency) -> GObje
         ref_mode: ref_mode,
         must_use: must_use,
         conversion_type: conversion_type,
+        as_ref_str: as_ref_str,
+        eq_str: eq_str,
+        newtype: newtype,
+        non_exhaustive: non_exhaustive,
+        boxed_inline: boxed_inline,
+        fundamental: fundamental,
+        final_type: final_type,
+        serde: serde,
+        extra_derives: extra_derives,
+        manual_traits: manual_traits,
+        generate_eq: generate_eq,
+        iterator_next: iterator_next,
+        index: index,
+        from_str: from_str,
+        as_ref_ancestors: as_ref_ancestors,
+        error_domain: error_domain,
+        identity_eq: identity_eq,
+        requires_symbol: requires_symbol,
+        atomic_fields: atomic_fields,
+        truncate_bits: truncate_bits,
+        borrowed_type: borrowed_type,
+        into_glib_error: into_glib_error,
+    }
+}
+
+fn parse_iterator_next(toml: &Value, object_name: &str) -> Option<IteratorNext> {
+    toml.check_unwanted(&["func", "item", "end"], &format!("iterator_next {}", object_name));
+    let func = toml.lookup("func").and_then(|v| v.as_str());
+    let item = toml.lookup("item").and_then(|v| v.as_str());
+    let end = toml.lookup("end")
+        .and_then(|v| v.as_str())
+        .map_or(Ok(IteratorEnd::Null), IteratorEnd::from_str);
+    match (func, item, end) {
+        (Some(func), Some(item), Ok(end)) => Some(IteratorNext {
+            func: func.to_owned(),
+            item: item.to_owned(),
+            end: end,
+        }),
+        (_, _, Err(e)) => {
+            error!("Invalid iterator_next for {}: {}", object_name, e);
+            None
+        }
+        _ => {
+            error!("No func or item given for iterator_next of {}", object_name);
+            None
+        }
+    }
+}
+
+fn parse_index(toml: &Value, object_name: &str) -> Option<IndexGetter> {
+    toml.check_unwanted(&["get", "len", "item"], &format!("index {}", object_name));
+    let get = toml.lookup("get").and_then(|v| v.as_str());
+    let len = toml.lookup("len").and_then(|v| v.as_str());
+    let item = toml.lookup("item").and_then(|v| v.as_str());
+    match (get, item) {
+        (Some(get), Some(item)) => Some(IndexGetter {
+            get: get.to_owned(),
+            len: len.map(|s| s.to_owned()),
+            item: item.to_owned(),
+        }),
+        _ => {
+            error!("No get or item given for index of {}", object_name);
+            None
+        }
     }
 }
 
@@ -298,7 +564,30 @@ fn parse_status_shorthand(
     );
 }
 
-pub fn resolve_type_ids(objects: &mut GObjects, library: &Library) {
+/// Loads `body_override_file` snippets of functions relative to the config directory.
+pub fn read_body_overrides(objects: &mut GObjects, config_dir: &Path) -> ConfigResult<()> {
+    for object in objects.values_mut() {
+        for function in &mut object.functions {
+            if let Some(ref file) = function.body_override_file {
+                let path = config_dir.join(file);
+                let mut body = String::new();
+                try!(
+                    File::open(&path)
+                        .and_then(|mut f| f.read_to_string(&mut body))
+                        .chain_err(|| format!("Can't read body override \"{}\"", path.display()))
+                );
+                function.body_override = Some(body);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn resolve_type_ids(
+    objects: &mut GObjects,
+    library: &Library,
+    unstable_feature: Option<&str>,
+) {
     let ns = library.namespace(MAIN_NAMESPACE);
     let global_functions_name = format!("{}.*", ns.name);
 
@@ -308,5 +597,89 @@ pub fn resolve_type_ids(objects: &mut GObjects, library: &Library) {
             warn!("Configured object `{}` missing from the library", name);
         }
         object.type_id = type_id;
+        if let Some(type_id) = type_id {
+            // Gates the whole type, the API inside is only gated by its own stability
+            object.cfg_condition = stability_cfg_condition(
+                object.cfg_condition.take(),
+                library.type_(type_id).stability(),
+                unstable_feature,
+            );
+        }
+        if !requirements_met(object, library) {
+            info!(
+                "Skipping `{}`, the library has no `{}`",
+                name,
+                object.requires_symbol.as_ref().unwrap()
+            );
+            object.status = GStatus::Ignore;
+        }
+    }
+}
+
+/// Lets one config target several builds of a library with optional features.
+fn requirements_met(object: &GObject, library: &Library) -> bool {
+    match object.requires_symbol {
+        Some(ref symbol) => library.has_symbol(symbol),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use library::{Function, FunctionKind, Parameter};
+
+    fn library_with(c_identifier: &str) -> Library {
+        let mut library = Library::new("Gtk");
+        library.add_function(
+            MAIN_NAMESPACE,
+            Function {
+                name: "foo".into(),
+                c_identifier: Some(c_identifier.into()),
+                kind: FunctionKind::Global,
+                parameters: Vec::new(),
+                ret: Parameter {
+                    name: String::new(),
+                    typ: TypeId::tid_none(),
+                    c_type: "void".into(),
+                    instance_parameter: false,
+                    direction: library::ParameterDirection::Return,
+                    transfer: library::Transfer::None,
+                    caller_allocates: false,
+                    nullable: library::Nullable(false),
+                    allow_none: false,
+                    array_length: None,
+                    is_error: false,
+                    doc: None,
+                },
+                throws: false,
+                version: None,
+                deprecated_version: None,
+                stability: Default::default(),
+                doc: None,
+                doc_deprecated: None,
+            },
+        );
+        library
+    }
+
+    #[test]
+    fn unmet_required_symbol_skips_object() {
+        let library = library_with("gtk_foo");
+        let mut objects = GObjects::new();
+        for &(name, symbol) in &[("Gtk.Present", "gtk_foo"), ("Gtk.Absent", "gtk_bar")] {
+            objects.insert(
+                name.into(),
+                GObject {
+                    name: name.into(),
+                    status: GStatus::Generate,
+                    requires_symbol: Some(symbol.into()),
+                    ..Default::default()
+                },
+            );
+        }
+        resolve_type_ids(&mut objects, &library, None);
+        assert_eq!(objects["Gtk.Present"].status, GStatus::Generate);
+        assert_eq!(objects["Gtk.Absent"].status, GStatus::Ignore);
     }
 }
@@ -0,0 +1,80 @@
+use std::str::FromStr;
+
+/// Handling of invalid code points in returned `gunichar`s.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InvalidChar {
+    Panic,   // `char`, panics like `glib`'s `from_glib`
+    Option,  // `Option<char>`, `None` for invalid code points
+    Replace, // `char`, invalid code points are replaced with U+FFFD
+}
+
+impl InvalidChar {
+    pub fn rust_type(&self) -> &'static str {
+        match *self {
+            InvalidChar::Panic | InvalidChar::Replace => "char",
+            InvalidChar::Option => "Option<char>",
+        }
+    }
+
+    /// Prefix and suffix converting the returned `u32`, `None` if `from_glib` is used.
+    pub fn from_u32(&self) -> Option<(&'static str, &'static str)> {
+        match *self {
+            InvalidChar::Panic => None,
+            InvalidChar::Option => Some(("::std::char::from_u32(", ")")),
+            InvalidChar::Replace => Some((
+                "::std::char::from_u32(",
+                ").unwrap_or(::std::char::REPLACEMENT_CHARACTER)",
+            )),
+        }
+    }
+}
+
+impl Default for InvalidChar {
+    fn default() -> InvalidChar {
+        InvalidChar::Panic
+    }
+}
+
+impl FromStr for InvalidChar {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "panic" => Ok(InvalidChar::Panic),
+            "option" => Ok(InvalidChar::Option),
+            "replace" => Ok(InvalidChar::Replace),
+            e => Err(format!("Wrong invalid char handling: \"{}\"", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_char_from_str() {
+        assert_eq!(InvalidChar::from_str("panic"), Ok(InvalidChar::Panic));
+        assert_eq!(InvalidChar::from_str("option"), Ok(InvalidChar::Option));
+        assert_eq!(InvalidChar::from_str("replace"), Ok(InvalidChar::Replace));
+        assert!(InvalidChar::from_str("ignore").is_err());
+    }
+
+    #[test]
+    fn invalid_char_conversions() {
+        assert_eq!(InvalidChar::Panic.rust_type(), "char");
+        assert_eq!(InvalidChar::Panic.from_u32(), None);
+        assert_eq!(InvalidChar::Option.rust_type(), "Option<char>");
+        assert_eq!(
+            InvalidChar::Option.from_u32(),
+            Some(("::std::char::from_u32(", ")"))
+        );
+        assert_eq!(InvalidChar::Replace.rust_type(), "char");
+        assert_eq!(
+            InvalidChar::Replace.from_u32(),
+            Some((
+                "::std::char::from_u32(",
+                ").unwrap_or(::std::char::REPLACEMENT_CHARACTER)"
+            ))
+        );
+    }
+}
@@ -7,14 +7,27 @@ pub mod parameter_matchable;
 pub mod functions;
 pub mod gobjects;
 pub mod ident;
+pub mod invalid_char;
 pub mod matchable;
 pub mod members;
 pub mod parsable;
+pub mod partitions;
+mod platform_cfg;
 pub mod properties;
 pub mod signals;
+pub mod size_type;
+pub mod string_type;
+pub mod time_integration;
+pub mod version_gating;
 pub mod work_mode;
 
 pub use self::config::Config;
 pub use self::external_libraries::ExternalLibrary;
+pub use self::invalid_char::InvalidChar;
+pub use self::platform_cfg::{combine_cfg_conditions, PlatformCfg};
+pub use self::size_type::SizeType;
+pub use self::string_type::StringType;
+pub use self::time_integration::TimeIntegration;
+pub use self::version_gating::VersionGating;
 pub use self::work_mode::WorkMode;
 pub use self::child_properties::{ChildProperties, ChildProperty};
@@ -0,0 +1,167 @@
+use std::path::{Path, PathBuf};
+use toml;
+
+use super::error::*;
+use nameutil::split_namespace_name;
+
+/// A part of the main namespace generated into a separate crate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Partition {
+    pub crate_name: String,
+    pub target_path: PathBuf,
+    /// Prefixes of type names without namespace, e.g. `"Video"` for `Gst.VideoInfo`
+    pub prefixes: Vec<String>,
+    /// Explicitly listed full names of types
+    pub objects: Vec<String>,
+}
+
+impl Partition {
+    pub fn contains(&self, full_name: &str) -> bool {
+        if self.objects.iter().any(|o| o == full_name) {
+            return true;
+        }
+        let name = split_namespace_name(full_name).1;
+        self.prefixes.iter().any(|p| name.starts_with(&p[..]))
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Partitions {
+    partitions: Vec<Partition>,
+}
+
+impl Partitions {
+    pub fn is_empty(&self) -> bool {
+        self.partitions.is_empty()
+    }
+
+    pub fn iter(&self) -> ::std::slice::Iter<Partition> {
+        self.partitions.iter()
+    }
+
+    /// Returns the partition the type is generated into, `None` for the main crate.
+    pub fn partition_of(&self, full_name: &str) -> Option<&Partition> {
+        self.partitions.iter().find(|p| p.contains(full_name))
+    }
+
+    pub fn crate_of(&self, full_name: &str) -> Option<&str> {
+        self.partition_of(full_name).map(|p| &p.crate_name[..])
+    }
+}
+
+pub fn read_partitions(toml: &toml::Value, config_dir: &Path) -> Result<Partitions> {
+    let mut partitions = Vec::new();
+    if let Some(value) = toml.lookup("partition") {
+        for toml_partition in try!(value.as_result_vec("partition")) {
+            let crate_name = match toml_partition.lookup("crate_name").and_then(|v| v.as_str()) {
+                Some(crate_name) => crate_name.to_owned(),
+                None => bail!("No crate_name for partition"),
+            };
+            let target_path = match toml_partition.lookup("target_path").and_then(|v| v.as_str()) {
+                Some(path) => config_dir.join(path),
+                None => bail!("No target_path for partition \"{}\"", crate_name),
+            };
+            let prefixes = try!(read_strings(toml_partition, "prefixes", &crate_name));
+            let objects = try!(read_strings(toml_partition, "objects", &crate_name));
+            if prefixes.is_empty() && objects.is_empty() {
+                warn!("Partition \"{}\" contains no types", crate_name);
+            }
+            partitions.push(Partition {
+                crate_name: crate_name,
+                target_path: target_path,
+                prefixes: prefixes,
+                objects: objects,
+            });
+        }
+    }
+
+    Ok(Partitions {
+        partitions: partitions,
+    })
+}
+
+fn read_strings(toml: &toml::Value, key: &str, crate_name: &str) -> Result<Vec<String>> {
+    match toml.lookup(key) {
+        Some(&toml::Value::Array(ref values)) => {
+            let mut strings = Vec::with_capacity(values.len());
+            for value in values {
+                match value.as_str() {
+                    Some(s) => strings.push(s.to_owned()),
+                    None => bail!("For partition \"{}\" {} must be strings", crate_name, key),
+                }
+            }
+            Ok(strings)
+        }
+        Some(_) => bail!("For partition \"{}\" {} must be an array", crate_name, key),
+        None => Ok(Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toml;
+
+    fn toml(input: &str) -> toml::Value {
+        let value = toml::from_str(&input);
+        assert!(value.is_ok());
+        value.unwrap()
+    }
+
+    fn partitions() -> Partitions {
+        let toml = toml(
+            r#"
+[[partition]]
+crate_name = "gst_video"
+target_path = "../gstreamer-video"
+prefixes = ["Video"]
+objects = ["Gst.ColorBalance"]
+
+[[partition]]
+crate_name = "gst_audio"
+target_path = "../gstreamer-audio"
+prefixes = ["Audio"]
+"#,
+        );
+        read_partitions(&toml, Path::new("conf")).unwrap()
+    }
+
+    #[test]
+    fn partitions_missing() {
+        let toml = toml("[options]");
+        assert!(read_partitions(&toml, Path::new("")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn partition_without_path() {
+        let toml = toml(
+            r#"
+[[partition]]
+crate_name = "gst_video"
+"#,
+        );
+        assert!(read_partitions(&toml, Path::new("")).is_err());
+    }
+
+    #[test]
+    fn partition_target_path_is_relative_to_config() {
+        let partitions = partitions();
+        let targets: Vec<_> = partitions.iter().map(|p| p.target_path.clone()).collect();
+        assert_eq!(
+            targets,
+            vec![
+                Path::new("conf").join("../gstreamer-video"),
+                Path::new("conf").join("../gstreamer-audio"),
+            ]
+        );
+    }
+
+    #[test]
+    fn partition_crate_of() {
+        let partitions = partitions();
+        assert_eq!(partitions.crate_of("Gst.VideoInfo"), Some("gst_video"));
+        assert_eq!(partitions.crate_of("Gst.ColorBalance"), Some("gst_video"));
+        assert_eq!(partitions.crate_of("Gst.AudioInfo"), Some("gst_audio"));
+        assert_eq!(partitions.crate_of("Gst.Element"), None);
+    }
+}
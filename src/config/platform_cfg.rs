@@ -0,0 +1,129 @@
+use toml;
+
+use super::error::*;
+
+/// Maps C symbol prefixes (or whole symbols) to target cfgs,
+/// e.g. `"g_unix_" = "unix"`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PlatformCfg {
+    prefixes: Vec<(String, String)>,
+}
+
+impl PlatformCfg {
+    /// Returns the cfg of the longest prefix matching `symbol`.
+    pub fn cfg_for(&self, symbol: &str) -> Option<&str> {
+        self.prefixes
+            .iter()
+            .filter(|&&(ref prefix, _)| symbol.starts_with(&prefix[..]))
+            .max_by_key(|&&(ref prefix, _)| prefix.len())
+            .map(|&(_, ref cfg)| &cfg[..])
+    }
+
+    /// Combines the configured `cfg_condition` with the platform cfg of `symbol`.
+    pub fn cfg_condition(&self, cfg_condition: Option<String>, symbol: &str) -> Option<String> {
+        combine_cfg_conditions(cfg_condition, self.cfg_for(symbol))
+    }
+}
+
+/// Combines two optional cfg conditions with `all(...)`.
+pub fn combine_cfg_conditions(
+    cfg_condition: Option<String>,
+    other: Option<&str>,
+) -> Option<String> {
+    match (cfg_condition, other) {
+        (Some(cond), Some(other)) => if cond == other {
+            Some(cond)
+        } else {
+            Some(format!("all({}, {})", cond, other))
+        },
+        (Some(cond), None) => Some(cond),
+        (None, Some(other)) => Some(other.to_owned()),
+        (None, None) => None,
+    }
+}
+
+pub fn read_platform_cfg(toml: &toml::Value) -> Result<PlatformCfg> {
+    let mut prefixes = Vec::new();
+    if let Some(value) = toml.lookup("options.platform_cfg") {
+        let table = try!(value.as_table().ok_or_else(|| {
+            format!(
+                "Invalid `options.platform_cfg` value, expected a table, found {}",
+                value.type_str()
+            )
+        }));
+        for (prefix, cfg) in table {
+            match cfg.as_str() {
+                Some(cfg) => prefixes.push((prefix.clone(), cfg.to_owned())),
+                None => bail!("For platform prefix \"{}\" cfg must be string", prefix),
+            }
+        }
+    }
+
+    Ok(PlatformCfg { prefixes: prefixes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toml;
+
+    fn toml(input: &str) -> toml::Value {
+        let value = toml::from_str(&input);
+        assert!(value.is_ok());
+        value.unwrap()
+    }
+
+    fn platform_cfg() -> PlatformCfg {
+        let toml = toml(
+            r#"
+[options]
+platform_cfg = { "g_unix_" = "unix", "g_win32_" = "windows", "g_unix_fd_" = "target_os = \"linux\"" }
+"#,
+        );
+        read_platform_cfg(&toml).unwrap()
+    }
+
+    #[test]
+    fn platform_cfg_missing() {
+        let toml = toml("[options]");
+        assert_eq!(read_platform_cfg(&toml).unwrap(), Default::default());
+    }
+
+    #[test]
+    fn platform_cfg_wrong_type() {
+        let toml = toml(
+            r#"
+[options]
+platform_cfg = "unix"
+"#,
+        );
+        assert!(read_platform_cfg(&toml).is_err());
+    }
+
+    #[test]
+    fn platform_cfg_prefix() {
+        let cfg = platform_cfg();
+        assert_eq!(cfg.cfg_for("g_unix_mounts_get"), Some("unix"));
+        assert_eq!(cfg.cfg_for("g_win32_get_locale"), Some("windows"));
+        assert_eq!(cfg.cfg_for("g_unix_fd_list_new"), Some("target_os = \"linux\""));
+        assert_eq!(cfg.cfg_for("g_file_new_for_path"), None);
+    }
+
+    #[test]
+    fn platform_cfg_condition() {
+        let cfg = platform_cfg();
+        assert_eq!(
+            cfg.cfg_condition(None, "g_unix_mounts_get"),
+            Some("unix".to_owned())
+        );
+        assert_eq!(
+            cfg.cfg_condition(Some("mycond".to_owned()), "g_unix_mounts_get"),
+            Some("all(mycond, unix)".to_owned())
+        );
+        assert_eq!(
+            cfg.cfg_condition(Some("mycond".to_owned()), "g_file_new_for_path"),
+            Some("mycond".to_owned())
+        );
+        assert_eq!(cfg.cfg_condition(None, "g_file_new_for_path"), None);
+    }
+}
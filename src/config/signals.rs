@@ -101,6 +101,8 @@ pub struct Signal {
     pub ret: Return,
     pub concurrency: library::Concurrency,
     pub doc_hidden: bool,
+    /// Generate an `emit_*` method, by default only for action signals
+    pub emit: Option<bool>,
 }
 
 impl Signal {
@@ -130,6 +132,7 @@ impl Signal {
                 "name",
                 "pattern",
                 "concurrency",
+                "emit",
             ],
             &format!("signal {}", object_name),
         );
@@ -156,6 +159,8 @@ impl Signal {
             .and_then(|val| val.as_bool())
             .unwrap_or(false);
 
+        let emit = toml.lookup("emit").and_then(|val| val.as_bool());
+
         Some(Signal {
             ident: ident,
             ignore: ignore,
@@ -165,6 +170,7 @@ impl Signal {
             ret: ret,
             concurrency: concurrency,
             doc_hidden: doc_hidden,
+            emit: emit,
         })
     }
 }
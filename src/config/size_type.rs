@@ -0,0 +1,61 @@
+use std::str::FromStr;
+
+/// Rust types `gsize` and `gssize` are mapped to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SizeType {
+    Usize, // platform native, like in C
+    U64,   // fixed width, casted at the FFI boundary
+}
+
+impl SizeType {
+    pub fn unsigned(&self) -> &'static str {
+        match *self {
+            SizeType::Usize => "usize",
+            SizeType::U64 => "u64",
+        }
+    }
+
+    pub fn signed(&self) -> &'static str {
+        match *self {
+            SizeType::Usize => "isize",
+            SizeType::U64 => "i64",
+        }
+    }
+}
+
+impl Default for SizeType {
+    fn default() -> SizeType {
+        SizeType::Usize
+    }
+}
+
+impl FromStr for SizeType {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "usize" => Ok(SizeType::Usize),
+            "u64" => Ok(SizeType::U64),
+            e => Err(format!("Wrong size type: \"{}\"", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_type_from_str() {
+        assert_eq!(SizeType::from_str("usize"), Ok(SizeType::Usize));
+        assert_eq!(SizeType::from_str("u64"), Ok(SizeType::U64));
+        assert!(SizeType::from_str("u32").is_err());
+    }
+
+    #[test]
+    fn size_type_names() {
+        assert_eq!(SizeType::Usize.unsigned(), "usize");
+        assert_eq!(SizeType::Usize.signed(), "isize");
+        assert_eq!(SizeType::U64.unsigned(), "u64");
+        assert_eq!(SizeType::U64.signed(), "i64");
+    }
+}
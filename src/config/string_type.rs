@@ -0,0 +1,46 @@
+use std::str::FromStr;
+
+/// Rust type of returned `utf8` strings.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StringType {
+    String,  // `String`, copied and validated
+    GString, // `glib::GString`, keeping the C string without a copy
+}
+
+impl StringType {
+    pub fn rust_type(&self) -> &'static str {
+        match *self {
+            StringType::String => "String",
+            StringType::GString => "GString",
+        }
+    }
+}
+
+impl Default for StringType {
+    fn default() -> StringType {
+        StringType::String
+    }
+}
+
+impl FromStr for StringType {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "string" => Ok(StringType::String),
+            "gstring" => Ok(StringType::GString),
+            e => Err(format!("Wrong string type: \"{}\"", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_type_from_str() {
+        assert_eq!(StringType::from_str("string"), Ok(StringType::String));
+        assert_eq!(StringType::from_str("gstring"), Ok(StringType::GString));
+        assert!(StringType::from_str("str").is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use std::str::FromStr;
+
+/// Rust types `GLib.DateTime`s and `time_t`s are converted to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimeIntegration {
+    None,   // keep `glib::DateTime`
+    Std,    // `std::time::SystemTime`
+    Chrono, // `chrono::DateTime<chrono::Utc>`
+}
+
+impl TimeIntegration {
+    pub fn rust_type(&self) -> Option<&'static str> {
+        match *self {
+            TimeIntegration::None => None,
+            TimeIntegration::Std => Some("::std::time::SystemTime"),
+            TimeIntegration::Chrono => Some("::chrono::DateTime<::chrono::Utc>"),
+        }
+    }
+
+    /// Returns the expression converting the `DateTime` `dt` to the Rust type.
+    ///
+    /// `g_date_time_to_unix()` rounds towards negative infinity and is independent of the
+    /// time zone of `dt`, the microseconds within the second are added on top of it.
+    pub fn from_date_time(&self) -> Option<&'static str> {
+        match *self {
+            TimeIntegration::None => None,
+            TimeIntegration::Std => Some(
+                "let secs = dt.to_unix(); \
+                 let micros = ::std::time::Duration::from_micros(dt.get_microsecond() as u64); \
+                 if secs >= 0 { \
+                 ::std::time::UNIX_EPOCH + ::std::time::Duration::from_secs(secs as u64) + micros \
+                 } else { \
+                 ::std::time::UNIX_EPOCH - ::std::time::Duration::from_secs(-secs as u64) + micros \
+                 }",
+            ),
+            TimeIntegration::Chrono => Some(
+                "::chrono::TimeZone::timestamp(&::chrono::Utc, dt.to_unix(), \
+                 dt.get_microsecond() as u32 * 1000)",
+            ),
+        }
+    }
+
+    /// Returns the expression converting the `i64` seconds since the Unix epoch `secs`
+    /// to the Rust type.
+    pub fn from_unix(&self) -> Option<&'static str> {
+        match *self {
+            TimeIntegration::None => None,
+            TimeIntegration::Std => Some(
+                "if secs >= 0 { \
+                 ::std::time::UNIX_EPOCH + ::std::time::Duration::from_secs(secs as u64) \
+                 } else { \
+                 ::std::time::UNIX_EPOCH - ::std::time::Duration::from_secs(-secs as u64) \
+                 }",
+            ),
+            TimeIntegration::Chrono => {
+                Some("::chrono::TimeZone::timestamp(&::chrono::Utc, secs, 0)")
+            }
+        }
+    }
+
+    /// Returns the expression converting the time `name` to the `i64` seconds since
+    /// the Unix epoch, rounded towards negative infinity like `g_date_time_to_unix()`.
+    pub fn to_unix(&self, name: &str) -> Option<String> {
+        match *self {
+            TimeIntegration::None => None,
+            TimeIntegration::Std => Some(format!(
+                "match {name}.duration_since(::std::time::UNIX_EPOCH) {{ \
+                 Ok(d) => d.as_secs() as i64, \
+                 Err(e) => -(e.duration().as_secs() as i64) \
+                 - if e.duration().subsec_nanos() > 0 {{ 1 }} else {{ 0 }} }}",
+                name = name
+            )),
+            TimeIntegration::Chrono => Some(format!("{}.timestamp()", name)),
+        }
+    }
+
+    /// Returns the expression converting the time `name` to a UTC `date_time_type`,
+    /// with the microseconds within the second added on top of the Unix time.
+    pub fn to_date_time(&self, name: &str, date_time_type: &str) -> Option<String> {
+        let secs_micros = match *self {
+            TimeIntegration::None => return None,
+            TimeIntegration::Std => format!(
+                "match {name}.duration_since(::std::time::UNIX_EPOCH) {{ \
+                 Ok(d) => (d.as_secs() as i64, i64::from(d.subsec_nanos() / 1000)), \
+                 Err(e) => {{ let d = e.duration(); let nanos = i64::from(d.subsec_nanos()); \
+                 if nanos == 0 {{ (-(d.as_secs() as i64), 0) }} \
+                 else {{ (-(d.as_secs() as i64) - 1, (1_000_000_000 - nanos) / 1000) }} }} }}",
+                name = name
+            ),
+            TimeIntegration::Chrono => format!(
+                "({name}.timestamp(), i64::from({name}.timestamp_subsec_micros()))",
+                name = name
+            ),
+        };
+        Some(format!(
+            "{{ let (secs, micros) = {}; {}::new_from_unix_utc(secs)\
+             .add(micros).expect(\"time out of the range of DateTime\") }}",
+            secs_micros,
+            date_time_type
+        ))
+    }
+}
+
+impl Default for TimeIntegration {
+    fn default() -> TimeIntegration {
+        TimeIntegration::None
+    }
+}
+
+impl FromStr for TimeIntegration {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(TimeIntegration::None),
+            "std" => Ok(TimeIntegration::Std),
+            "chrono" => Ok(TimeIntegration::Chrono),
+            e => Err(format!("Wrong time integration: \"{}\"", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_integration_from_str() {
+        assert_eq!(TimeIntegration::from_str("none"), Ok(TimeIntegration::None));
+        assert_eq!(TimeIntegration::from_str("std"), Ok(TimeIntegration::Std));
+        assert_eq!(TimeIntegration::from_str("chrono"), Ok(TimeIntegration::Chrono));
+        assert!(TimeIntegration::from_str("time").is_err());
+    }
+
+    #[test]
+    fn none_keeps_date_time() {
+        assert_eq!(TimeIntegration::None.rust_type(), None);
+        assert_eq!(TimeIntegration::None.from_date_time(), None);
+        assert_eq!(TimeIntegration::None.from_unix(), None);
+        assert_eq!(TimeIntegration::None.to_unix("t"), None);
+        assert_eq!(TimeIntegration::None.to_date_time("t", "glib::DateTime"), None);
+    }
+
+    #[test]
+    fn chrono_converts_to_date_time() {
+        assert_eq!(
+            TimeIntegration::Chrono.to_date_time("t", "glib::DateTime"),
+            Some(
+                "{ let (secs, micros) = (t.timestamp(), i64::from(t.timestamp_subsec_micros())); \
+                 glib::DateTime::new_from_unix_utc(secs).add(micros)\
+                 .expect(\"time out of the range of DateTime\") }"
+                    .to_owned()
+            )
+        );
+        assert_eq!(TimeIntegration::Chrono.to_unix("t"), Some("t.timestamp()".to_owned()));
+    }
+}
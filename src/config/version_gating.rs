@@ -0,0 +1,37 @@
+use std::str::FromStr;
+
+/// How items introduced in later versions are gated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VersionGating {
+    Dox,      // by the version feature, or the `dox` feature for docs
+    Features, // by the version feature only
+}
+
+impl Default for VersionGating {
+    fn default() -> VersionGating {
+        VersionGating::Dox
+    }
+}
+
+impl FromStr for VersionGating {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dox" => Ok(VersionGating::Dox),
+            "features" => Ok(VersionGating::Features),
+            e => Err(format!("Wrong version gating: \"{}\"", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_gating_from_str() {
+        assert_eq!(VersionGating::from_str("dox"), Ok(VersionGating::Dox));
+        assert_eq!(VersionGating::from_str("features"), Ok(VersionGating::Features));
+        assert!(VersionGating::from_str("cfg").is_err());
+    }
+}
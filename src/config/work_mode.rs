@@ -24,3 +24,16 @@ impl FromStr for WorkMode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn work_mode_from_str() {
+        assert_eq!(WorkMode::from_str("normal"), Ok(WorkMode::Normal));
+        assert_eq!(WorkMode::from_str("sys"), Ok(WorkMode::Sys));
+        assert_eq!(WorkMode::from_str("doc"), Ok(WorkMode::Doc));
+        assert!(WorkMode::from_str("ffi").is_err());
+    }
+}
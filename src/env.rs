@@ -43,4 +43,55 @@ impl Env {
             _ => false,
         }
     }
+
+    /// Checks whether an item introduced in `version` is above `max_cfg_version`
+    /// and must be omitted, reporting it if so.
+    pub fn is_too_new(&self, name: &str, version: Option<Version>) -> bool {
+        if exceeds_max_version(version, self.config.max_cfg_version) {
+            warn!(
+                "Omitting `{}` introduced in {}, above max_cfg_version",
+                name,
+                version.unwrap()
+            );
+            true
+        } else {
+            false
+        }
+    }
+}
+
+fn exceeds_max_version(version: Option<Version>, max_version: Option<Version>) -> bool {
+    match (version, max_version) {
+        (Some(version), Some(max_version)) => version > max_version,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_version_omits_newer_items() {
+        let max = Some(Version::Full(3, 10, 0));
+        let items = vec![
+            ("old", Some(Version::Full(3, 4, 0))),
+            ("same", Some(Version::Full(3, 10, 0))),
+            ("unversioned", None),
+            ("new", Some(Version::Full(3, 12, 0))),
+            ("new_patch", Some(Version::Full(3, 10, 1))),
+        ];
+        let kept: Vec<_> = items
+            .iter()
+            .filter(|&&(_, version)| !exceeds_max_version(version, max))
+            .map(|&(name, _)| name)
+            .collect();
+        assert_eq!(kept, vec!["old", "same", "unversioned"]);
+    }
+
+    #[test]
+    fn no_max_version_keeps_everything() {
+        assert!(!exceeds_max_version(Some(Version::Full(99, 0, 0)), None));
+        assert!(!exceeds_max_version(None, None));
+    }
 }
@@ -22,6 +22,9 @@ impl FromStr for Transfer {
             "none" => Ok(None),
             "container" => Ok(Container),
             "full" => Ok(Full),
+            // A floating reference is sunk by `from_glib_none` like a borrowed one is
+            // referenced, so the wrapper owns it either way
+            "floating" => Ok(None),
             _ => Err("Unknown ownership transfer mode".into()),
         }
     }
@@ -125,6 +128,32 @@ impl Default for Concurrency {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Stability {
+    Stable,
+    Unstable,
+    Private,
+}
+
+impl FromStr for Stability {
+    type Err = String;
+    fn from_str(name: &str) -> Result<Stability, String> {
+        use self::Stability::*;
+        match name {
+            "Stable" => Ok(Stable),
+            "Unstable" => Ok(Unstable),
+            "Private" => Ok(Private),
+            _ => Err(format!("Unknown stability \"{}\"", name)),
+        }
+    }
+}
+
+impl Default for Stability {
+    fn default() -> Stability {
+        Stability::Stable
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Fundamental {
     None,
@@ -224,6 +253,7 @@ pub struct Alias {
     pub c_identifier: String,
     pub typ: TypeId,
     pub target_c_type: String,
+    pub version: Option<Version>,
     pub doc: Option<String>,
 }
 
@@ -246,6 +276,7 @@ pub struct Member {
     pub c_identifier: String,
     pub value: String,
     pub doc: Option<String>,
+    pub nick: Option<String>,
 }
 
 #[derive(Debug)]
@@ -256,6 +287,7 @@ pub struct Enumeration {
     pub functions: Vec<Function>,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
     pub doc_deprecated: Option<String>,
     pub error_domain: Option<String>,
@@ -270,6 +302,7 @@ pub struct Bitfield {
     pub functions: Vec<Function>,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
     pub doc_deprecated: Option<String>,
     pub glib_get_type: Option<String>,
@@ -285,6 +318,7 @@ pub struct Record {
     pub functions: Vec<Function>,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
     pub doc_deprecated: Option<String>,
 }
@@ -322,6 +356,7 @@ pub struct Property {
     pub transfer: Transfer,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
     pub doc_deprecated: Option<String>,
 }
@@ -352,6 +387,7 @@ pub struct Function {
     pub throws: bool,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
     pub doc_deprecated: Option<String>,
 }
@@ -364,6 +400,7 @@ pub struct Signal {
     pub is_action: bool,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
     pub doc_deprecated: Option<String>,
 }
@@ -381,6 +418,7 @@ pub struct Interface {
     pub prerequisites: Vec<TypeId>,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
 }
 
@@ -399,7 +437,14 @@ pub struct Class {
     pub implements: Vec<TypeId>,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
+    /// A GType fundamental that isn't a GObject, e.g. `GParamSpec`
+    pub is_fundamental: bool,
+    pub ref_fn: Option<String>,
+    pub unref_fn: Option<String>,
+    /// Can't be subclassed, so parameters of this type don't need `IsA` bounds
+    pub final_type: bool,
 }
 
 macro_rules! impl_lexical_ord {
@@ -500,6 +545,32 @@ impl Type {
         }
     }
 
+    /// Only classes, interfaces, records, enumerations and flags are gated by stability.
+    pub fn stability(&self) -> Stability {
+        use self::Type::*;
+        match *self {
+            Enumeration(ref enum_) => enum_.stability,
+            Bitfield(ref bit_field) => bit_field.stability,
+            Record(ref rec) => rec.stability,
+            Interface(ref interface) => interface.stability,
+            Class(ref class) => class.stability,
+            _ => Stability::Stable,
+        }
+    }
+
+    pub fn functions(&self) -> &[Function] {
+        use self::Type::*;
+        match *self {
+            Enumeration(ref enum_) => &enum_.functions,
+            Bitfield(ref bit_field) => &bit_field.functions,
+            Record(ref rec) => &rec.functions,
+            Union(ref union) => &union.functions,
+            Interface(ref interface) => &interface.functions,
+            Class(ref class) => &class.functions,
+            _ => &[],
+        }
+    }
+
     pub fn c_array(library: &mut Library, inner: TypeId, size: Option<u16>) -> TypeId {
         if let Some(size) = size {
             library.add_type(
@@ -573,6 +644,7 @@ impl Type {
             functions: r.functions,
             version: r.version,
             deprecated_version: r.deprecated_version,
+            stability: r.stability,
             doc: r.doc,
             doc_deprecated: r.doc_deprecated,
         });
@@ -836,6 +908,22 @@ impl Library {
         }))
     }
 
+    /// Whether a C function, constant or type of this name was parsed.
+    pub fn has_symbol(&self, c_identifier: &str) -> bool {
+        self.namespaces.iter().any(|ns| {
+            let type_functions = ns.types
+                .iter()
+                .filter_map(|t| t.as_ref())
+                .flat_map(|t| t.functions());
+            ns.functions
+                .iter()
+                .chain(type_functions)
+                .any(|f| f.c_identifier.as_ref().map_or(false, |s| s == c_identifier)) ||
+                ns.constants.iter().any(|c| c.c_identifier == c_identifier) ||
+                ns.glib_name_index.contains_key(c_identifier)
+        })
+    }
+
     /// Types from a single namespace in alphabetical order.
     pub fn namespace_types<'a>(&'a self, ns_id: u16) -> Box<Iterator<Item = (TypeId, &Type)> + 'a> {
         let ns = self.namespace(ns_id);
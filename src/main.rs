@@ -39,6 +39,8 @@ mod library;
 mod library_postprocessing;
 mod nameutil;
 mod parser;
+#[cfg(test)]
+mod test_env;
 mod traits;
 mod update_version;
 mod version;
@@ -114,7 +116,7 @@ fn do_main() -> Result<()> {
     {
         let _watcher = statistics.enter("Namespace/symbol/class analysis");
 
-        let namespaces = analysis::namespaces::run(&library);
+        let namespaces = analysis::namespaces::run(&library, &cfg.ffi_crate_names);
         let symbols = analysis::symbols::run(&library, &namespaces);
         let class_hierarchy = analysis::class_hierarchy::run(&library);
 
@@ -130,7 +132,7 @@ fn do_main() -> Result<()> {
 
     {
         let _watcher = statistics.enter("Analysing");
-        analysis::run(&mut env);
+        try!(analysis::run(&mut env));
     }
 
     {
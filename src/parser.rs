@@ -217,6 +217,10 @@ impl Library {
         let type_struct = attrs
             .by_name("type-struct")
             .map(|s| s.to_owned());
+        let is_fundamental = attrs.by_name("fundamental") == Some("1");
+        let ref_fn = attrs.by_name("ref-func").map(|s| s.to_owned());
+        let unref_fn = attrs.by_name("unref-func").map(|s| s.to_owned());
+        let final_type = attrs.by_name("final") == Some("1");
         let get_type = try!(
             attrs
                 .by_name("get-type")
@@ -322,6 +326,11 @@ impl Library {
             doc: doc,
             version: version,
             deprecated_version: deprecated_version,
+            stability: parse_stability(attrs),
+            is_fundamental: is_fundamental,
+            ref_fn: ref_fn,
+            unref_fn: unref_fn,
+            final_type: final_type,
         });
         self.add_type(ns_id, class_name, typ);
         Ok(())
@@ -474,6 +483,7 @@ impl Library {
                     c_identifier: "GdkAtom".into(),
                     typ: tid,
                     target_c_type: "GdkAtom_*".into(),
+                    version: None,
                     doc: None, //TODO: temporary
                 }),
             );
@@ -488,6 +498,7 @@ impl Library {
             functions: fns,
             version: version,
             deprecated_version: deprecated_version,
+            stability: parse_stability(attrs),
             doc: doc,
             doc_deprecated: doc_deprecated,
         });
@@ -785,6 +796,7 @@ impl Library {
             doc: doc,
             version: version,
             deprecated_version: deprecated_version,
+            stability: parse_stability(attrs),
         });
         self.add_type(ns_id, interface_name, typ);
         Ok(())
@@ -840,6 +852,7 @@ impl Library {
             functions: fns,
             version: version,
             deprecated_version: deprecated_version,
+            stability: parse_stability(attrs),
             doc: doc,
             doc_deprecated: doc_deprecated,
             glib_get_type: get_type,
@@ -904,6 +917,7 @@ impl Library {
             functions: fns,
             version: version,
             deprecated_version: deprecated_version,
+            stability: parse_stability(attrs),
             doc: doc,
             doc_deprecated: doc_deprecated,
             error_domain: error_domain,
@@ -1013,6 +1027,7 @@ impl Library {
                 .by_name("type")
                 .ok_or_else(|| mk_error!("Missing c:type attribute", parser))
         );
+        let version = try!(self.parse_version(parser, ns_id, attrs.by_name("version")));
         let mut inner = None;
         let mut doc = None;
         loop {
@@ -1047,6 +1062,7 @@ impl Library {
                 c_identifier: c_identifier.into(),
                 typ: typ,
                 target_c_type: c_type.into(),
+                version: version,
                 doc: doc,
             });
             self.add_type(ns_id, alias_name, typ);
@@ -1068,6 +1084,7 @@ impl Library {
                 .ok_or_else(|| mk_error!("Missing member value", parser))
         );
         let c_identifier = attrs.by_name("identifier").map(|x| x.into());
+        let nick = attrs.by_name("nick").map(|x| x.into());
         let mut doc = None;
         loop {
             let event = try!(parser.next());
@@ -1096,6 +1113,7 @@ impl Library {
             value: value.into(),
             doc: doc,
             c_identifier: c_identifier.unwrap_or_else(|| member_name.into()),
+            nick: nick,
         })
     }
 
@@ -1115,13 +1133,13 @@ impl Library {
             .by_name("identifier")
             .or_else(|| attrs.by_name("type"));
         let kind = try!(FunctionKind::from_str(kind_str).map_err(|why| mk_error!(why, parser)));
-        let is_method = kind == FunctionKind::Method;
         let version = try!(self.parse_version(parser, ns_id, attrs.by_name("version")));
         let deprecated_version = try!(self.parse_version(
             parser,
             ns_id,
             attrs.by_name("deprecated-version"),
         ));
+        let stability = parse_stability(attrs);
         let mut params = Vec::new();
         let mut ret = None;
         let mut doc = None;
@@ -1135,7 +1153,7 @@ impl Library {
                     match name.local_name.as_ref() {
                         "parameters" => {
                             //params.append(&mut try!(self.read_parameters(parser, ns_id)));
-                            try!(self.read_parameters(parser, ns_id, false, is_method))
+                            try!(self.read_parameters(parser, ns_id, false))
                                 .into_iter()
                                 .map(|p| params.push(p))
                                 .count();
@@ -1149,8 +1167,7 @@ impl Library {
                                 ns_id,
                                 "return-value",
                                 &attributes,
-                                false,
-                                is_method
+                                false
                             )));
                         }
                         "doc" => doc = try!(read_text(parser)),
@@ -1163,6 +1180,13 @@ impl Library {
                 _ => xml_next!(event, parser),
             }
         }
+        let kind = method_kind(kind, &params);
+        if params.iter().any(|p| p.instance_parameter) {
+            // Array lengths are indices among the parameters after the instance parameter
+            for par in params.iter_mut().chain(ret.iter_mut()) {
+                par.array_length = par.array_length.map(|l| l + 1);
+            }
+        }
         let throws = attrs.by_name("throws").unwrap_or("") == "1";
         if throws {
             params.push(Parameter {
@@ -1190,6 +1214,7 @@ impl Library {
                 throws: throws,
                 version: version,
                 deprecated_version: deprecated_version,
+                stability: stability,
                 doc: doc,
                 doc_deprecated: doc_deprecated,
             })
@@ -1280,7 +1305,7 @@ impl Library {
                     name, attributes, ..
                 } => match name.local_name.as_ref() {
                     "parameters" => {
-                        try!(self.read_parameters(parser, ns_id, true, false))
+                        try!(self.read_parameters(parser, ns_id, true))
                             .into_iter()
                             .map(|p| params.push(p))
                             .count();
@@ -1294,8 +1319,7 @@ impl Library {
                             ns_id,
                             "return-value",
                             &attributes,
-                            true,
-                            false
+                            true
                         )));
                     }
                     "doc" => doc = try!(read_text(parser)),
@@ -1314,6 +1338,7 @@ impl Library {
                 is_action: is_action,
                 version: version,
                 deprecated_version: deprecated_version,
+                stability: parse_stability(attrs),
                 doc: doc,
                 doc_deprecated: doc_deprecated,
             })
@@ -1340,7 +1365,6 @@ impl Library {
         parser: &mut Reader,
         ns_id: u16,
         allow_no_ctype: bool,
-        for_method: bool,
     ) -> Result<Vec<Parameter>> {
         let mut params = Vec::new();
         loop {
@@ -1355,8 +1379,7 @@ impl Library {
                             ns_id,
                             kind,
                             &attributes,
-                            allow_no_ctype,
-                            for_method
+                            allow_no_ctype
                         ));
                         params.push(param);
                     }
@@ -1376,7 +1399,6 @@ impl Library {
         kind_str: &str,
         attrs: &Attributes,
         allow_no_ctype: bool,
-        for_method: bool,
     ) -> Result<Parameter> {
         let param_name = attrs.by_name("name").unwrap_or("");
         let instance_parameter = kind_str == "instance-parameter";
@@ -1428,10 +1450,7 @@ impl Library {
                 _ => xml_next!(event, parser),
             }
         }
-        if let Some((tid, c_type, mut array_length)) = typ {
-            if for_method {
-                array_length = array_length.map(|l| l + 1);
-            }
+        if let Some((tid, c_type, array_length)) = typ {
             Ok(Parameter {
                 name: param_name.into(),
                 typ: tid,
@@ -1556,6 +1575,7 @@ impl Library {
                 c_type: c_type,
                 version: version,
                 deprecated_version: deprecated_version,
+                stability: parse_stability(attrs),
                 doc: doc,
                 doc_deprecated: doc_deprecated,
             }))
@@ -1715,6 +1735,73 @@ fn make_file_name(dir: &Path, name: &str) -> PathBuf {
     path
 }
 
+/// Unset or unknown stability is treated as stable.
+fn parse_stability(attrs: &Attributes) -> Stability {
+    attrs
+        .by_name("stability")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_default()
+}
+
 fn to_bool(s: &str) -> bool {
     s == "1"
 }
+
+/// Whether a function is a method is decided by its `<instance-parameter>`,
+/// not by the element it's declared with.
+fn method_kind(kind: FunctionKind, params: &[Parameter]) -> FunctionKind {
+    let has_instance_parameter = params.iter().any(|p| p.instance_parameter);
+    match kind {
+        FunctionKind::Method if !has_instance_parameter => FunctionKind::Function,
+        FunctionKind::Function if has_instance_parameter => FunctionKind::Method,
+        kind => kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameter(name: &str, instance_parameter: bool) -> Parameter {
+        Parameter {
+            name: name.into(),
+            typ: TypeId::tid_none(),
+            c_type: "gpointer".into(),
+            instance_parameter: instance_parameter,
+            direction: ParameterDirection::In,
+            transfer: Transfer::None,
+            caller_allocates: false,
+            nullable: Nullable(false),
+            allow_none: false,
+            array_length: None,
+            is_error: false,
+            doc: None,
+        }
+    }
+
+    #[test]
+    fn instance_parameter_makes_a_method() {
+        let with_instance = vec![parameter("widget", true), parameter("name", false)];
+        let without_instance = vec![parameter("name", false)];
+        assert_eq!(
+            method_kind(FunctionKind::Method, &with_instance),
+            FunctionKind::Method
+        );
+        assert_eq!(
+            method_kind(FunctionKind::Function, &with_instance),
+            FunctionKind::Method
+        );
+        assert_eq!(
+            method_kind(FunctionKind::Method, &without_instance),
+            FunctionKind::Function
+        );
+        assert_eq!(
+            method_kind(FunctionKind::Function, &without_instance),
+            FunctionKind::Function
+        );
+        assert_eq!(
+            method_kind(FunctionKind::Constructor, &without_instance),
+            FunctionKind::Constructor
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! Analyzes a small GIR namespace with a config, so tests can check the
+//! generated code of real analysis results.
+
+use std::cell::RefCell;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use analysis;
+use codegen;
+use config::Config;
+use config::error::Result;
+use env::Env;
+use library::{Library, Type};
+use nameutil::module_name;
+use update_version;
+
+static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+const HEADER: &'static str = r#"<?xml version="1.0"?>
+<repository version="1.2"
+            xmlns="http://www.gtk.org/introspection/core/1.0"
+            xmlns:c="http://www.gtk.org/introspection/c/1.0"
+            xmlns:glib="http://www.gtk.org/introspection/glib/1.0">"#;
+
+const GLIB: &'static str = r#"
+  <package name="glib-2.0"/>
+  <namespace name="GLib" version="2.0" shared-library="libglib-2.0.so.0"
+             c:identifier-prefixes="G" c:symbol-prefixes="g">
+    <record name="Error" c:type="GError" glib:type-name="GError"
+            glib:get-type="g_error_get_type">
+    </record>
+    <record name="Bytes" c:type="GBytes" glib:type-name="GBytes"
+            glib:get-type="g_bytes_get_type">
+    </record>
+    <record name="DateTime" c:type="GDateTime" glib:type-name="GDateTime"
+            glib:get-type="g_date_time_get_type">
+    </record>
+    <record name="MainContext" c:type="GMainContext" glib:type-name="GMainContext"
+            glib:get-type="g_main_context_get_type">
+    </record>
+    <record name="Variant" c:type="GVariant" glib:type-name="GVariant"
+            glib:get-type="intern">
+    </record>
+    <record name="VariantType" c:type="GVariantType" glib:type-name="GVariantType"
+            glib:get-type="g_variant_type_get_gtype">
+    </record>
+  </namespace>
+</repository>
+"#;
+
+const GOBJECT: &'static str = r#"
+  <include name="GLib" version="2.0"/>
+  <package name="gobject-2.0"/>
+  <namespace name="GObject" version="2.0" shared-library="libgobject-2.0.so.0"
+             c:identifier-prefixes="G" c:symbol-prefixes="g">
+    <class name="Object" c:type="GObject" glib:type-name="GObject"
+           glib:get-type="g_object_get_type">
+    </class>
+    <class name="InitiallyUnowned" c:type="GInitiallyUnowned" parent="Object"
+           glib:type-name="GInitiallyUnowned" glib:get-type="g_initially_unowned_get_type">
+    </class>
+  </namespace>
+</repository>
+"#;
+
+const GIO: &'static str = r#"
+  <include name="GObject" version="2.0"/>
+  <package name="gio-2.0"/>
+  <namespace name="Gio" version="2.0" shared-library="libgio-2.0.so.0"
+             c:identifier-prefixes="G" c:symbol-prefixes="g">
+    <class name="Cancellable" c:type="GCancellable" parent="GObject.Object"
+           glib:type-name="GCancellable" glib:get-type="g_cancellable_get_type">
+    </class>
+  </namespace>
+</repository>
+"#;
+
+/// Analyzes the `Test-1.0` namespace holding the `gir` elements, with the
+/// `options` and `objects` of the config. `Test` is the main namespace.
+pub fn env(gir: &str, options: &str, objects: &str) -> Env {
+    try_env(gir, options, objects).unwrap()
+}
+
+/// Like `env`, but returns the errors of the analysis. The target path is an empty
+/// temporary directory, which tests writing files into it have to remove.
+pub fn try_env(gir: &str, options: &str, objects: &str) -> Result<Env> {
+    let dir = temp_dir();
+    write_gir(&dir, "GLib-2.0", GLIB);
+    write_gir(&dir, "GObject-2.0", GOBJECT);
+    write_gir(&dir, "Gio-2.0", GIO);
+    let test = format!(
+        r#"
+  <include name="GObject" version="2.0"/>
+  <include name="Gio" version="2.0"/>
+  <package name="test-1.0"/>
+  <namespace name="Test" version="1.0" shared-library="libtest-1.0.so.0"
+             c:identifier-prefixes="Test" c:symbol-prefixes="test">
+{}
+  </namespace>
+</repository>
+"#,
+        gir
+    );
+    write_gir(&dir, "Test-1.0", &test);
+
+    // `options` can select another work mode, like `work_mode = "sys"`
+    let work_mode = if options.contains("work_mode") {
+        ""
+    } else {
+        "work_mode = \"normal\"\n"
+    };
+    let toml = format!(
+        "[options]
+{}library = \"Test\"
+version = \"1.0\"
+target_path = \"{}\"
+{}
+{}",
+        work_mode,
+        dir.display(),
+        options,
+        objects
+    );
+    let mut config = Config::from_toml_str(&toml, &dir).unwrap();
+
+    let mut library = Library::new("Test");
+    library.read_file(&dir, "Test-1.0").unwrap();
+    library.postprocessing();
+    config.resolve_type_ids(&library);
+    update_version::check_function_real_version(&mut library);
+    let _ = fs::remove_dir_all(&dir);
+
+    let namespaces = analysis::namespaces::run(&library, &config.ffi_crate_names);
+    let symbols = analysis::symbols::run(&library, &namespaces);
+    let class_hierarchy = analysis::class_hierarchy::run(&library);
+    let mut env = Env {
+        library: library,
+        config: config,
+        namespaces: namespaces,
+        symbols: RefCell::new(symbols),
+        class_hierarchy: class_hierarchy,
+        analysis: Default::default(),
+    };
+    try!(analysis::run(&mut env));
+    Ok(env)
+}
+
+/// Generates the `Test.{name}` object, record, enumeration, flags or alias, or the
+/// global functions for `*`, and returns the file holding it. `objects` follows the
+/// `[[object]]` entry of `name`, e.g. with its functions or further objects. Like in
+/// all written files, tabs are replaced by spaces.
+pub fn generated_object(gir: &str, options: &str, name: &str, objects: &str) -> String {
+    let objects = format!(
+        "[[object]]\nname = \"Test.{}\"\nstatus = \"generate\"\n{}\n",
+        name,
+        objects
+    );
+    let env = env(gir, options, &objects);
+    let file = if name == "*" {
+        "functions".to_owned()
+    } else {
+        let tid = env.library.find_type(0, &format!("Test.{}", name)).unwrap();
+        match *env.type_(tid) {
+            Type::Enumeration(_) => "enums".to_owned(),
+            Type::Bitfield(_) => "flags".to_owned(),
+            Type::Alias(_) => "alias".to_owned(),
+            _ => module_name(name),
+        }
+    };
+    generated_code(&env, &format!("{}.rs", file))
+}
+
+/// Generates the crate and returns its `src/auto/{file}`, e.g. `mod.rs`.
+pub fn generated_file(gir: &str, options: &str, objects: &str, file: &str) -> String {
+    generated_code(&env(gir, options, objects), file)
+}
+
+fn generated_code(env: &Env, file: &str) -> String {
+    codegen::generate(env);
+    let root = &env.config.target_path;
+    let mut code = String::new();
+    File::open(root.join("src").join("auto").join(file))
+        .unwrap()
+        .read_to_string(&mut code)
+        .unwrap();
+    fs::remove_dir_all(root).unwrap();
+    code
+}
+
+/// Runs a code generator writing into a buffer.
+pub fn generated<F>(f: F) -> String
+where
+    F: FnOnce(&mut Write) -> ::std::io::Result<()>,
+{
+    let mut buf = Vec::new();
+    f(&mut buf).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+fn temp_dir() -> PathBuf {
+    let dir = ::std::env::temp_dir().join(format!(
+        "gir-test-{}-{}",
+        process::id(),
+        NEXT_DIR.fetch_add(1, Ordering::SeqCst)
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write_gir(dir: &PathBuf, name: &str, body: &str) {
+    let mut file = File::create(dir.join(format!("{}.gir", name))).unwrap();
+    file.write_all(HEADER.as_bytes()).unwrap();
+    file.write_all(body.as_bytes()).unwrap();
+}
@@ -18,6 +18,14 @@ impl Version {
         }
     }
 
+    /// Condition of an item available from `since` until it's removed in this version.
+    pub fn to_window_cfg(&self, since: Option<Version>) -> String {
+        match since {
+            Some(since) => format!("all({}, not({}))", since.to_cfg(), self.to_cfg()),
+            None => format!("not({})", self.to_cfg()),
+        }
+    }
+
     pub fn to_feature(&self) -> String {
         use self::Version::*;
         match *self {
@@ -86,6 +94,15 @@ mod tests {
         assert_eq!("1".parse(), Ok(Short(1)));
     }
 
+    #[test]
+    fn window_cfg() {
+        assert_eq!(
+            Full(3, 20, 0).to_window_cfg(Some(Full(3, 10, 0))),
+            "all(feature = \"v3_10\", not(feature = \"v3_20\"))"
+        );
+        assert_eq!(Full(3, 20, 0).to_window_cfg(None), "not(feature = \"v3_20\")");
+    }
+
     #[test]
     fn ord() {
         assert!(Full(0, 0, 0) < Full(1, 2, 3));
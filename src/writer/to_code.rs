@@ -116,11 +116,14 @@ impl ToCode for Chunk {
                 let s = format_block_one_line(&prefix, suffix, &value_strings, "", "");
                 vec![s]
             }
-            ErrorResultReturn { ref value } => {
+            ErrorResultReturn {
+                ref value,
+                ref error_type,
+            } => {
                 let value_strings = value.to_code(env);
                 let prefix = "if error.is_null() { Ok(";
-                let suffix = ") } else { Err(from_glib_full(error)) }";
-                let s = format_block_one_line(prefix, suffix, &value_strings, "", "");
+                let suffix = error_result_suffix(error_type.as_ref());
+                let s = format_block_one_line(prefix, &suffix, &value_strings, "", "");
                 vec![s]
             }
             AssertInitializedAndInMainThread => {
@@ -155,3 +158,34 @@ impl ToCode for [Chunk] {
         v
     }
 }
+
+/// A thrown `glib::Error` is converted into the configured error domain enum,
+/// errors of other domains become its `Failed` member.
+fn error_result_suffix(error_type: Option<&String>) -> String {
+    match error_type {
+        Some(error_type) => format!(
+            ") }} else {{ let error: Error = from_glib_full(error); \
+             Err(error.kind::<{0}>().unwrap_or({0}::Failed)) }}",
+            error_type
+        ),
+        None => ") } else { Err(from_glib_full(error)) }".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_result_with_error_type() {
+        assert_eq!(
+            error_result_suffix(Some(&"FileError".to_owned())),
+            ") } else { let error: Error = from_glib_full(error); \
+             Err(error.kind::<FileError>().unwrap_or(FileError::Failed)) }"
+        );
+        assert_eq!(
+            error_result_suffix(None),
+            ") } else { Err(from_glib_full(error)) }"
+        );
+    }
+}